@@ -0,0 +1,255 @@
+use std::fmt::Write;
+
+use mv3_contract_solana::state::{
+    accumulator::OutboundAccumulator,
+    chain::ChainConfig,
+    channel::ChannelState,
+    config::{ForeignAddress, MessageClient, MessengerConfig, RoleRegistry, UserPermission},
+    rate_limit::RateLimitConfig,
+    validators::ValidatorSet,
+};
+
+pub fn format_foreign_address(address: &ForeignAddress) -> String {
+    format!("0x{}", hex::encode(address))
+}
+
+fn format_optional_address(address: &Option<ForeignAddress>) -> String {
+    address
+        .as_ref()
+        .map(format_foreign_address)
+        .unwrap_or_else(|| "none".to_string())
+}
+
+fn format_permissions(permissions: &[UserPermission]) -> String {
+    let active: Vec<String> = permissions
+        .iter()
+        .filter(|permission| permission.is_active)
+        .map(|permission| permission.wallet.to_string())
+        .collect();
+
+    format!("[{}]", active.join(", "))
+}
+
+fn format_rate_limit(limit: &RateLimitConfig) -> String {
+    format!(
+        "{} messages / {} bytes per {}s",
+        limit.max_messages, limit.max_bytes, limit.window
+    )
+}
+
+pub fn format_config(config: &MessengerConfig, roles: &RoleRegistry) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "Owner:                   {}", config.owner).unwrap();
+    writeln!(out, "Accountant:              {}", config.accountant).unwrap();
+    writeln!(out, "Chain id:                {}", config.chain_id()).unwrap();
+    writeln!(out, "Next tx id:              {}", config.next_tx_id()).unwrap();
+    writeln!(out, "Messages sent:           {}", config.messages_sent()).unwrap();
+    writeln!(
+        out,
+        "Messages received:       {}",
+        config.messages_received()
+    )
+    .unwrap();
+    writeln!(out, "Bridge enabled:          {}", config.bridge_enabled()).unwrap();
+    writeln!(out, "Whitelist only:          {}", config.whitelist_only()).unwrap();
+    writeln!(
+        out,
+        "Chainsig:                {}",
+        format_optional_address(&config.chainsig())
+    )
+    .unwrap();
+    writeln!(out, "Digest scheme:           {:?}", config.digest_scheme()).unwrap();
+    writeln!(out, "BLS verification:        {}", config.bls_enabled()).unwrap();
+    writeln!(
+        out,
+        "Operators:               {}",
+        format_permissions(&roles.bridge_operators)
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "Supers:                  {}",
+        format_permissions(&roles.bridge_supers)
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "A-team:                  {}",
+        format_permissions(&roles.bridge_a_team)
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "Whitelist:               {}",
+        format_permissions(&roles.whitelists)
+    )
+    .unwrap();
+    writeln!(out, "Inbound paused:          {}", config.inbound_paused()).unwrap();
+    writeln!(out, "Outbound paused:         {}", config.outbound_paused()).unwrap();
+
+    out
+}
+
+pub fn format_chain(chain: &ChainConfig) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "Chain id:                {}", chain.chain_id).unwrap();
+    writeln!(out, "Name:                    {}", chain.name).unwrap();
+    writeln!(out, "Address format:          {:?}", chain.address_format).unwrap();
+    writeln!(out, "Enabled:                 {}", chain.enabled).unwrap();
+    writeln!(out, "Min. confirmations:      {}", chain.min_confirmations).unwrap();
+    writeln!(out, "Gas limit:               {}", chain.fees.gas_limit).unwrap();
+    writeln!(
+        out,
+        "Fees:                    {} + {} per byte",
+        chain.fees.base_fee, chain.fees.fee_per_byte
+    )
+    .unwrap();
+    writeln!(out, "Inbound paused:          {}", chain.inbound_paused).unwrap();
+    writeln!(out, "Outbound paused:         {}", chain.outbound_paused).unwrap();
+
+    if let Some(rate_limit) = &chain.rate_limit {
+        writeln!(
+            out,
+            "Rate limit:              {}",
+            format_rate_limit(rate_limit)
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+pub fn format_validator_set(validators: &ValidatorSet) -> String {
+    let mut out = String::new();
+
+    writeln!(
+        out,
+        "Threshold:               {} of {}",
+        validators.threshold,
+        validators.bls_keys.len()
+    )
+    .unwrap();
+
+    for (index, key) in validators.bls_keys.iter().enumerate() {
+        writeln!(
+            out,
+            "Validator {:<3}           0x{}",
+            index,
+            hex::encode(key)
+        )
+        .unwrap();
+    }
+
+    writeln!(
+        out,
+        "Ed25519 threshold:       {} of {}",
+        validators.ed25519_threshold,
+        validators.ed25519_keys.len()
+    )
+    .unwrap();
+
+    for (index, key) in validators.ed25519_keys.iter().enumerate() {
+        writeln!(out, "Ed25519 validator {:<3}   {}", index, key).unwrap();
+    }
+
+    out
+}
+
+pub fn format_accumulator(accumulator: &OutboundAccumulator) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "Messages:                {}", accumulator.count).unwrap();
+    writeln!(
+        out,
+        "Root:                    0x{}",
+        hex::encode(accumulator.root())
+    )
+    .unwrap();
+
+    out
+}
+
+pub fn format_channel(channel: &ChannelState) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "Source chain:            {}", channel.source_chain_id).unwrap();
+    writeln!(
+        out,
+        "Sender:                  {}",
+        format_foreign_address(&channel.remote_sender)
+    )
+    .unwrap();
+    writeln!(out, "Receiver:                {}", channel.local_program).unwrap();
+    writeln!(out, "Next sequence:           {}", channel.next_sequence).unwrap();
+
+    out
+}
+
+pub fn format_client(client: &MessageClient) -> String {
+    let mut out = String::new();
+
+    writeln!(
+        out,
+        "Destination contract:    {}",
+        client.destination_contract
+    )
+    .unwrap();
+    writeln!(out, "Authority:               {}", client.authority).unwrap();
+
+    if let Some(pending_authority) = client.pending_authority {
+        writeln!(out, "Pending authority:       {}", pending_authority).unwrap();
+    }
+
+    writeln!(out, "Paused:                  {}", client.paused).unwrap();
+    writeln!(out, "Notify on failure:       {}", client.notify_on_failure).unwrap();
+    writeln!(out, "Optimistic:              {}", client.optimistic).unwrap();
+    writeln!(out, "Ordered:                 {}", client.ordered).unwrap();
+    writeln!(
+        out,
+        "Supported chains:        {:?}",
+        client.supported_chains
+    )
+    .unwrap();
+
+    let allowed_contracts: Vec<String> = client
+        .allowed_contracts
+        .iter()
+        .map(format_foreign_address)
+        .collect();
+
+    writeln!(
+        out,
+        "Allowed contracts:       [{}]",
+        allowed_contracts.join(", ")
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "Exsig:                   {}",
+        format_optional_address(&client.exsig)
+    )
+    .unwrap();
+
+    if let Some(pending_exsig) = &client.pending_exsig {
+        writeln!(
+            out,
+            "Pending exsig:           {} (activates at {})",
+            format_optional_address(&pending_exsig.exsig),
+            pending_exsig.activates_at
+        )
+        .unwrap();
+    }
+
+    if let Some(rate_limit) = &client.rate_limit {
+        writeln!(
+            out,
+            "Rate limit:              {}",
+            format_rate_limit(rate_limit)
+        )
+        .unwrap();
+    }
+
+    out
+}
@@ -0,0 +1,4 @@
+pub mod display;
+pub mod parse;
+pub mod rpc;
+pub mod transaction;
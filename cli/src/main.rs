@@ -0,0 +1,614 @@
+use std::{error::Error, path::PathBuf};
+
+use clap::{Parser, Subcommand};
+use futures::executor::block_on;
+use mv3_cli::{
+    display::{
+        format_accumulator, format_chain, format_channel, format_client, format_config,
+        format_validator_set,
+    },
+    parse::{
+        parse_bls_key, parse_foreign_address, parse_hex, AccountKindArg, AddressFormatArg,
+        DigestSchemeArg, RoleArg,
+    },
+    rpc::RpcFetcher,
+    transaction::sign_partial,
+};
+use mv3_client::Gateway;
+use mv3_contract_solana::{
+    bls::BlsKey,
+    instruction::{
+        AddUserPermission, ChangeConfig, DepositWithdraw, FraudEvidence, RateLimitTarget,
+        RegisterChain, SetBlsValidators, SetEd25519Validators, SetExsig, SetOptimisticConfig,
+    },
+    state::{
+        account::AccountKind,
+        chain::ChainFees,
+        config::{ForeignAddress, MessageClient},
+    },
+    utils::keccak256,
+};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair},
+    signer::Signer,
+};
+
+#[derive(Parser)]
+#[command(name = "mv3-cli", about = "Operate a gateway deployment")]
+struct Cli {
+    /// RPC endpoint of the cluster
+    #[arg(long, short, global = true, default_value = "http://localhost:8899")]
+    url: String,
+
+    /// Keypair paying and signing transactions, defaults to ~/.config/solana/id.json
+    #[arg(long, short, global = true)]
+    keypair: Option<PathBuf>,
+
+    /// Gateway program of the deployment
+    #[arg(long, global = true, default_value_t = mv3_contract_solana::id())]
+    program_id: Pubkey,
+
+    /// Authority signing the instruction when it is not the keypair, e.g. a multisig
+    #[arg(long, global = true)]
+    authority: Option<Pubkey>,
+
+    /// Print the transaction signed by the keypair instead of sending it
+    #[arg(long, global = true, requires = "blockhash")]
+    sign_only: bool,
+
+    /// Blockhash of offline transactions
+    #[arg(long, global = true)]
+    blockhash: Option<Hash>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create the config, the keypair becomes its owner
+    InitConfig {
+        #[arg(long)]
+        accountant: Pubkey,
+        /// ID of the chain the gateway is deployed on
+        #[arg(long)]
+        chain_id: u64,
+    },
+    /// Change config fields, omitted fields keep their value
+    ChangeConfig {
+        #[arg(long)]
+        bridge_enabled: Option<bool>,
+        #[arg(long)]
+        accountant: Option<Pubkey>,
+        #[arg(long)]
+        whitelist_only: Option<bool>,
+        #[arg(long, value_parser = parse_foreign_address)]
+        chainsig: Option<ForeignAddress>,
+        /// Hash the signers of inbound messages sign
+        #[arg(long, value_enum)]
+        digest_scheme: Option<DigestSchemeArg>,
+    },
+    /// Register a chain or replace its entry, pauses and rate limit are kept
+    RegisterChain {
+        chain_id: u64,
+        #[arg(long)]
+        name: String,
+        #[arg(long, value_enum)]
+        address_format: AddressFormatArg,
+        #[arg(long, default_value_t = 0)]
+        min_confirmations: u16,
+        /// Gas the relayer executes messages with on the chain
+        #[arg(long, default_value_t = 0)]
+        gas_limit: u64,
+        /// Lamports charged per message sent to the chain
+        #[arg(long, default_value_t = 0)]
+        base_fee: u64,
+        /// Lamports charged per payload byte sent to the chain
+        #[arg(long, default_value_t = 0)]
+        fee_per_byte: u64,
+        /// Reject messages from and to the chain
+        #[arg(long)]
+        disabled: bool,
+    },
+    /// Write BLS validator keys from an offset on, dropping the keys after them. Sets too large
+    /// for one transaction are written in several calls, all but the last with --disabled.
+    SetBlsValidators {
+        /// Hex encoded G1 keys, each followed by its G2 key and proof of possession
+        #[arg(long = "key", value_parser = parse_bls_key)]
+        keys: Vec<BlsKey>,
+        /// Signers a message needs at least
+        #[arg(long)]
+        threshold: u16,
+        /// Index of the first key written
+        #[arg(long, default_value_t = 0)]
+        offset: u16,
+        /// Verify inbound messages with the chainsig instead
+        #[arg(long)]
+        disabled: bool,
+    },
+    /// Replace the Ed25519 validator keys, inbound messages then need their signatures
+    SetEd25519Validators {
+        /// Ed25519 public keys, none stop checking Ed25519 signatures
+        #[arg(long = "key")]
+        keys: Vec<Pubkey>,
+        /// Signers a message needs at least
+        #[arg(long, default_value_t = 0)]
+        threshold: u16,
+    },
+    /// Grant or revoke a role
+    SetRole {
+        user: Pubkey,
+        #[arg(value_enum)]
+        role: RoleArg,
+        #[arg(long)]
+        revoke: bool,
+    },
+    /// Register or update the message client of a program
+    ConfigureClient {
+        program: Pubkey,
+        /// Defaults to the signing authority
+        #[arg(long)]
+        client_authority: Option<Pubkey>,
+        #[arg(long, value_delimiter = ',')]
+        supported_chains: Vec<u64>,
+        #[arg(long, value_delimiter = ',', value_parser = parse_foreign_address)]
+        allowed_contracts: Vec<ForeignAddress>,
+        #[arg(long, value_parser = parse_foreign_address)]
+        exsig: Option<ForeignAddress>,
+        #[arg(long)]
+        notify_on_failure: bool,
+    },
+    /// Set the exsig of a client, an omitted exsig removes it
+    SetExsig {
+        program: Pubkey,
+        #[arg(long, value_parser = parse_foreign_address)]
+        exsig: Option<ForeignAddress>,
+        /// Seconds until the exsig replaces the current one
+        #[arg(long, default_value_t = 0)]
+        delay: i64,
+    },
+    /// Deposit lamports into the treasury of a client
+    Deposit { program: Pubkey, lamports: u64 },
+    /// Withdraw lamports from the treasury of a client
+    Withdraw { program: Pubkey, lamports: u64 },
+    /// Set the challenge period, minimum operator bond and watchers of optimistic delivery
+    SetOptimisticConfig {
+        /// Seconds a commitment can be challenged for
+        #[arg(long)]
+        challenge_period: i64,
+        /// Lamports an operator has to keep bonded
+        #[arg(long)]
+        min_bond: u64,
+        #[arg(long = "watcher")]
+        watchers: Vec<Pubkey>,
+    },
+    /// Deliver messages to the client of a program on operator commitments
+    SetClientOptimistic {
+        program: Pubkey,
+        /// Verify messages to the client with validator signatures again
+        #[arg(long)]
+        disabled: bool,
+    },
+    /// Opt a client in to ordered channels, whose messages carry a sequence number after the
+    /// destination program
+    SetClientOrdered {
+        program: Pubkey,
+        /// Execute messages to the client in any order again
+        #[arg(long)]
+        disabled: bool,
+    },
+    /// Bond lamports of the signing operator
+    DepositBond { lamports: u64 },
+    /// Withdraw bonded lamports of the signing operator
+    WithdrawBond { lamports: u64 },
+    /// Challenge the commitment of a message with the message the validators signed under its
+    /// tx ID, slashing the bond of its operator
+    ChallengeCommitment {
+        tx_id: u128,
+        /// Operator who posted the commitment
+        #[arg(long)]
+        operator: Pubkey,
+        /// Source chain of the signed message
+        #[arg(long)]
+        source_chain: u64,
+        #[arg(long, value_parser = parse_foreign_address)]
+        sender: ForeignAddress,
+        #[arg(long)]
+        receiver: Pubkey,
+        /// Hex encoded payload of the signed message
+        #[arg(long, value_parser = parse_hex)]
+        payload: ::std::vec::Vec<u8>,
+        /// Hex encoded BLS proof or chainsig signature of the signed message
+        #[arg(long, value_parser = parse_hex)]
+        signature: ::std::vec::Vec<u8>,
+    },
+    /// Dispute the commitment of a message as a watcher, blocking it until the dispute is
+    /// settled
+    DisputeCommitment {
+        tx_id: u128,
+        /// Operator who posted the commitment
+        #[arg(long)]
+        operator: Pubkey,
+    },
+    /// Settle the dispute of a commitment, slashing the bond of its operator unless the
+    /// signature of the committed message is given
+    SettleDispute {
+        tx_id: u128,
+        /// Operator who posted the commitment
+        #[arg(long)]
+        operator: Pubkey,
+        /// Hex encoded BLS proof or chainsig signature of the committed message
+        #[arg(long, value_parser = parse_hex)]
+        signature: Option<::std::vec::Vec<u8>>,
+    },
+    /// Prefix an account written before discriminators with its discriminator, migrate the
+    /// config first
+    MigrateAccount {
+        #[arg(value_enum)]
+        kind: AccountKindArg,
+        /// Defaults to the config, or the rate limit account of the given chain or client
+        account: Option<Pubkey>,
+        /// Chain limited by the rate limit account
+        #[arg(long, conflicts_with = "client")]
+        chain: Option<u64>,
+        /// Program whose client is limited by the rate limit account
+        #[arg(long)]
+        client: Option<Pubkey>,
+    },
+    /// Upgrade the config and the clients of the given programs to the current layout
+    MigrateConfig {
+        /// Programs whose message clients are upgraded along with the config
+        #[arg(long = "client")]
+        clients: Vec<Pubkey>,
+    },
+    /// Print the config
+    InspectConfig,
+    /// Print the message client of a program
+    InspectClient { program: Pubkey },
+    /// Print the registry entry of a chain
+    InspectChain { chain_id: u64 },
+    /// Print the BLS validator set
+    InspectValidators,
+    /// Print the message count and root of the outbound message accumulator
+    InspectAccumulator,
+    /// Print the next sequence number of the ordered channel of a sender to a program
+    InspectChannel {
+        chain_id: u64,
+        #[arg(value_parser = parse_foreign_address)]
+        sender: ForeignAddress,
+        program: Pubkey,
+    },
+}
+
+fn default_keypair_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_default();
+
+    PathBuf::from(home).join(".config/solana/id.json")
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+
+    let rpc = RpcClient::new(cli.url.clone());
+    let gateway = Gateway {
+        program_id: cli.program_id,
+        ..Gateway::default()
+    };
+
+    match &cli.command {
+        Command::InspectConfig => {
+            let fetcher = RpcFetcher(&rpc);
+
+            let config = block_on(gateway.get_config(&fetcher))?;
+            let roles = block_on(gateway.get_roles(&fetcher))?;
+
+            print!("{}", format_config(&config, &roles));
+
+            return Ok(());
+        }
+        Command::InspectClient { program } => {
+            let client = block_on(gateway.get_message_client(&RpcFetcher(&rpc), program))?;
+
+            print!("{}", format_client(&client));
+
+            return Ok(());
+        }
+        Command::InspectChain { chain_id } => {
+            let chain = block_on(gateway.get_chain(&RpcFetcher(&rpc), *chain_id))?;
+
+            print!("{}", format_chain(&chain));
+
+            return Ok(());
+        }
+        Command::InspectValidators => {
+            let validators = block_on(gateway.get_validator_set(&RpcFetcher(&rpc)))?;
+
+            print!("{}", format_validator_set(&validators));
+
+            return Ok(());
+        }
+        Command::InspectAccumulator => {
+            let accumulator = block_on(gateway.get_accumulator(&RpcFetcher(&rpc)))?;
+
+            print!("{}", format_accumulator(&accumulator));
+
+            return Ok(());
+        }
+        Command::InspectChannel {
+            chain_id,
+            sender,
+            program,
+        } => {
+            let channel =
+                block_on(gateway.get_channel(&RpcFetcher(&rpc), *chain_id, sender, program))?;
+
+            print!("{}", format_channel(&channel));
+
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let keypair_path = cli.keypair.clone().unwrap_or_else(default_keypair_path);
+    let keypair = read_keypair_file(&keypair_path)
+        .map_err(|err| format!("Failed to read {}: {}", keypair_path.display(), err))?;
+
+    let authority = cli.authority.unwrap_or_else(|| keypair.pubkey());
+
+    let instruction = match cli.command {
+        //the chains enabled by a legacy config name the registry accounts the migration creates
+        Command::MigrateConfig { clients } => {
+            let chain_ids = block_on(gateway.get_legacy_chain_ids(&RpcFetcher(&rpc)))?;
+
+            gateway.migrate_config(&authority, &chain_ids, &clients)
+        }
+        Command::MigrateAccount {
+            kind,
+            account,
+            chain,
+            client,
+        } => {
+            let rate_limit = chain
+                .map(RateLimitTarget::Chain)
+                .or(client.map(RateLimitTarget::Client));
+
+            let kind = kind.account_kind(rate_limit)?;
+
+            let account = match (account, kind) {
+                (Some(account), _) => account,
+                (None, AccountKind::RateLimit(RateLimitTarget::Chain(chain_id))) => {
+                    gateway.chain_rate_limit_address(chain_id).0
+                }
+                (None, AccountKind::RateLimit(RateLimitTarget::Client(program))) => {
+                    gateway.client_rate_limit_address(&program).0
+                }
+                (None, _) => gateway.config_address().0,
+            };
+
+            gateway.migrate_account(&authority, &account, kind)
+        }
+        command => build_instruction(&gateway, &authority, command),
+    };
+
+    submit(&rpc, &keypair, &[instruction], cli.sign_only, cli.blockhash)
+}
+
+fn build_instruction(gateway: &Gateway, authority: &Pubkey, command: Command) -> Instruction {
+    match command {
+        Command::InitConfig {
+            accountant,
+            chain_id,
+        } => gateway.initialize_config(authority, accountant, chain_id),
+        Command::ChangeConfig {
+            bridge_enabled,
+            accountant,
+            whitelist_only,
+            chainsig,
+            digest_scheme,
+        } => gateway.change_config(
+            authority,
+            ChangeConfig {
+                bridge_enabled,
+                accountant,
+                whitelist_only,
+                chainsig,
+                digest_scheme: digest_scheme.map(Into::into),
+            },
+        ),
+        Command::RegisterChain {
+            chain_id,
+            name,
+            address_format,
+            min_confirmations,
+            gas_limit,
+            base_fee,
+            fee_per_byte,
+            disabled,
+        } => gateway.register_chain(
+            authority,
+            RegisterChain {
+                chain_id,
+                name,
+                address_format: address_format.into(),
+                min_confirmations,
+                fees: ChainFees {
+                    gas_limit,
+                    base_fee,
+                    fee_per_byte,
+                },
+                enabled: !disabled,
+            },
+        ),
+        Command::SetBlsValidators {
+            keys,
+            threshold,
+            offset,
+            disabled,
+        } => gateway.set_bls_validators(
+            authority,
+            SetBlsValidators {
+                offset,
+                keys,
+                threshold,
+                enabled: !disabled,
+            },
+        ),
+        Command::SetEd25519Validators { keys, threshold } => {
+            gateway.set_ed25519_validators(authority, SetEd25519Validators { keys, threshold })
+        }
+        Command::SetRole { user, role, revoke } => gateway.add_user_permission(
+            authority,
+            AddUserPermission {
+                user,
+                is_active: !revoke,
+                role: role.into(),
+            },
+        ),
+        Command::ConfigureClient {
+            program,
+            client_authority,
+            supported_chains,
+            allowed_contracts,
+            exsig,
+            notify_on_failure,
+        } => gateway.configure_client(
+            authority,
+            MessageClient {
+                version: MessageClient::VERSION,
+                authority: client_authority.unwrap_or(*authority),
+                destination_contract: program,
+                notify_on_failure,
+                supported_chains,
+                allowed_contracts,
+                exsig,
+                paused: false,
+                pending_authority: None,
+                pending_exsig: None,
+                rate_limit: None,
+                optimistic: false,
+                ordered: false,
+            },
+        ),
+        Command::SetExsig {
+            program,
+            exsig,
+            delay,
+        } => gateway.set_exsig(
+            authority,
+            &program,
+            SetExsig {
+                exsig,
+                activation_delay: delay,
+            },
+        ),
+        Command::Deposit { program, lamports } => {
+            gateway.deposit_withdraw(authority, &program, DepositWithdraw::Deposit, lamports)
+        }
+        Command::Withdraw { program, lamports } => {
+            gateway.deposit_withdraw(authority, &program, DepositWithdraw::Withdraw, lamports)
+        }
+        Command::SetOptimisticConfig {
+            challenge_period,
+            min_bond,
+            watchers,
+        } => gateway.set_optimistic_config(
+            authority,
+            SetOptimisticConfig {
+                challenge_period,
+                min_bond,
+                watchers,
+            },
+        ),
+        Command::SetClientOptimistic { program, disabled } => {
+            gateway.set_client_optimistic(authority, program, !disabled)
+        }
+        Command::SetClientOrdered { program, disabled } => {
+            gateway.set_client_ordered(authority, program, !disabled)
+        }
+        Command::DepositBond { lamports } => {
+            gateway.deposit_withdraw_bond(authority, DepositWithdraw::Deposit, lamports)
+        }
+        Command::WithdrawBond { lamports } => {
+            gateway.deposit_withdraw_bond(authority, DepositWithdraw::Withdraw, lamports)
+        }
+        Command::ChallengeCommitment {
+            tx_id,
+            operator,
+            source_chain,
+            sender,
+            receiver,
+            payload,
+            signature,
+        } => gateway.challenge_commitment(
+            authority,
+            &operator,
+            tx_id,
+            FraudEvidence {
+                source_chain_id: source_chain,
+                sender,
+                receiver,
+                payload_hash: keccak256(&payload),
+                signature,
+            },
+        ),
+        Command::DisputeCommitment { tx_id, operator } => {
+            gateway.dispute_commitment(authority, &operator, tx_id)
+        }
+        Command::SettleDispute {
+            tx_id,
+            operator,
+            signature,
+        } => gateway.settle_dispute(&operator, tx_id, signature),
+        Command::MigrateConfig { .. }
+        | Command::MigrateAccount { .. }
+        | Command::InspectConfig
+        | Command::InspectClient { .. }
+        | Command::InspectChain { .. }
+        | Command::InspectValidators
+        | Command::InspectAccumulator
+        | Command::InspectChannel { .. } => unreachable!(),
+    }
+}
+
+fn submit(
+    rpc: &RpcClient,
+    keypair: &Keypair,
+    instructions: &[Instruction],
+    sign_only: bool,
+    blockhash: Option<Hash>,
+) -> Result<(), Box<dyn Error>> {
+    let blockhash = match blockhash {
+        Some(blockhash) => blockhash,
+        None => rpc.get_latest_blockhash()?,
+    };
+
+    let signed = sign_partial(instructions, keypair, blockhash);
+
+    if sign_only {
+        println!("{}", signed.encode());
+
+        for signer in &signed.missing_signers {
+            eprintln!("Missing signature of {}", signer);
+        }
+
+        return Ok(());
+    }
+
+    if !signed.missing_signers.is_empty() {
+        return Err(format!(
+            "Missing signatures of {:?}, use --sign-only",
+            signed.missing_signers
+        )
+        .into());
+    }
+
+    let signature = rpc.send_and_confirm_transaction(&signed.transaction)?;
+
+    println!("{}", signature);
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use clap::ValueEnum;
+use mv3_client::bls::BlsPublicKey;
+use mv3_contract_solana::{
+    bls::BlsKey,
+    instruction::RateLimitTarget,
+    state::{
+        account::AccountKind,
+        address::TypedAddress,
+        chain::AddressFormat,
+        config::{DigestScheme, ForeignAddress, Role},
+    },
+};
+
+/// Parses an address of another chain: 0x prefixed or plain hex of a 20 byte EVM address, whose
+/// EIP-55 checksum is checked when it is mixed case, or of a 32 byte address, otherwise a base58
+/// Solana address. EVM addresses are left padded with zeros the same way the gateway stores them.
+pub fn parse_foreign_address(value: &str) -> Result<ForeignAddress, String> {
+    let hex_address = value.trim_start_matches("0x");
+
+    let is_hex = hex_address.chars().all(|c| c.is_ascii_hexdigit());
+
+    let format = match hex_address.len() {
+        40 if is_hex => AddressFormat::Evm,
+        64 if is_hex => AddressFormat::Bytes32,
+        _ => AddressFormat::Solana,
+    };
+
+    TypedAddress::parse(format, value)
+        .map(|address| address.to_foreign())
+        .map_err(|_| {
+            format!(
+                "Expected a 20 or 32 byte hex or a base58 address, got {}",
+                value
+            )
+        })
+}
+
+/// Parses a BN254 validator key with its proof of possession, the 0x prefixed or plain hex of the
+/// uncompressed G1 key, G2 key and signature over `possession_message`, `x || y` each. Keys whose
+/// halves differ or whose signature does not verify are rejected like the gateway does.
+pub fn parse_bls_key(value: &str) -> Result<BlsKey, String> {
+    let mut bytes = [0; 64 + 128 + 64];
+
+    hex::decode_to_slice(value.trim_start_matches("0x"), &mut bytes)
+        .map_err(|_| format!("Expected 256 hex encoded bytes, got {}", value))?;
+
+    let key = BlsPublicKey {
+        g1: bytes[..64].try_into().unwrap(),
+        g2: bytes[64..192].try_into().unwrap(),
+    };
+
+    key.with_possession(bytes[192..].try_into().unwrap())
+        .map_err(|error| format!("{}: {}", error, value))
+}
+
+/// Parses 0x prefixed or plain hex bytes, e.g. a payload or a signature
+pub fn parse_hex(value: &str) -> Result<Vec<u8>, String> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(|_| format!("Expected hex encoded bytes, got {}", value))
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoleArg {
+    Operator,
+    ATeam,
+    Super,
+    Whitelist,
+    Accountant,
+}
+
+impl From<RoleArg> for Role {
+    fn from(role: RoleArg) -> Self {
+        match role {
+            RoleArg::Operator => Role::Operator,
+            RoleArg::ATeam => Role::ATeam,
+            RoleArg::Super => Role::Super,
+            RoleArg::Whitelist => Role::Whitelist,
+            RoleArg::Accountant => Role::Accountant,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressFormatArg {
+    Evm,
+    Bytes32,
+    Solana,
+}
+
+impl From<AddressFormatArg> for AddressFormat {
+    fn from(format: AddressFormatArg) -> Self {
+        match format {
+            AddressFormatArg::Evm => AddressFormat::Evm,
+            AddressFormatArg::Bytes32 => AddressFormat::Bytes32,
+            AddressFormatArg::Solana => AddressFormat::Solana,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DigestSchemeArg {
+    PersonalSign,
+    Eip712,
+}
+
+impl From<DigestSchemeArg> for DigestScheme {
+    fn from(scheme: DigestSchemeArg) -> Self {
+        match scheme {
+            DigestSchemeArg::PersonalSign => DigestScheme::PersonalSign,
+            DigestSchemeArg::Eip712 => DigestScheme::Eip712,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccountKindArg {
+    Config,
+    MessageClient,
+    Message,
+    ReceivedMessage,
+    RateLimit,
+}
+
+impl AccountKindArg {
+    /// Kind of the account, rate limit accounts are told apart by the chain or client they limit
+    pub fn account_kind(self, rate_limit: Option<RateLimitTarget>) -> Result<AccountKind, String> {
+        let kind = match self {
+            AccountKindArg::Config => AccountKind::Config,
+            AccountKindArg::MessageClient => AccountKind::MessageClient,
+            AccountKindArg::Message => AccountKind::Message,
+            AccountKindArg::ReceivedMessage => AccountKind::ReceivedMessage,
+            AccountKindArg::RateLimit => AccountKind::RateLimit(
+                rate_limit.ok_or("Rate limit accounts need --chain or --client")?,
+            ),
+        };
+
+        Ok(kind)
+    }
+}
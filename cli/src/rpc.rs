@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+use mv3_client::{error::ClientError, fetcher::AccountFetcher};
+use solana_program::pubkey::Pubkey;
+use solana_rpc_client::rpc_client::RpcClient;
+
+pub struct RpcFetcher<'a>(pub &'a RpcClient);
+
+#[async_trait]
+impl AccountFetcher for RpcFetcher<'_> {
+    async fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError> {
+        let account = self
+            .0
+            .get_account_with_commitment(address, self.0.commitment())
+            .map_err(|err| ClientError::FetchFailed(*address, err.to_string()))?;
+
+        Ok(account.value.map(|account| account.data))
+    }
+}
@@ -0,0 +1,44 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_sdk::{
+    hash::Hash, instruction::Instruction, message::Message, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, transaction::Transaction,
+};
+
+/// Transaction signed by the fee payer only, `missing_signers` still have to sign it
+pub struct PartiallySigned {
+    pub transaction: Transaction,
+    pub missing_signers: Vec<Pubkey>,
+}
+
+impl PartiallySigned {
+    /// Base64 of the bincode encoded transaction, the wire format of `sendTransaction`
+    pub fn encode(&self) -> String {
+        STANDARD.encode(bincode::serialize(&self.transaction).unwrap())
+    }
+}
+
+pub fn sign_partial(
+    instructions: &[Instruction],
+    fee_payer: &Keypair,
+    blockhash: Hash,
+) -> PartiallySigned {
+    let message = Message::new(instructions, Some(&fee_payer.pubkey()));
+
+    let mut transaction = Transaction::new_unsigned(message);
+
+    let signer_keys = &transaction.message.account_keys
+        [..usize::from(transaction.message.header.num_required_signatures)];
+
+    let missing_signers = signer_keys
+        .iter()
+        .filter(|key| **key != fee_payer.pubkey())
+        .copied()
+        .collect();
+
+    transaction.partial_sign(&[fee_payer], blockhash);
+
+    PartiallySigned {
+        transaction,
+        missing_signers,
+    }
+}
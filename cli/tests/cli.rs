@@ -0,0 +1,143 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use mv3_cli::{
+    display::{format_chain, format_config},
+    parse::{parse_bls_key, parse_foreign_address, parse_hex},
+    transaction::sign_partial,
+};
+use mv3_client::{bls::BlsSecretKey, Gateway};
+use mv3_contract_solana::{
+    bls::G1_GENERATOR,
+    instruction::ChangeConfig,
+    state::{
+        chain::{AddressFormat, ChainConfig},
+        config::{MessengerConfig, RoleRegistry},
+    },
+};
+use solana_sdk::{
+    hash::Hash, pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction,
+};
+
+#[test]
+pub fn test_parse_foreign_address() {
+    let evm = parse_foreign_address("0x52908400098527886E0F7030069857D2E4169EE7").unwrap();
+
+    assert_eq!(evm[..12], [0; 12]);
+    assert_eq!(evm[12], 0x52);
+
+    let full = parse_foreign_address(&"ab".repeat(32)).unwrap();
+
+    assert_eq!(full, [0xab; 32]);
+
+    let solana = Pubkey::new_unique();
+
+    assert_eq!(
+        parse_foreign_address(&solana.to_string()).unwrap(),
+        solana.to_bytes()
+    );
+
+    //mixed case EVM addresses carry a checksum
+    assert!(parse_foreign_address("0x52908400098527886e0F7030069857D2E4169EE7").is_err());
+    assert!(parse_foreign_address("0x1234").is_err());
+    assert!(parse_foreign_address("not hex").is_err());
+}
+
+#[test]
+pub fn test_parse_bls_key() {
+    let validator = BlsSecretKey::from_seed(b"validator");
+    let key = validator.public_key();
+    let possession = validator.sign_possession();
+
+    let encoded = format!(
+        "0x{}{}{}",
+        hex::encode(key.g1),
+        hex::encode(key.g2),
+        hex::encode(possession)
+    );
+
+    assert_eq!(
+        parse_bls_key(&encoded).unwrap(),
+        key.with_possession(&possession).unwrap()
+    );
+
+    //the signature over the key of another validator
+    let other = BlsSecretKey::from_seed(b"other").sign_possession();
+
+    assert!(parse_bls_key(&format!("{}{}", &encoded[..2 + 384], hex::encode(other))).is_err());
+
+    let mut off_curve = G1_GENERATOR;
+    off_curve[63] = 3;
+
+    assert!(parse_bls_key(&format!(
+        "{}{}",
+        hex::encode(off_curve),
+        &encoded[2 + 128..]
+    ))
+    .is_err());
+    assert!(parse_bls_key(&hex::encode(G1_GENERATOR)).is_err());
+    assert!(parse_bls_key("0x1234").is_err());
+}
+
+#[test]
+pub fn test_parse_hex() {
+    assert_eq!(parse_hex("0x0102ff").unwrap(), vec![1, 2, 0xff]);
+    assert_eq!(parse_hex("").unwrap(), Vec::<u8>::new());
+    assert!(parse_hex("0x123").is_err());
+    assert!(parse_hex("zz").is_err());
+}
+
+#[test]
+pub fn test_sign_only_with_multisig_authority() {
+    let gateway = Gateway::default();
+
+    let fee_payer = Keypair::new();
+    let authority = Pubkey::new_unique();
+
+    let ix = gateway.change_config(
+        &authority,
+        ChangeConfig {
+            bridge_enabled: Some(true),
+            accountant: None,
+            whitelist_only: None,
+            chainsig: None,
+            digest_scheme: None,
+        },
+    );
+
+    let signed = sign_partial(&[ix], &fee_payer, Hash::new_unique());
+
+    assert_eq!(signed.missing_signers, vec![authority]);
+
+    let decoded: Transaction =
+        bincode::deserialize(&STANDARD.decode(signed.encode()).unwrap()).unwrap();
+
+    assert_eq!(decoded, signed.transaction);
+    assert_eq!(decoded.message.account_keys[0], fee_payer.pubkey());
+    assert!(decoded.verify_with_results()[0]);
+}
+
+#[test]
+pub fn test_format_config() {
+    let owner = Pubkey::new_unique();
+
+    let mut config = MessengerConfig::new(&owner, &owner, 1);
+    config.set_chainsig(Some([1; 32]));
+
+    let formatted = format_config(&config, &RoleRegistry::default());
+
+    assert!(formatted.contains(&owner.to_string()));
+    assert!(formatted.contains("Chain id:                1"));
+    assert!(formatted.contains(&format!("0x{}", "01".repeat(32))));
+}
+
+#[test]
+pub fn test_format_chain() {
+    let mut chain = ChainConfig::new(5, "goerli", AddressFormat::Evm);
+    chain.fees.base_fee = 1000;
+    chain.fees.fee_per_byte = 10;
+
+    let formatted = format_chain(&chain);
+
+    assert!(formatted.contains("Name:                    goerli"));
+    assert!(formatted.contains("Address format:          Evm"));
+    assert!(formatted.contains("Fees:                    1000 + 10 per byte"));
+}
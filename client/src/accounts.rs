@@ -0,0 +1,281 @@
+use std::io;
+
+use borsh::BorshDeserialize;
+use mv3_contract_solana::{
+    error::MessengerError,
+    state::{
+        account::{has_discriminator, GatewayAccount, DISCRIMINATOR_LEN},
+        accumulator::OutboundAccumulator,
+        batch::MessageRoot,
+        chain::ChainConfig,
+        channel::ChannelState,
+        config::{ForeignAddress, MessageClient, MessengerConfig, RoleRegistry},
+        message::{Message, MessagePayload},
+        optimistic::{MessageCommitment, OperatorBond, OptimisticConfig},
+        rate_limit::RateLimitState,
+        validators::ValidatorSet,
+        version::{stored_version, unpack_legacy_config, VersionedAccount},
+    },
+};
+use mv3_solana_sender::state::{Config as SenderConfig, Message as SenderMessage};
+use solana_program::{borsh0_10::try_from_slice_unchecked, pubkey::Pubkey};
+
+use crate::{
+    error::{decode_program_error, ClientError},
+    fetcher::AccountFetcher,
+    Gateway,
+};
+
+/// Decodes a gateway account, trailing bytes left over from reallocations are ignored
+pub fn decode_account<T: BorshDeserialize>(
+    address: &Pubkey,
+    data: &[u8],
+) -> Result<T, ClientError> {
+    try_from_slice_unchecked(data).map_err(|err| ClientError::InvalidAccountData(*address, err))
+}
+
+/// Decodes a gateway account after checking its discriminator
+pub fn decode_gateway_account<T: GatewayAccount>(
+    address: &Pubkey,
+    data: &[u8],
+) -> Result<T, ClientError> {
+    if !has_discriminator::<T>(data) {
+        return Err(ClientError::InvalidDiscriminator(*address));
+    }
+
+    decode_account(address, &data[DISCRIMINATOR_LEN..])
+}
+
+/// Decodes a config or message client stored in any historical layout, upgraded to the current one
+pub fn decode_versioned_account<T: VersionedAccount>(
+    address: &Pubkey,
+    data: &[u8],
+) -> Result<T, ClientError> {
+    let version =
+        stored_version::<T>(data).map_err(|_| ClientError::InvalidDiscriminator(*address))?;
+
+    if Some(version) == T::VERSION {
+        return decode_gateway_account(address, data);
+    }
+
+    T::unpack_outdated(version, data).map_err(|err| match err {
+        err if decode_program_error(&err) == Some(MessengerError::UnsupportedAccountVersion) => {
+            ClientError::UnsupportedVersion(*address, version)
+        }
+        err => ClientError::InvalidAccountData(
+            *address,
+            io::Error::new(io::ErrorKind::InvalidData, err.to_string()),
+        ),
+    })
+}
+
+pub async fn fetch_account<T: BorshDeserialize>(
+    fetcher: &impl AccountFetcher,
+    address: &Pubkey,
+) -> Result<T, ClientError> {
+    let data = fetcher
+        .get_account_data(address)
+        .await?
+        .ok_or(ClientError::AccountNotFound(*address))?;
+
+    decode_account(address, &data)
+}
+
+pub async fn fetch_gateway_account<T: GatewayAccount>(
+    fetcher: &impl AccountFetcher,
+    address: &Pubkey,
+) -> Result<T, ClientError> {
+    let data = fetcher
+        .get_account_data(address)
+        .await?
+        .ok_or(ClientError::AccountNotFound(*address))?;
+
+    decode_gateway_account(address, &data)
+}
+
+pub async fn fetch_versioned_account<T: VersionedAccount>(
+    fetcher: &impl AccountFetcher,
+    address: &Pubkey,
+) -> Result<T, ClientError> {
+    let data = fetcher
+        .get_account_data(address)
+        .await?
+        .ok_or(ClientError::AccountNotFound(*address))?;
+
+    decode_versioned_account(address, &data)
+}
+
+impl Gateway {
+    pub async fn get_config(
+        &self,
+        fetcher: &impl AccountFetcher,
+    ) -> Result<MessengerConfig, ClientError> {
+        fetch_versioned_account(fetcher, &self.config_address().0).await
+    }
+
+    pub async fn get_roles(
+        &self,
+        fetcher: &impl AccountFetcher,
+    ) -> Result<RoleRegistry, ClientError> {
+        fetch_gateway_account(fetcher, &self.roles_address().0).await
+    }
+
+    /// Fails with `AccountNotFound` for chains which are not registered
+    pub async fn get_chain(
+        &self,
+        fetcher: &impl AccountFetcher,
+        chain_id: u64,
+    ) -> Result<ChainConfig, ClientError> {
+        fetch_gateway_account(fetcher, &self.chain_address(chain_id).0).await
+    }
+
+    /// Fails with `AccountNotFound` before BLS validators were set
+    pub async fn get_validator_set(
+        &self,
+        fetcher: &impl AccountFetcher,
+    ) -> Result<ValidatorSet, ClientError> {
+        fetch_gateway_account(fetcher, &self.validators_address().0).await
+    }
+
+    /// Fails with `AccountNotFound` before optimistic delivery was configured
+    pub async fn get_optimistic_config(
+        &self,
+        fetcher: &impl AccountFetcher,
+    ) -> Result<OptimisticConfig, ClientError> {
+        fetch_gateway_account(fetcher, &self.optimistic_config_address().0).await
+    }
+
+    pub async fn get_operator_bond(
+        &self,
+        fetcher: &impl AccountFetcher,
+        operator: &Pubkey,
+    ) -> Result<OperatorBond, ClientError> {
+        fetch_gateway_account(fetcher, &self.bond_address(operator).0).await
+    }
+
+    pub async fn get_commitment(
+        &self,
+        fetcher: &impl AccountFetcher,
+        tx_id: u128,
+    ) -> Result<MessageCommitment, ClientError> {
+        fetch_gateway_account(fetcher, &self.commitment_address(tx_id).0).await
+    }
+
+    /// Fails with `AccountNotFound` before the first outbound message
+    pub async fn get_accumulator(
+        &self,
+        fetcher: &impl AccountFetcher,
+    ) -> Result<OutboundAccumulator, ClientError> {
+        fetch_gateway_account(fetcher, &self.accumulator_address().0).await
+    }
+
+    /// Fails with `AccountNotFound` until the first message of the channel was delivered
+    pub async fn get_channel(
+        &self,
+        fetcher: &impl AccountFetcher,
+        source_chain_id: u64,
+        sender: &ForeignAddress,
+        receiver: &Pubkey,
+    ) -> Result<ChannelState, ClientError> {
+        fetch_gateway_account(
+            fetcher,
+            &self.channel_address(source_chain_id, sender, receiver).0,
+        )
+        .await
+    }
+
+    /// Fails with `AccountNotFound` until the root of the batch was posted
+    pub async fn get_message_root(
+        &self,
+        fetcher: &impl AccountFetcher,
+        source_chain_id: u64,
+        batch: u64,
+    ) -> Result<MessageRoot, ClientError> {
+        fetch_gateway_account(
+            fetcher,
+            &self.message_root_address(source_chain_id, batch).0,
+        )
+        .await
+    }
+
+    /// Chains enabled by a config stored before the header split, which `migrate_config` imports
+    /// into the registry, empty once it was migrated
+    pub async fn get_legacy_chain_ids(
+        &self,
+        fetcher: &impl AccountFetcher,
+    ) -> Result<Vec<u64>, ClientError> {
+        let (config, _) = self.config_address();
+
+        let data = fetcher
+            .get_account_data(&config)
+            .await?
+            .ok_or(ClientError::AccountNotFound(config))?;
+
+        Ok(unpack_legacy_config(&data)
+            .map(|legacy| legacy.chain_ids())
+            .unwrap_or_default())
+    }
+
+    pub async fn get_message_client(
+        &self,
+        fetcher: &impl AccountFetcher,
+        destination_contract: &Pubkey,
+    ) -> Result<MessageClient, ClientError> {
+        fetch_versioned_account(
+            fetcher,
+            &self.message_client_address(destination_contract).0,
+        )
+        .await
+    }
+
+    pub async fn get_outbound_message(
+        &self,
+        fetcher: &impl AccountFetcher,
+    ) -> Result<Message, ClientError> {
+        fetch_gateway_account(fetcher, &self.outbound_message_address().0).await
+    }
+
+    /// Fails with `AccountNotFound` while `tx_id` has not been delivered
+    pub async fn get_received_message(
+        &self,
+        fetcher: &impl AccountFetcher,
+        tx_id: u128,
+    ) -> Result<MessagePayload, ClientError> {
+        fetch_gateway_account(fetcher, &self.received_message_address(tx_id).0).await
+    }
+
+    pub async fn get_chain_rate_limit(
+        &self,
+        fetcher: &impl AccountFetcher,
+        chain_id: u64,
+    ) -> Result<RateLimitState, ClientError> {
+        fetch_gateway_account(fetcher, &self.chain_rate_limit_address(chain_id).0).await
+    }
+
+    pub async fn get_client_rate_limit(
+        &self,
+        fetcher: &impl AccountFetcher,
+        destination_contract: &Pubkey,
+    ) -> Result<RateLimitState, ClientError> {
+        fetch_gateway_account(
+            fetcher,
+            &self.client_rate_limit_address(destination_contract).0,
+        )
+        .await
+    }
+
+    pub async fn get_sender_config(
+        &self,
+        fetcher: &impl AccountFetcher,
+    ) -> Result<SenderConfig, ClientError> {
+        fetch_account(fetcher, &self.sender_config_address().0).await
+    }
+
+    pub async fn get_sender_message(
+        &self,
+        fetcher: &impl AccountFetcher,
+        sender: &Pubkey,
+    ) -> Result<SenderMessage, ClientError> {
+        fetch_account(fetcher, &self.sender_message_address(sender).0).await
+    }
+}
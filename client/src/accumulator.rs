@@ -0,0 +1,133 @@
+//! Off-chain side of the outbound message accumulator `Send` appends to: the tree of every leaf
+//! of the gateway, rebuilt from the `MessageAccumulated` events, and the inclusion proofs EVM
+//! contracts verify messages sent from Solana with. A contract trusting a root of the
+//! accumulator checks a proof with
+//!
+//! ```solidity
+//! function verify(bytes32 leaf, uint256 index, bytes32[32] calldata proof, bytes32 root)
+//!     internal pure returns (bool)
+//! {
+//!     bytes32 node = leaf;
+//!     for (uint256 level = 0; level < 32; level++) {
+//!         node = (index >> level) & 1 == 1
+//!             ? keccak256(abi.encodePacked(proof[level], node))
+//!             : keccak256(abi.encodePacked(node, proof[level]));
+//!     }
+//!     return node == root;
+//! }
+//! ```
+//!
+//! the leaf being the `OutboundDigest::leaf` of the message.
+
+use mv3_contract_solana::{
+    events::GatewayEvent,
+    state::accumulator::{hash_node, zero_hashes, OutboundAccumulator, TREE_DEPTH},
+};
+
+use crate::error::ClientError;
+
+/// Every leaf of the outbound accumulator, in the order they were appended, next to the same
+/// frontier the gateway keeps so the root of every appended leaf is checked in `TREE_DEPTH` hashes
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OutboundTree {
+    leaves: Vec<[u8; 32]>,
+    frontier: OutboundAccumulator,
+}
+
+/// Parents of the nodes of `level`, a node without a right sibling is paired with `zero`
+fn parents(level: &[[u8; 32]], zero: &[u8; 32]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| hash_node(&pair[0], pair.get(1).unwrap_or(zero)))
+        .collect()
+}
+
+impl OutboundTree {
+    pub fn new() -> Self {
+        Self {
+            leaves: Vec::new(),
+            frontier: OutboundAccumulator::new(),
+        }
+    }
+
+    /// Tree over the leaves of `events`, which have to hold every `MessageAccumulated` event of
+    /// the gateway from the first one on
+    pub fn from_events<'a>(
+        events: impl IntoIterator<Item = &'a GatewayEvent>,
+    ) -> Result<Self, ClientError> {
+        let mut tree = Self::new();
+
+        for event in events {
+            tree.apply(event)?;
+        }
+
+        Ok(tree)
+    }
+
+    /// Appends the leaf of a `MessageAccumulated` event after checking it extends the tree to
+    /// the root the gateway reported, other events are skipped
+    pub fn apply(&mut self, event: &GatewayEvent) -> Result<(), ClientError> {
+        if let GatewayEvent::MessageAccumulated {
+            index, leaf, root, ..
+        } = event
+        {
+            let mut frontier = self.frontier.clone();
+
+            if *index != self.len() || frontier.append(*leaf).is_err() || frontier.root() != *root {
+                return Err(ClientError::InvalidAccumulatorEvent(*index));
+            }
+
+            self.leaves.push(*leaf);
+            self.frontier = frontier;
+        }
+
+        Ok(())
+    }
+
+    /// Appends `leaf` and returns its index
+    pub fn push(&mut self, leaf: [u8; 32]) -> Result<u64, ClientError> {
+        let index = self
+            .frontier
+            .append(leaf)
+            .map_err(|_| ClientError::AccumulatorFull)?;
+
+        self.leaves.push(leaf);
+
+        Ok(index)
+    }
+
+    pub fn len(&self) -> u64 {
+        self.leaves.len() as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Root the gateway reports after the last leaf, `OutboundAccumulator::root`
+    pub fn root(&self) -> [u8; 32] {
+        self.frontier.root()
+    }
+
+    /// Siblings of the leaf at `index` from the bottom up, proving it against `root`
+    pub fn proof(&self, index: u64) -> Option<[[u8; 32]; TREE_DEPTH]> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let zero_hashes = zero_hashes();
+
+        let mut proof = [[0; 32]; TREE_DEPTH];
+        let mut level = self.leaves.clone();
+        let mut position = index as usize;
+
+        for (sibling, zero) in proof.iter_mut().zip(&zero_hashes) {
+            *sibling = level.get(position ^ 1).copied().unwrap_or(*zero);
+
+            level = parents(&level, zero);
+            position /= 2;
+        }
+
+        Some(proof)
+    }
+}
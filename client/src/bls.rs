@@ -0,0 +1,295 @@
+//! Off-chain side of the BLS validator signatures verified by `mv3_contract_solana::bls`:
+//! validator keys, signing and the aggregation of the signatures of a message into the
+//! `BlsProof` relayers deliver it with.
+
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, Group};
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+use mv3_contract_solana::{
+    bls::{hash_to_field, possession_message, BlsKey, BlsProof, G1Point, G2Point},
+    utils::keccak256,
+};
+
+use crate::error::ClientError;
+
+/// Secret key of a validator
+#[derive(Clone, PartialEq, Eq)]
+pub struct BlsSecretKey(Fr);
+
+/// Key of a validator in both groups. The gateway stores `g1`, relayers sum `g2` of the signers
+/// of a message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlsPublicKey {
+    pub g1: G1Point,
+    pub g2: G2Point,
+}
+
+impl BlsSecretKey {
+    /// Big endian scalar, `None` for 0 and values not below the group order
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let scalar = Fr::from_be_bytes_mod_order(bytes);
+
+        (!scalar.is_zero() && to_bytes(scalar) == *bytes).then_some(BlsSecretKey(scalar))
+    }
+
+    /// Key derived from `keccak256(seed)`, for tests and local deployments
+    pub fn from_seed(seed: &[u8]) -> Self {
+        BlsSecretKey(Fr::from_be_bytes_mod_order(&keccak256(seed)))
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        to_bytes(self.0)
+    }
+
+    pub fn public_key(&self) -> BlsPublicKey {
+        BlsPublicKey {
+            g1: encode_g1(&(G1Projective::generator() * self.0).into_affine()),
+            g2: encode_g2(&(G2Projective::generator() * self.0).into_affine()),
+        }
+    }
+
+    /// Signature over `message`, the hash `ReceiveMessage` verifies
+    pub fn sign(&self, message: &[u8; 32]) -> G1Point {
+        let (_, point) = hash_to_g1(message);
+
+        encode_g1(&(point * self.0).into_affine())
+    }
+
+    /// Signature proving the validator holds the key, which `SetBlsValidators` takes with it
+    pub fn sign_possession(&self) -> G1Point {
+        self.sign(&possession_message(&self.public_key().g1))
+    }
+}
+
+impl BlsPublicKey {
+    /// Checks both halves are points of their groups holding the same secret
+    pub fn check(&self) -> Result<(), ClientError> {
+        let g1 = decode_g1(&self.g1)?;
+        let g2 = decode_g2(&self.g2)?;
+
+        if g1.is_zero()
+            || !pairs_to_one(&[(-g1, G2Affine::generator()), (G1Affine::generator(), g2)])
+        {
+            return Err(ClientError::InvalidBlsPoint);
+        }
+
+        Ok(())
+    }
+
+    /// Key as written by `SetBlsValidators`, with `possession` the signature of its validator
+    /// over `possession_message`
+    pub fn with_possession(&self, possession: &G1Point) -> Result<BlsKey, ClientError> {
+        self.check()?;
+
+        if !verify_signature(&possession_message(&self.g1), self, possession) {
+            return Err(ClientError::InvalidPossessionProof);
+        }
+
+        Ok(BlsKey {
+            key: self.g1,
+            possession: proof(&possession_message(&self.g1), vec![1], possession, &self.g2),
+        })
+    }
+}
+
+/// Point `message` is hashed to, as checked by the gateway, with the square roots of
+/// `-(x³ + 3)` proving the counters before the one it was found with have no point
+pub fn hash_to_g1(message: &[u8; 32]) -> (Vec<[u8; 32]>, G1Affine) {
+    let mut skipped_roots = vec![];
+
+    for counter in 0..=u8::MAX {
+        let x = Fq::from_be_bytes_mod_order(&hash_to_field(message, counter));
+        let rhs = x * x * x + Fq::from(3u64);
+
+        let Some(y) = rhs.sqrt() else {
+            skipped_roots.push(to_bytes(
+                (-rhs).sqrt().expect("-1 is not a square modulo p"),
+            ));
+
+            continue;
+        };
+
+        let y = if y.into_bigint() > Fq::MODULUS_MINUS_ONE_DIV_TWO {
+            -y
+        } else {
+            y
+        };
+
+        return (skipped_roots, G1Affine::new_unchecked(x, y));
+    }
+
+    panic!("No point found for any counter")
+}
+
+/// Checks `signature` is the signature of `key` over `message`
+pub fn verify_signature(message: &[u8; 32], key: &BlsPublicKey, signature: &G1Point) -> bool {
+    let (Ok(key), Ok(signature)) = (decode_g2(&key.g2), decode_g1(signature)) else {
+        return false;
+    };
+
+    let (_, point) = hash_to_g1(message);
+
+    pairs_to_one(&[(-signature, G2Affine::generator()), (point, key)])
+}
+
+/// Collects the signatures of the validators over one message
+pub struct BlsAggregator {
+    message: [u8; 32],
+    validators: Vec<BlsPublicKey>,
+    signatures: Vec<Option<G1Affine>>,
+}
+
+impl BlsAggregator {
+    /// Aggregator for `message` signed by `validators`, in the order of the gateway's set
+    pub fn new(message: [u8; 32], validators: Vec<BlsPublicKey>) -> Self {
+        let signatures = vec![None; validators.len()];
+
+        BlsAggregator {
+            message,
+            validators,
+            signatures,
+        }
+    }
+
+    /// Adds the signature of validator `index` after checking it
+    pub fn add(&mut self, index: usize, signature: &G1Point) -> Result<(), ClientError> {
+        let key = self
+            .validators
+            .get(index)
+            .ok_or(ClientError::UnknownValidator(index))?;
+
+        if !verify_signature(&self.message, key, signature) {
+            return Err(ClientError::InvalidBlsSignature(index));
+        }
+
+        self.signatures[index] = Some(decode_g1(signature)?);
+
+        Ok(())
+    }
+
+    /// Validators whose signature was added
+    pub fn signers(&self) -> usize {
+        self.signatures.iter().flatten().count()
+    }
+
+    /// Proof over the signatures added so far
+    pub fn proof(&self) -> BlsProof {
+        let mut signers = vec![0; self.validators.len().div_ceil(8)];
+        let mut signature = G1Projective::zero();
+        let mut aggregate_key = G2Projective::zero();
+
+        for (index, partial) in self.signatures.iter().enumerate() {
+            if let Some(partial) = partial {
+                signers[index / 8] |= 1 << (index % 8);
+                signature += partial;
+                aggregate_key += decode_g2(&self.validators[index].g2)
+                    .expect("Validator keys are checked when their signature is added");
+            }
+        }
+
+        proof(
+            &self.message,
+            signers,
+            &encode_g1(&signature.into_affine()),
+            &encode_g2(&aggregate_key.into_affine()),
+        )
+    }
+}
+
+fn proof(
+    message: &[u8; 32],
+    signers: Vec<u8>,
+    signature: &G1Point,
+    aggregate_key: &G2Point,
+) -> BlsProof {
+    let (skipped_roots, point) = hash_to_g1(message);
+
+    BlsProof {
+        signers,
+        signature: *signature,
+        aggregate_key: *aggregate_key,
+        skipped_roots,
+        message_y: to_bytes(point.y),
+    }
+}
+
+fn pairs_to_one(pairs: &[(G1Affine, G2Affine)]) -> bool {
+    Bn254::multi_pairing(
+        pairs.iter().map(|pair| pair.0),
+        pairs.iter().map(|pair| pair.1),
+    )
+    .is_zero()
+}
+
+fn to_bytes<F: PrimeField>(value: F) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    bytes.copy_from_slice(&value.into_bigint().to_bytes_be());
+
+    bytes
+}
+
+/// Field element of the 32 big endian bytes at `bytes`, which have to be below the modulus
+fn decode_fq(bytes: &[u8]) -> Result<Fq, ClientError> {
+    let value = Fq::from_be_bytes_mod_order(bytes);
+
+    if to_bytes(value)[..] != *bytes {
+        return Err(ClientError::InvalidBlsPoint);
+    }
+
+    Ok(value)
+}
+
+pub fn encode_g1(point: &G1Affine) -> G1Point {
+    let mut bytes = [0; 64];
+
+    if let Some((x, y)) = point.xy() {
+        bytes[..32].copy_from_slice(&to_bytes(*x));
+        bytes[32..].copy_from_slice(&to_bytes(*y));
+    }
+
+    bytes
+}
+
+pub fn decode_g1(bytes: &G1Point) -> Result<G1Affine, ClientError> {
+    if *bytes == [0; 64] {
+        return Ok(G1Affine::zero());
+    }
+
+    let point = G1Affine::new_unchecked(decode_fq(&bytes[..32])?, decode_fq(&bytes[32..])?);
+
+    if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(ClientError::InvalidBlsPoint);
+    }
+
+    Ok(point)
+}
+
+pub fn encode_g2(point: &G2Affine) -> G2Point {
+    let mut bytes = [0; 128];
+
+    if let Some((x, y)) = point.xy() {
+        bytes[..32].copy_from_slice(&to_bytes(x.c1));
+        bytes[32..64].copy_from_slice(&to_bytes(x.c0));
+        bytes[64..96].copy_from_slice(&to_bytes(y.c1));
+        bytes[96..].copy_from_slice(&to_bytes(y.c0));
+    }
+
+    bytes
+}
+
+pub fn decode_g2(bytes: &G2Point) -> Result<G2Affine, ClientError> {
+    if *bytes == [0; 128] {
+        return Ok(G2Affine::zero());
+    }
+
+    let x = Fq2::new(decode_fq(&bytes[32..64])?, decode_fq(&bytes[..32])?);
+    let y = Fq2::new(decode_fq(&bytes[96..])?, decode_fq(&bytes[64..96])?);
+
+    let point = G2Affine::new_unchecked(x, y);
+
+    if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(ClientError::InvalidBlsPoint);
+    }
+
+    Ok(point)
+}
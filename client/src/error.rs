@@ -0,0 +1,70 @@
+use mv3_contract_solana::error::MessengerError;
+use solana_program::{instruction::InstructionError, program_error::ProgramError, pubkey::Pubkey};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("Account {0} does not exist")]
+    AccountNotFound(Pubkey),
+    #[error("Failed to decode account {0}: {1}")]
+    InvalidAccountData(Pubkey, std::io::Error),
+    #[error("Account {0} holds a different type")]
+    InvalidDiscriminator(Pubkey),
+    #[error("Account {0} is stored in unsupported layout version {1}")]
+    UnsupportedVersion(Pubkey, u8),
+    #[error("Failed to fetch account {0}: {1}")]
+    FetchFailed(Pubkey, String),
+    #[error("Failed to resolve extra accounts: {0}")]
+    ExtraAccountResolution(String),
+    #[error("Missing message payload")]
+    MissingPayload,
+    #[error("Validator {0} is not in the validator set")]
+    UnknownValidator(usize),
+    #[error("Signature of validator {0} does not verify")]
+    InvalidBlsSignature(usize),
+    #[error("Not a BN254 point of the expected group")]
+    InvalidBlsPoint,
+    #[error("Proof of possession does not verify against the key")]
+    InvalidPossessionProof,
+    #[error("Accumulator event of leaf {0} does not extend the tree")]
+    InvalidAccumulatorEvent(u64),
+    #[error("Outbound accumulator is full")]
+    AccumulatorFull,
+}
+
+/// Gateway error a failed instruction returned, `None` for errors raised outside the gateway
+pub fn decode_program_error(error: &ProgramError) -> Option<MessengerError> {
+    match error {
+        ProgramError::Custom(code) => MessengerError::from_code(*code),
+        _ => None,
+    }
+}
+
+/// Gateway error of an instruction failed in a transaction, as reported by the cluster
+pub fn decode_instruction_error(error: &InstructionError) -> Option<MessengerError> {
+    match error {
+        InstructionError::Custom(code) => MessengerError::from_code(*code),
+        _ => None,
+    }
+}
+
+/// Whether resubmitting the same instruction later can succeed. Pauses, rate limits, pending
+/// migrations, running challenge periods, pending disputes and earlier messages of an ordered channel clear without
+/// the message changing, every other failure is fatal to it.
+pub fn is_retryable(error: MessengerError) -> bool {
+    matches!(
+        error,
+        MessengerError::BrigdeNotEnabled
+            | MessengerError::InboundPaused
+            | MessengerError::OutboundPaused
+            | MessengerError::SourceChainPaused
+            | MessengerError::DestinationChainPaused
+            | MessengerError::ClientPaused
+            | MessengerError::RateLimitExceeded
+            | MessengerError::UnsupportedAccountVersion
+            | MessengerError::ChallengePeriodActive
+            | MessengerError::CommitmentDisputed
+            | MessengerError::DisputePeriodActive
+            | MessengerError::OutOfOrderMessage
+    )
+}
@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use solana_program::pubkey::Pubkey;
+
+use crate::error::ClientError;
+
+/// Source of account data used to decode state and resolve extra accounts, implemented over an
+/// RPC client by integrators and by `AccountStore` in tests.
+#[async_trait]
+pub trait AccountFetcher: Sync {
+    /// Returns `None` for accounts which do not exist
+    async fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError>;
+}
+
+/// In-memory accounts, fetches fail for nothing but missing accounts
+#[derive(Debug, Default, Clone)]
+pub struct AccountStore {
+    accounts: HashMap<Pubkey, Vec<u8>>,
+}
+
+impl AccountStore {
+    pub fn insert(&mut self, address: Pubkey, data: Vec<u8>) {
+        self.accounts.insert(address, data);
+    }
+
+    pub fn remove(&mut self, address: &Pubkey) -> Option<Vec<u8>> {
+        self.accounts.remove(address)
+    }
+}
+
+#[async_trait]
+impl AccountFetcher for AccountStore {
+    async fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError> {
+        Ok(self.accounts.get(address).cloned())
+    }
+}
@@ -0,0 +1,783 @@
+use borsh::BorshSerialize;
+use message_hook::offchain::add_extra_account_metas_for_execute;
+use mv3_contract_solana::{
+    instruction::{
+        AddUserPermission, BatchProof, ChangeConfig, DepositWithdraw, FraudEvidence, PauseTarget,
+        PostCommitment, PostMessageRoot, RateLimitTarget, ReceiveMessage, RegisterChain,
+        SendMessage, SetBlsValidators, SetEd25519Validators, SetExsig, SetOptimisticConfig,
+        SetPause, SetRateLimit, TransferClientAuthority, V3Instruction,
+    },
+    state::{account::AccountKind, config::MessageClient},
+};
+use mv3_solana_sender::{instructions::Mv3SenderInstruction, state::ForeignAddress};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+use crate::{error::ClientError, fetcher::AccountFetcher, pda::program_data_address, Gateway};
+
+impl Gateway {
+    fn instruction(&self, accounts: Vec<AccountMeta>, data: V3Instruction) -> Instruction {
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data: data.try_to_vec().unwrap(),
+        }
+    }
+
+    /// Authority, config and system program, the accounts of every config instruction
+    fn config_accounts(&self, authority: &Pubkey) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(self.config_address().0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]
+    }
+
+    /// Creates the config of a gateway deployed on `chain_id`
+    pub fn initialize_config(
+        &self,
+        payer: &Pubkey,
+        accountant: Pubkey,
+        chain_id: u64,
+    ) -> Instruction {
+        let mut accounts = self.config_accounts(payer);
+
+        accounts.push(AccountMeta::new(self.roles_address().0, false));
+
+        self.instruction(
+            accounts,
+            V3Instruction::InitializeConfig {
+                accountant,
+                chain_id,
+            },
+        )
+    }
+
+    pub fn change_config(&self, authority: &Pubkey, data: ChangeConfig) -> Instruction {
+        self.instruction(
+            self.config_accounts(authority),
+            V3Instruction::ChangeConfig {
+                bridge_enabled: data.bridge_enabled,
+                accountant: data.accountant,
+                whitelist_only: data.whitelist_only,
+                chainsig: data.chainsig,
+                digest_scheme: data.digest_scheme,
+            },
+        )
+    }
+
+    pub fn add_user_permission(&self, authority: &Pubkey, data: AddUserPermission) -> Instruction {
+        let mut accounts = self.config_accounts(authority);
+
+        accounts.push(AccountMeta::new(self.roles_address().0, false));
+
+        self.instruction(
+            accounts,
+            V3Instruction::AddUserPermission {
+                user: data.user,
+                is_active: data.is_active,
+                role: data.role,
+            },
+        )
+    }
+
+    pub fn set_pause(&self, authority: &Pubkey, data: SetPause) -> Instruction {
+        let mut accounts = self.config_accounts(authority);
+
+        accounts.push(AccountMeta::new_readonly(self.roles_address().0, false));
+
+        let target = match data.target {
+            PauseTarget::Inbound | PauseTarget::Outbound => None,
+            PauseTarget::SourceChain(chain_id) | PauseTarget::DestinationChain(chain_id) => {
+                Some(self.chain_address(chain_id).0)
+            }
+            PauseTarget::Client(destination_contract) => {
+                Some(self.message_client_address(&destination_contract).0)
+            }
+        };
+
+        if let Some(target) = target {
+            accounts.push(AccountMeta::new(target, false));
+        }
+
+        self.instruction(
+            accounts,
+            V3Instruction::SetPause {
+                target: data.target,
+                paused: data.paused,
+            },
+        )
+    }
+
+    pub fn set_rate_limit(&self, authority: &Pubkey, data: SetRateLimit) -> Instruction {
+        let mut accounts = self.config_accounts(authority);
+
+        let (target, bucket) = match data.target {
+            RateLimitTarget::Chain(chain_id) => (
+                self.chain_address(chain_id).0,
+                self.chain_rate_limit_address(chain_id).0,
+            ),
+            RateLimitTarget::Client(destination_contract) => (
+                self.message_client_address(&destination_contract).0,
+                self.client_rate_limit_address(&destination_contract).0,
+            ),
+        };
+
+        accounts.push(AccountMeta::new(target, false));
+        accounts.push(AccountMeta::new(bucket, false));
+
+        self.instruction(
+            accounts,
+            V3Instruction::SetRateLimit {
+                target: data.target,
+                limit: data.limit,
+            },
+        )
+    }
+
+    /// Prefixes a legacy `account` with its discriminator and upgrades client layouts, the config
+    /// has to be migrated first with `migrate_config`
+    pub fn migrate_account(
+        &self,
+        authority: &Pubkey,
+        account: &Pubkey,
+        kind: AccountKind,
+    ) -> Instruction {
+        let mut accounts = self.config_accounts(authority);
+
+        if *account != self.config_address().0 {
+            accounts.push(AccountMeta::new(*account, false));
+        }
+
+        self.instruction(accounts, V3Instruction::MigrateAccount { kind })
+    }
+
+    /// Upgrades the config and the clients of `destination_contracts` to the current layout and
+    /// imports the chains enabled by a legacy config into the registry, `chain_ids` are the ones
+    /// returned by `get_legacy_chain_ids`
+    pub fn migrate_config(
+        &self,
+        authority: &Pubkey,
+        chain_ids: &[u64],
+        destination_contracts: &[Pubkey],
+    ) -> Instruction {
+        let mut accounts = self.config_accounts(authority);
+
+        accounts.push(AccountMeta::new(self.roles_address().0, false));
+
+        for chain_id in chain_ids {
+            accounts.push(AccountMeta::new(self.chain_address(*chain_id).0, false));
+        }
+
+        for destination_contract in destination_contracts {
+            accounts.push(AccountMeta::new(
+                self.message_client_address(destination_contract).0,
+                false,
+            ));
+        }
+
+        self.instruction(accounts, V3Instruction::MigrateConfig)
+    }
+
+    /// Registers `data.chain_id` or updates its entry, signed by the config owner
+    pub fn register_chain(&self, authority: &Pubkey, data: RegisterChain) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(self.config_address().0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(self.chain_address(data.chain_id).0, false),
+        ];
+
+        self.instruction(
+            accounts,
+            V3Instruction::RegisterChain {
+                chain_id: data.chain_id,
+                name: data.name,
+                address_format: data.address_format,
+                min_confirmations: data.min_confirmations,
+                fees: data.fees,
+                enabled: data.enabled,
+            },
+        )
+    }
+
+    /// Writes BLS validator keys from `data.offset` on, sets larger than one transaction are
+    /// written by several calls with `enabled` only set on the last
+    pub fn set_bls_validators(&self, authority: &Pubkey, data: SetBlsValidators) -> Instruction {
+        let mut accounts = self.config_accounts(authority);
+
+        accounts.push(AccountMeta::new(self.validators_address().0, false));
+
+        self.instruction(
+            accounts,
+            V3Instruction::SetBlsValidators {
+                offset: data.offset,
+                keys: data.keys,
+                threshold: data.threshold,
+                enabled: data.enabled,
+            },
+        )
+    }
+
+    /// Replaces the Ed25519 validator keys. Messages are then relayed right after an Ed25519
+    /// program instruction with the validator signatures, see `ed25519::verify_instruction`.
+    pub fn set_ed25519_validators(
+        &self,
+        authority: &Pubkey,
+        data: SetEd25519Validators,
+    ) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(self.config_address().0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(self.validators_address().0, false),
+        ];
+
+        self.instruction(
+            accounts,
+            V3Instruction::SetEd25519Validators {
+                keys: data.keys,
+                threshold: data.threshold,
+            },
+        )
+    }
+
+    /// Registers or updates the client of `data.destination_contract`, registration has to be
+    /// signed by the upgrade authority of the program
+    /// Takes the registry entries of the supported chains, which have to be registered
+    pub fn configure_client(&self, payer: &Pubkey, data: MessageClient) -> Instruction {
+        let mut accounts = vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(
+                self.message_client_address(&data.destination_contract).0,
+                false,
+            ),
+            AccountMeta::new_readonly(data.destination_contract, false),
+            AccountMeta::new_readonly(program_data_address(&data.destination_contract), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+
+        for chain_id in &data.supported_chains {
+            accounts.push(AccountMeta::new_readonly(
+                self.chain_address(*chain_id).0,
+                false,
+            ));
+        }
+
+        self.instruction(
+            accounts,
+            V3Instruction::ConfigureClient {
+                authority: data.authority,
+                destination_contract: data.destination_contract,
+                notify_on_failure: data.notify_on_failure,
+                supported_chains: data.supported_chains,
+                allowed_contracts: data.allowed_contracts,
+                exsig: data.exsig,
+            },
+        )
+    }
+
+    /// Authority, client and system program, the accounts of every client instruction
+    fn client_accounts(
+        &self,
+        authority: &Pubkey,
+        destination_contract: &Pubkey,
+    ) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(self.message_client_address(destination_contract).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]
+    }
+
+    pub fn set_exsig(
+        &self,
+        authority: &Pubkey,
+        destination_contract: &Pubkey,
+        data: SetExsig,
+    ) -> Instruction {
+        self.instruction(
+            self.client_accounts(authority, destination_contract),
+            V3Instruction::SetExsig {
+                exsig: data.exsig,
+                activation_delay: data.activation_delay,
+            },
+        )
+    }
+
+    pub fn transfer_client_authority(
+        &self,
+        authority: &Pubkey,
+        data: TransferClientAuthority,
+    ) -> Instruction {
+        self.instruction(
+            self.client_accounts(authority, &data.destination_contract),
+            V3Instruction::TransferClientAuthority {
+                destination_contract: data.destination_contract,
+                new_authority: data.new_authority,
+            },
+        )
+    }
+
+    pub fn accept_client_authority(
+        &self,
+        new_authority: &Pubkey,
+        destination_contract: Pubkey,
+    ) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new(*new_authority, true),
+            AccountMeta::new(self.message_client_address(&destination_contract).0, false),
+        ];
+
+        self.instruction(
+            accounts,
+            V3Instruction::AcceptClientAuthority {
+                destination_contract,
+            },
+        )
+    }
+
+    pub fn close_client(&self, authority: &Pubkey, destination_contract: Pubkey) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(self.message_client_address(&destination_contract).0, false),
+            AccountMeta::new(self.client_treasury_address(&destination_contract).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(
+                self.client_rate_limit_address(&destination_contract).0,
+                false,
+            ),
+        ];
+
+        self.instruction(
+            accounts,
+            V3Instruction::CloseClient {
+                destination_contract,
+            },
+        )
+    }
+
+    pub fn deposit_withdraw(
+        &self,
+        payer: &Pubkey,
+        destination_contract: &Pubkey,
+        action: DepositWithdraw,
+        amount: u64,
+    ) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(self.message_client_address(destination_contract).0, false),
+            AccountMeta::new(self.client_treasury_address(destination_contract).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+
+        self.instruction(accounts, V3Instruction::DepositWithdraw { action, amount })
+    }
+
+    /// Sets the challenge period, minimum operator bond and watchers of optimistic delivery
+    pub fn set_optimistic_config(
+        &self,
+        authority: &Pubkey,
+        data: SetOptimisticConfig,
+    ) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(self.config_address().0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(self.optimistic_config_address().0, false),
+        ];
+
+        self.instruction(
+            accounts,
+            V3Instruction::SetOptimisticConfig {
+                challenge_period: data.challenge_period,
+                min_bond: data.min_bond,
+                watchers: data.watchers,
+            },
+        )
+    }
+
+    /// Opts the client in or out of optimistic delivery, signed by the client authority
+    pub fn set_client_optimistic(
+        &self,
+        authority: &Pubkey,
+        destination_contract: Pubkey,
+        enabled: bool,
+    ) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(self.message_client_address(&destination_contract).0, false),
+        ];
+
+        self.instruction(
+            accounts,
+            V3Instruction::SetClientOptimistic {
+                destination_contract,
+                enabled,
+            },
+        )
+    }
+
+    /// Opts the client in or out of ordered channels, signed by the client authority
+    pub fn set_client_ordered(
+        &self,
+        authority: &Pubkey,
+        destination_contract: Pubkey,
+        enabled: bool,
+    ) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(self.message_client_address(&destination_contract).0, false),
+        ];
+
+        self.instruction(
+            accounts,
+            V3Instruction::SetClientOrdered {
+                destination_contract,
+                enabled,
+            },
+        )
+    }
+
+    pub fn deposit_withdraw_bond(
+        &self,
+        operator: &Pubkey,
+        action: DepositWithdraw,
+        amount: u64,
+    ) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new(*operator, true),
+            AccountMeta::new(self.bond_address(operator).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+
+        self.instruction(
+            accounts,
+            V3Instruction::DepositWithdrawBond { action, amount },
+        )
+    }
+
+    /// Commits `operator` to a message, `data.hash` being its digest hash in the digest scheme of
+    /// the config. The message is relayed with `receive_message` after the challenge period.
+    pub fn post_commitment(&self, operator: &Pubkey, data: PostCommitment) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new(*operator, true),
+            AccountMeta::new_readonly(self.roles_address().0, false),
+            AccountMeta::new_readonly(self.optimistic_config_address().0, false),
+            AccountMeta::new(self.bond_address(operator).0, false),
+            AccountMeta::new_readonly(self.message_client_address(&data.receiver).0, false),
+            AccountMeta::new(self.commitment_address(data.tx_id).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+
+        self.instruction(
+            accounts,
+            V3Instruction::PostCommitment {
+                tx_id: data.tx_id,
+                receiver: data.receiver,
+                hash: data.hash,
+            },
+        )
+    }
+
+    /// Challenges the commitment of `operator` to `tx_id` with the message the validators signed
+    /// under `tx_id`, signed by a registered watcher. Ed25519 validator signatures go in the
+    /// preceding instruction.
+    pub fn challenge_commitment(
+        &self,
+        watcher: &Pubkey,
+        operator: &Pubkey,
+        tx_id: u128,
+        evidence: FraudEvidence,
+    ) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new_readonly(*watcher, true),
+            AccountMeta::new_readonly(self.optimistic_config_address().0, false),
+            AccountMeta::new(self.commitment_address(tx_id).0, false),
+            AccountMeta::new(self.bond_address(operator).0, false),
+            AccountMeta::new(self.global_treasury_address().0, false),
+            AccountMeta::new_readonly(self.config_address().0, false),
+            AccountMeta::new_readonly(self.validators_address().0, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ];
+
+        self.instruction(
+            accounts,
+            V3Instruction::ChallengeCommitment {
+                tx_id,
+                source_chain_id: evidence.source_chain_id,
+                sender: evidence.sender,
+                receiver: evidence.receiver,
+                payload_hash: evidence.payload_hash,
+                signature: evidence.signature,
+            },
+        )
+    }
+
+    /// Disputes the commitment of `operator` to `tx_id` on the word of a registered watcher,
+    /// who pays the rent of the dispute
+    pub fn dispute_commitment(
+        &self,
+        watcher: &Pubkey,
+        operator: &Pubkey,
+        tx_id: u128,
+    ) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new(*watcher, true),
+            AccountMeta::new_readonly(self.optimistic_config_address().0, false),
+            AccountMeta::new(self.commitment_address(tx_id).0, false),
+            AccountMeta::new(self.bond_address(operator).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+
+        self.instruction(accounts, V3Instruction::DisputeCommitment { tx_id })
+    }
+
+    /// Settles the dispute of the commitment of `operator` to `tx_id`, dropping it with the
+    /// BLS proof or chainsig `signature` of the committed digest or slashing the bond once the
+    /// dispute period is over. Ed25519 validator signatures go in the preceding instruction.
+    pub fn settle_dispute(
+        &self,
+        operator: &Pubkey,
+        tx_id: u128,
+        signature: Option<Vec<u8>>,
+    ) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.commitment_address(tx_id).0, false),
+            AccountMeta::new(self.bond_address(operator).0, false),
+            AccountMeta::new(self.global_treasury_address().0, false),
+            AccountMeta::new_readonly(self.config_address().0, false),
+            AccountMeta::new_readonly(self.validators_address().0, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ];
+
+        self.instruction(accounts, V3Instruction::SettleDispute { tx_id, signature })
+    }
+
+    /// Posts the root of a batch of messages, `data.signature` being the BLS proof or chainsig
+    /// signature of its `RootDigest`
+    pub fn post_message_root(&self, payer: &Pubkey, data: PostMessageRoot) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(self.config_address().0, false),
+            AccountMeta::new_readonly(self.chain_address(data.source_chain_id).0, false),
+            AccountMeta::new_readonly(self.validators_address().0, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+            AccountMeta::new(
+                self.message_root_address(data.source_chain_id, data.batch)
+                    .0,
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+
+        self.instruction(
+            accounts,
+            V3Instruction::PostMessageRoot {
+                source_chain_id: data.source_chain_id,
+                batch: data.batch,
+                root: data.root,
+                signature: data.signature,
+            },
+        )
+    }
+
+    /// `Send` has to be preceded by an instruction of the caller program in the same transaction
+    pub fn send(&self, sender: &Pubkey, data: SendMessage) -> Instruction {
+        let chain = data.chain;
+
+        let accounts = vec![
+            AccountMeta::new(self.config_address().0, false),
+            AccountMeta::new(*sender, true),
+            AccountMeta::new(self.outbound_message_address().0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+            AccountMeta::new(self.chain_rate_limit_address(chain).0, false),
+            AccountMeta::new_readonly(self.message_client_address(sender).0, false),
+            AccountMeta::new(self.client_rate_limit_address(sender).0, false),
+            AccountMeta::new_readonly(self.chain_address(chain).0, false),
+            AccountMeta::new_readonly(self.roles_address().0, false),
+            AccountMeta::new(self.global_treasury_address().0, false),
+            AccountMeta::new(self.accumulator_address().0, false),
+        ];
+
+        self.instruction(
+            accounts,
+            V3Instruction::Send {
+                recipient: data.recipient,
+                chain: data.chain,
+                confirmations: data.confirmations,
+                data: data.data,
+            },
+        )
+    }
+
+    /// `ReceiveMessage` without the accounts of the receiving program, see `receive_message`
+    pub fn receive_message_unresolved(&self, payer: &Pubkey, data: ReceiveMessage) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(self.config_address().0, false),
+            AccountMeta::new_readonly(self.message_client_address(&data.receiver).0, false),
+            AccountMeta::new(self.received_message_address(data.tx_id).0, false),
+            AccountMeta::new(self.client_treasury_address(&data.receiver).0, false),
+            AccountMeta::new(self.global_treasury_address().0, false),
+            AccountMeta::new(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+            AccountMeta::new(self.chain_rate_limit_address(data.source_chain_id).0, false),
+            AccountMeta::new(self.client_rate_limit_address(&data.receiver).0, false),
+            AccountMeta::new_readonly(self.chain_address(data.source_chain_id).0, false),
+            AccountMeta::new_readonly(self.validators_address().0, false),
+            AccountMeta::new_readonly(self.commitment_address(data.tx_id).0, false),
+            AccountMeta::new(
+                self.channel_address(data.source_chain_id, &data.sender, &data.receiver)
+                    .0,
+                false,
+            ),
+        ];
+
+        self.instruction(
+            accounts,
+            V3Instruction::ReceiveMessage {
+                tx_id: data.tx_id,
+                dest_chain_id: data.dest_chain_id,
+                receiver: data.receiver,
+                data: data.data,
+                source_chain_id: data.source_chain_id,
+                sender: data.sender,
+            },
+        )
+    }
+
+    /// `ReceiveBatchedMessage` without the accounts of the receiving program, see
+    /// `receive_batched_message`
+    pub fn receive_batched_message_unresolved(
+        &self,
+        payer: &Pubkey,
+        data: ReceiveMessage,
+        proof: BatchProof,
+    ) -> Instruction {
+        let root = self
+            .message_root_address(data.source_chain_id, proof.batch)
+            .0;
+
+        let batched = V3Instruction::ReceiveBatchedMessage {
+            tx_id: data.tx_id,
+            dest_chain_id: data.dest_chain_id,
+            receiver: data.receiver,
+            data: data.data.clone(),
+            source_chain_id: data.source_chain_id,
+            sender: data.sender,
+            batch: proof.batch,
+            proof: proof.proof,
+        };
+
+        let mut instruction = self.receive_message_unresolved(payer, data);
+
+        instruction
+            .accounts
+            .push(AccountMeta::new_readonly(root, false));
+        instruction.data = batched.try_to_vec().unwrap();
+
+        instruction
+    }
+
+    /// `ReceiveMessage` including the extra accounts the receiving program requires, resolved
+    /// from its extra account meta list. The payload is the last entry of `data.data`, after the
+    /// exsig signature and the chainsig signature or BLS proof.
+    pub async fn receive_message(
+        &self,
+        fetcher: &impl AccountFetcher,
+        payer: &Pubkey,
+        data: ReceiveMessage,
+    ) -> Result<Instruction, ClientError> {
+        let receiver = data.receiver;
+        let payload = data.data.last().ok_or(ClientError::MissingPayload)?.clone();
+
+        let instruction = self.receive_message_unresolved(payer, data);
+
+        self.resolve_execute_accounts(fetcher, instruction, &receiver, payload)
+            .await
+    }
+
+    /// `ReceiveBatchedMessage` of a message proven by `proof` to be part of a posted root,
+    /// including the extra accounts the receiving program requires. The payload is the last
+    /// entry of `data.data`, after the exsig signature if the receiver has one.
+    pub async fn receive_batched_message(
+        &self,
+        fetcher: &impl AccountFetcher,
+        payer: &Pubkey,
+        data: ReceiveMessage,
+        proof: BatchProof,
+    ) -> Result<Instruction, ClientError> {
+        let receiver = data.receiver;
+        let payload = data.data.last().ok_or(ClientError::MissingPayload)?.clone();
+
+        let instruction = self.receive_batched_message_unresolved(payer, data, proof);
+
+        self.resolve_execute_accounts(fetcher, instruction, &receiver, payload)
+            .await
+    }
+
+    /// Appends the accounts `receiver` requires to execute `payload`, resolved from its extra
+    /// account meta list
+    async fn resolve_execute_accounts(
+        &self,
+        fetcher: &impl AccountFetcher,
+        mut instruction: Instruction,
+        receiver: &Pubkey,
+        payload: Vec<u8>,
+    ) -> Result<Instruction, ClientError> {
+        let (message_client, _) = self.message_client_address(receiver);
+
+        add_extra_account_metas_for_execute(
+            &mut instruction,
+            receiver,
+            &message_client,
+            payload,
+            |address| async move {
+                fetcher
+                    .get_account_data(&address)
+                    .await
+                    .map_err(|err| err.to_string().into())
+            },
+        )
+        .await
+        .map_err(|err| ClientError::ExtraAccountResolution(err.to_string()))?;
+
+        Ok(instruction)
+    }
+
+    /// `SendData` of the sender program, called by `sender` through CPI
+    pub fn send_data(
+        &self,
+        payer: &Pubkey,
+        sender: &Pubkey,
+        destination_chain_id: u64,
+        destination: ForeignAddress,
+        payload: Vec<u8>,
+    ) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.sender_config_address().0, false),
+            AccountMeta::new(self.sender_message_address(sender).0, false),
+            AccountMeta::new(*sender, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+
+        let data = Mv3SenderInstruction::SendData {
+            destination_chain_id,
+            payload,
+            destination,
+        };
+
+        Instruction {
+            program_id: self.sender_program_id,
+            accounts,
+            data: data.try_to_vec().unwrap(),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+pub mod accounts;
+pub mod accumulator;
+pub mod bls;
+pub mod error;
+pub mod fetcher;
+pub mod instructions;
+pub mod pda;
+
+pub use mv3_contract_solana;
+pub use mv3_solana_sender;
+
+use solana_program::pubkey::Pubkey;
+
+/// Addresses of a gateway deployment, every builder and PDA of the client is derived from them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gateway {
+    pub program_id: Pubkey,
+    pub sender_program_id: Pubkey,
+}
+
+impl Gateway {
+    pub fn new(program_id: Pubkey, sender_program_id: Pubkey) -> Self {
+        Self {
+            program_id,
+            sender_program_id,
+        }
+    }
+}
+
+impl Default for Gateway {
+    fn default() -> Self {
+        Self::new(mv3_contract_solana::id(), mv3_solana_sender::id())
+    }
+}
@@ -0,0 +1,154 @@
+use mv3_contract_solana::{
+    constants::{
+        ACCUMULATOR_SEED, BOND_SEED, CHAIN_RATE_LIMIT_SEED, CHAIN_SEED, CHANNEL_SEED,
+        CLIENT_RATE_LIMIT_SEED, COMMITMENT_SEED, CONFIG_SEED, GLOBAL_TREASURY, MESSAGE_CLIENT_SEED,
+        MESSAGE_CLIENT_TREASURY_SEED, MESSAGE_SEED, OPTIMISTIC_SEED, ROLES_SEED, ROOT_SEED,
+        VALIDATORS_SEED,
+    },
+    state::config::ForeignAddress,
+};
+use solana_program::{bpf_loader_upgradeable, pubkey::Pubkey};
+
+use crate::Gateway;
+
+impl Gateway {
+    pub fn config_address(&self) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[CONFIG_SEED], &self.program_id)
+    }
+
+    pub fn roles_address(&self) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[ROLES_SEED], &self.program_id)
+    }
+
+    /// BLS validator set inbound messages are verified against while BLS verification is enabled
+    pub fn validators_address(&self) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[VALIDATORS_SEED], &self.program_id)
+    }
+
+    /// Challenge period, minimum bond and watchers of optimistic delivery
+    pub fn optimistic_config_address(&self) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[OPTIMISTIC_SEED], &self.program_id)
+    }
+
+    /// Bond `operator` posts commitments against
+    pub fn bond_address(&self, operator: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[BOND_SEED, operator.as_ref()], &self.program_id)
+    }
+
+    /// Operator commitment to the inbound message `tx_id` of an optimistic client
+    pub fn commitment_address(&self, tx_id: u128) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[COMMITMENT_SEED, &tx_id.to_le_bytes()], &self.program_id)
+    }
+
+    /// Accumulator over the leaves of every outbound message
+    pub fn accumulator_address(&self) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[ACCUMULATOR_SEED], &self.program_id)
+    }
+
+    /// Ordered channel of the messages from `sender` on `source_chain_id` to `receiver`
+    pub fn channel_address(
+        &self,
+        source_chain_id: u64,
+        sender: &ForeignAddress,
+        receiver: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                CHANNEL_SEED,
+                &source_chain_id.to_le_bytes(),
+                sender,
+                receiver.as_ref(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    /// Signed root of the batch `batch` of messages from `source_chain_id`
+    pub fn message_root_address(&self, source_chain_id: u64, batch: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                ROOT_SEED,
+                &source_chain_id.to_le_bytes(),
+                &batch.to_le_bytes(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    /// Registry entry of `chain_id`
+    pub fn chain_address(&self, chain_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[CHAIN_SEED, &chain_id.to_le_bytes()], &self.program_id)
+    }
+
+    pub fn message_client_address(&self, destination_contract: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[MESSAGE_CLIENT_SEED, destination_contract.as_ref()],
+            &self.program_id,
+        )
+    }
+
+    pub fn client_treasury_address(&self, destination_contract: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                MESSAGE_CLIENT_SEED,
+                destination_contract.as_ref(),
+                MESSAGE_CLIENT_TREASURY_SEED,
+            ],
+            &self.program_id,
+        )
+    }
+
+    pub fn global_treasury_address(&self) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[MESSAGE_SEED, GLOBAL_TREASURY], &self.program_id)
+    }
+
+    /// Account the outbound `Send` instruction writes the message to
+    pub fn outbound_message_address(&self) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[MESSAGE_SEED], &self.program_id)
+    }
+
+    /// Account marking `tx_id` as delivered
+    pub fn received_message_address(&self, tx_id: u128) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[MESSAGE_SEED, &tx_id.to_le_bytes()], &self.program_id)
+    }
+
+    pub fn chain_rate_limit_address(&self, chain_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[CHAIN_RATE_LIMIT_SEED, &chain_id.to_le_bytes()],
+            &self.program_id,
+        )
+    }
+
+    pub fn client_rate_limit_address(&self, destination_contract: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[CLIENT_RATE_LIMIT_SEED, destination_contract.as_ref()],
+            &self.program_id,
+        )
+    }
+
+    /// Extra account meta list the receiving program keeps for its message client
+    pub fn extra_account_metas_address(&self, destination_contract: &Pubkey) -> Pubkey {
+        let (message_client, _) = self.message_client_address(destination_contract);
+
+        message_hook::get_extra_account_metas_address(&message_client, destination_contract)
+    }
+
+    pub fn sender_config_address(&self) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[mv3_solana_sender::instructions::CONFIG_SEED],
+            &self.sender_program_id,
+        )
+    }
+
+    pub fn sender_message_address(&self, sender: &Pubkey) -> (Pubkey, u8) {
+        mv3_solana_sender::instructions::get_message_pda(sender, &self.sender_program_id)
+    }
+}
+
+/// ProgramData account of a program deployed with the upgradeable loader
+pub fn program_data_address(program: &Pubkey) -> Pubkey {
+    let (program_data, _) =
+        Pubkey::find_program_address(&[program.as_ref()], &bpf_loader_upgradeable::id());
+
+    program_data
+}
@@ -0,0 +1,81 @@
+use mv3_client::{accumulator::OutboundTree, error::ClientError};
+use mv3_contract_solana::{
+    events::GatewayEvent,
+    state::accumulator::{process_inclusion_proof, OutboundAccumulator},
+    utils::keccak256,
+};
+
+fn leaf(i: u8) -> [u8; 32] {
+    keccak256(&[i])
+}
+
+#[test]
+pub fn test_tree_matches_accumulator() {
+    let mut accumulator = OutboundAccumulator::new();
+    let mut tree = OutboundTree::new();
+
+    assert_eq!(tree.root(), accumulator.root());
+    assert_eq!(tree.proof(0), None);
+
+    for i in 0..9 {
+        assert_eq!(
+            tree.push(leaf(i)).unwrap(),
+            accumulator.append(leaf(i)).unwrap()
+        );
+
+        let root = accumulator.root();
+
+        assert_eq!(tree.root(), root);
+
+        for index in 0..tree.len() {
+            let proof = tree.proof(index).unwrap();
+
+            assert_eq!(
+                process_inclusion_proof(&leaf(index as u8), index, &proof),
+                root
+            );
+        }
+    }
+}
+
+#[test]
+pub fn test_tree_from_events() {
+    let mut accumulator = OutboundAccumulator::new();
+
+    let events: Vec<GatewayEvent> = (0..3)
+        .map(|i| {
+            let index = accumulator.append(leaf(i)).unwrap();
+
+            GatewayEvent::MessageAccumulated {
+                tx_id: u128::from(i),
+                index,
+                leaf: leaf(i),
+                root: accumulator.root(),
+            }
+        })
+        .collect();
+
+    let tree = OutboundTree::from_events(&events).unwrap();
+
+    assert_eq!(tree.len(), 3);
+    assert_eq!(tree.root(), accumulator.root());
+
+    //events have to be read from the first one on
+    assert!(matches!(
+        OutboundTree::from_events(&events[1..]),
+        Err(ClientError::InvalidAccumulatorEvent(1))
+    ));
+
+    //a leaf which does not lead to the reported root is rejected
+    let mut tree = OutboundTree::new();
+
+    let forged = GatewayEvent::MessageAccumulated {
+        tx_id: 0,
+        index: 0,
+        leaf: leaf(9),
+        root: accumulator.root(),
+    };
+
+    assert!(tree.apply(&forged).is_err());
+    assert!(tree.is_empty());
+}
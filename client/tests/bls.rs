@@ -0,0 +1,370 @@
+use mv3_client::{
+    bls::{encode_g1, hash_to_g1, verify_signature, BlsAggregator, BlsPublicKey, BlsSecretKey},
+    error::ClientError,
+};
+use mv3_contract_solana::{
+    bls::{self, BlsKey, G1_GENERATOR, G2_GENERATOR},
+    error::MessengerError,
+    state::validators::ValidatorSet,
+    utils::keccak256,
+};
+
+fn message() -> [u8; 32] {
+    keccak256(b"mv3 bls")
+}
+
+fn validators(count: u8) -> Vec<BlsSecretKey> {
+    (0..count)
+        .map(|i| BlsSecretKey::from_seed(&[b'v', i]))
+        .collect()
+}
+
+fn public_keys(keys: &[BlsSecretKey]) -> Vec<BlsPublicKey> {
+    keys.iter().map(BlsSecretKey::public_key).collect()
+}
+
+fn bls_keys(keys: &[BlsSecretKey]) -> Vec<BlsKey> {
+    keys.iter()
+        .map(|key| {
+            key.public_key()
+                .with_possession(&key.sign_possession())
+                .unwrap()
+        })
+        .collect()
+}
+
+fn validator_set(keys: &[BlsSecretKey], threshold: u16) -> ValidatorSet {
+    let mut set = ValidatorSet::new();
+
+    set.write_keys(0, &bls_keys(keys)).unwrap();
+    set.threshold = threshold;
+    set.check().unwrap();
+
+    set
+}
+
+fn aggregate(keys: &[BlsSecretKey], signers: &[usize], message: &[u8; 32]) -> BlsAggregator {
+    let mut aggregator = BlsAggregator::new(*message, public_keys(keys));
+
+    for &signer in signers {
+        aggregator.add(signer, &keys[signer].sign(message)).unwrap();
+    }
+
+    aggregator
+}
+
+fn bytes<const N: usize>(hex_bytes: &str) -> [u8; N] {
+    let mut bytes = [0; N];
+    hex::decode_to_slice(hex_bytes, &mut bytes).unwrap();
+
+    bytes
+}
+
+//keys are encoded like the arguments of the EVM precompiles
+#[test]
+pub fn test_generator_encoding() {
+    let key = BlsSecretKey::from_bytes(&bytes(
+        "0000000000000000000000000000000000000000000000000000000000000001",
+    ))
+    .unwrap();
+
+    assert_eq!(key.public_key().g1, G1_GENERATOR);
+    assert_eq!(key.public_key().g2, G2_GENERATOR);
+    key.public_key().check().unwrap();
+
+    assert!(BlsSecretKey::from_bytes(&[0; 32]).is_none());
+    assert!(BlsSecretKey::from_bytes(&[0xff; 32]).is_none());
+}
+
+#[test]
+pub fn test_vectors() {
+    assert_eq!(
+        hex::encode(message()),
+        "86fe01ee99062b44fdc36a4468d04a4c8d59ad35221b8ce7a3e58d3226acd6b2"
+    );
+
+    let (skipped_roots, point) = hash_to_g1(&message());
+
+    assert!(skipped_roots.is_empty());
+    assert_eq!(
+        hex::encode(encode_g1(&point)),
+        "22945f23185c99c22283e1a6b07c410bd5977a81997962872b7293455ee9bfdd\
+         0a7525fed449886865876c0a921d394c2e2722b9170067898ba61f43e01d8f6b"
+    );
+
+    let keys = validators(4);
+
+    assert_eq!(
+        hex::encode(keys[0].public_key().g1),
+        "0113741e49c0858d01a7c6bedcc655853c7f1424582c368dcb9999610f7a78f1\
+         2a0b779b1da3831e8123081534949031192d5b8265c479987d1fb7555b5d3bc8"
+    );
+
+    let proof = aggregate(&keys, &[0, 1, 3], &message()).proof();
+
+    assert_eq!(proof.signers, vec![0b1011]);
+    assert_eq!(
+        hex::encode(proof.signature),
+        "0c2aa4ab01bd61a4480b4b1d8862693cd14546d221b1b1ae739df46847337c78\
+         133a640baa12ac41d807e5fa3b4c554c3311c26a0581216da0ddffb885f41fce"
+    );
+}
+
+#[test]
+pub fn test_aggregated_signature_verifies() {
+    let keys = validators(10);
+    let set = validator_set(&keys, 7);
+
+    for key in public_keys(&keys) {
+        key.check().unwrap();
+    }
+
+    let proof = aggregate(&keys, &[0, 2, 3, 4, 5, 8, 9], &message()).proof();
+
+    assert_eq!(proof.signers, vec![0b0011_1101, 0b11]);
+    assert_eq!(set.verify(&message(), &proof), Ok(()));
+
+    let proof = aggregate(&keys, &(0..10).collect::<Vec<_>>(), &message()).proof();
+
+    assert_eq!(set.verify(&message(), &proof), Ok(()));
+}
+
+#[test]
+pub fn test_threshold() {
+    let keys = validators(4);
+    let set = validator_set(&keys, 3);
+
+    let proof = aggregate(&keys, &[1, 2], &message()).proof();
+
+    assert_eq!(
+        set.verify(&message(), &proof),
+        Err(MessengerError::BlsThresholdNotMet)
+    );
+}
+
+#[test]
+pub fn test_forged_proofs() {
+    let keys = validators(4);
+    let set = validator_set(&keys, 2);
+
+    let proof = aggregate(&keys, &[0, 1, 2], &message()).proof();
+
+    assert_eq!(
+        set.verify(&keccak256(b"other"), &proof),
+        Err(MessengerError::InvalidSignature)
+    );
+
+    //claiming a validator which did not sign
+    let mut forged = proof.clone();
+    forged.signers = vec![0b1111];
+
+    assert_eq!(
+        set.verify(&message(), &forged),
+        Err(MessengerError::InvalidSignature)
+    );
+
+    //G2 sum of other signers than the G1 sum
+    let mut forged = proof.clone();
+    forged.aggregate_key = aggregate(&keys, &[0, 1, 3], &message())
+        .proof()
+        .aggregate_key;
+
+    assert_eq!(
+        set.verify(&message(), &forged),
+        Err(MessengerError::InvalidSignature)
+    );
+
+    //the other square root of the message point
+    let mut forged = proof.clone();
+    let (_, point) = hash_to_g1(&message());
+    forged.message_y = encode_g1(&-point)[32..].try_into().unwrap();
+
+    assert_eq!(
+        set.verify(&message(), &forged),
+        Err(MessengerError::InvalidSignature)
+    );
+
+    //signers of validators outside the set
+    let mut forged = proof.clone();
+    forged.signers = vec![0b1_0111];
+
+    assert_eq!(
+        set.verify(&message(), &forged),
+        Err(MessengerError::InvalidSignature)
+    );
+
+    let mut forged = proof;
+    forged.signers = vec![0b111, 0];
+
+    assert_eq!(
+        set.verify(&message(), &forged),
+        Err(MessengerError::InvalidSignature)
+    );
+}
+
+//the message point is the one of the first counter with a point
+#[test]
+pub fn test_message_point_is_canonical() {
+    let keys = validators(3);
+    let set = validator_set(&keys, 2);
+
+    let skipping = (0..=u8::MAX)
+        .map(|i| keccak256(&[i]))
+        .find(|message| hash_to_g1(message).0.len() > 1)
+        .unwrap();
+
+    let proof = aggregate(&keys, &[0, 2], &skipping).proof();
+
+    assert_eq!(set.verify(&skipping, &proof), Ok(()));
+
+    //a point of a later counter
+    let mut forged = proof.clone();
+    forged.skipped_roots.push(forged.skipped_roots[0]);
+
+    assert_eq!(
+        set.verify(&skipping, &forged),
+        Err(MessengerError::InvalidSignature)
+    );
+
+    //a point of an earlier counter, which has none
+    let mut forged = proof.clone();
+    forged.skipped_roots.pop();
+
+    assert_eq!(
+        set.verify(&skipping, &forged),
+        Err(MessengerError::InvalidSignature)
+    );
+
+    let mut forged = proof;
+    forged.skipped_roots[1] = forged.skipped_roots[0];
+
+    assert_eq!(
+        set.verify(&skipping, &forged),
+        Err(MessengerError::InvalidSignature)
+    );
+
+    //skipping the first counter of a message which has a point for it
+    let mut forged = aggregate(&keys, &[0, 2], &message()).proof();
+    forged.skipped_roots = vec![[1; 32]];
+
+    assert_eq!(
+        set.verify(&message(), &forged),
+        Err(MessengerError::InvalidSignature)
+    );
+}
+
+//keys only join the set with a signature of their validator
+#[test]
+pub fn test_proof_of_possession() {
+    let keys = validators(3);
+    let possessions = bls_keys(&keys);
+
+    assert!(matches!(
+        keys[0]
+            .public_key()
+            .with_possession(&keys[1].sign_possession()),
+        Err(ClientError::InvalidPossessionProof)
+    ));
+    assert!(matches!(
+        keys[0]
+            .public_key()
+            .with_possession(&keys[0].sign(&message())),
+        Err(ClientError::InvalidPossessionProof)
+    ));
+
+    //a key cancelling the other keys out of their sum
+    let others = bls::add(&possessions[0].key, &possessions[1].key).unwrap();
+
+    let mut rogue = possessions[2].clone();
+    rogue.key = bls::add(&possessions[2].key, &bls::negate(&others)).unwrap();
+
+    let mut set = ValidatorSet::new();
+
+    assert_eq!(
+        set.write_keys(0, &[possessions[0].clone(), possessions[1].clone(), rogue]),
+        Err(MessengerError::InvalidValidatorSet)
+    );
+
+    let mut swapped = possessions[1].clone();
+    swapped.possession = possessions[0].possession.clone();
+
+    assert_eq!(
+        set.write_keys(0, &[swapped]),
+        Err(MessengerError::InvalidValidatorSet)
+    );
+
+    set.write_keys(0, &possessions).unwrap();
+}
+
+#[test]
+pub fn test_aggregator_rejects_bad_signatures() {
+    let keys = validators(3);
+    let mut aggregator = BlsAggregator::new(message(), public_keys(&keys));
+
+    assert!(matches!(
+        aggregator.add(0, &keys[1].sign(&message())),
+        Err(ClientError::InvalidBlsSignature(0))
+    ));
+    assert!(matches!(
+        aggregator.add(3, &keys[0].sign(&message())),
+        Err(ClientError::UnknownValidator(3))
+    ));
+    assert!(!verify_signature(
+        &message(),
+        &keys[0].public_key(),
+        &keys[0].sign(&keccak256(b"other"))
+    ));
+
+    aggregator.add(2, &keys[2].sign(&message())).unwrap();
+
+    assert_eq!(aggregator.signers(), 1);
+}
+
+#[test]
+pub fn test_validator_set_checks() {
+    let keys = bls_keys(&validators(3));
+    let g1_keys: Vec<_> = keys.iter().map(|key| key.key).collect();
+
+    let mut set = ValidatorSet::new();
+
+    assert_eq!(
+        set.write_keys(1, &keys),
+        Err(MessengerError::InvalidValidatorSet)
+    );
+
+    //keys are written in chunks and the last chunk drops the keys after it
+    set.write_keys(0, &keys[..2]).unwrap();
+    set.write_keys(2, &keys[2..]).unwrap();
+    set.write_keys(1, &keys[1..2]).unwrap();
+
+    assert_eq!(set.bls_keys, g1_keys[..2]);
+
+    set.threshold = 3;
+
+    assert_eq!(set.check(), Err(MessengerError::InvalidValidatorSet));
+
+    set.threshold = 0;
+
+    assert_eq!(set.check(), Err(MessengerError::InvalidValidatorSet));
+
+    set.threshold = 2;
+    set.write_keys(1, &keys[..1]).unwrap();
+
+    assert_eq!(set.check(), Err(MessengerError::InvalidValidatorSet));
+
+    let mut off_curve = keys[0].clone();
+    off_curve.key[63] = 3;
+
+    assert_eq!(
+        set.write_keys(0, &[off_curve]),
+        Err(MessengerError::InvalidValidatorSet)
+    );
+
+    let mut identity = keys[0].clone();
+    identity.key = [0; 64];
+
+    assert_eq!(
+        set.write_keys(0, &[identity]),
+        Err(MessengerError::InvalidValidatorSet)
+    );
+}
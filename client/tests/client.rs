@@ -0,0 +1,283 @@
+use borsh::BorshSerialize;
+use futures::executor::block_on;
+use message_hook::instruction::ProcessMessageInstruction;
+use mv3_client::{
+    error::{decode_instruction_error, decode_program_error, is_retryable, ClientError},
+    fetcher::AccountStore,
+    Gateway,
+};
+use mv3_contract_solana::{
+    error::MessengerError,
+    instruction::{BatchProof, ReceiveMessage},
+    state::{
+        account::{GatewayAccount, DISCRIMINATOR_LEN},
+        config::{MessageClient, MessengerConfig},
+        version::MessengerConfigV0,
+    },
+};
+use solana_program::{
+    instruction::{AccountMeta, InstructionError},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList};
+
+fn receive_data(receiver: Pubkey) -> ReceiveMessage {
+    ReceiveMessage {
+        tx_id: 7,
+        dest_chain_id: 19999999991,
+        receiver,
+        data: vec![vec![1; 65], vec![4, 2]],
+        source_chain_id: 5,
+        sender: [3; 32],
+    }
+}
+
+#[test]
+pub fn test_custom_deployment() {
+    let gateway = Gateway::new(Pubkey::new_unique(), Pubkey::new_unique());
+
+    let payer = Pubkey::new_unique();
+
+    let ix = gateway.initialize_config(&payer, payer, 5);
+
+    assert_eq!(ix.program_id, gateway.program_id);
+    assert_eq!(ix.accounts[1].pubkey, gateway.config_address().0);
+    assert_ne!(
+        gateway.config_address(),
+        Gateway::default().config_address()
+    );
+
+    let ix = gateway.send_data(&payer, &payer, 5, [0; 32], vec![1]);
+
+    assert_eq!(ix.program_id, gateway.sender_program_id);
+    assert_eq!(ix.accounts[0].pubkey, gateway.sender_config_address().0);
+}
+
+#[test]
+pub fn test_receive_message_resolves_extra_accounts() {
+    let gateway = Gateway::default();
+
+    let payer = Pubkey::new_unique();
+    let receiver = Pubkey::new_unique();
+    let extra_account = Pubkey::new_unique();
+
+    let metas = [
+        ExtraAccountMeta::new_with_pubkey(&extra_account, false, true).unwrap(),
+        ExtraAccountMeta::new_with_seeds(
+            &[spl_tlv_account_resolution::seeds::Seed::Literal {
+                bytes: b"vault".to_vec(),
+            }],
+            false,
+            true,
+        )
+        .unwrap(),
+    ];
+
+    let mut meta_list = vec![0; ExtraAccountMetaList::size_of(metas.len()).unwrap()];
+    ExtraAccountMetaList::init::<ProcessMessageInstruction>(&mut meta_list, &metas).unwrap();
+
+    let mut store = AccountStore::default();
+    store.insert(gateway.extra_account_metas_address(&receiver), meta_list);
+
+    let unresolved = gateway.receive_message_unresolved(&payer, receive_data(receiver));
+
+    let ix = block_on(gateway.receive_message(&store, &payer, receive_data(receiver))).unwrap();
+
+    let (vault, _) = Pubkey::find_program_address(&[b"vault"], &receiver);
+
+    assert_eq!(ix.data, unresolved.data);
+    assert_eq!(
+        ix.accounts[..unresolved.accounts.len()],
+        unresolved.accounts
+    );
+    assert_eq!(
+        ix.accounts[unresolved.accounts.len()..],
+        [
+            AccountMeta::new(extra_account, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(receiver, false),
+            AccountMeta::new_readonly(gateway.extra_account_metas_address(&receiver), false),
+        ]
+    );
+}
+
+#[test]
+pub fn test_receive_batched_message_adds_root() {
+    let gateway = Gateway::default();
+
+    let payer = Pubkey::new_unique();
+    let receiver = Pubkey::new_unique();
+
+    let unresolved = gateway.receive_message_unresolved(&payer, receive_data(receiver));
+
+    let batched = gateway.receive_batched_message_unresolved(
+        &payer,
+        receive_data(receiver),
+        BatchProof {
+            batch: 3,
+            proof: vec![[1; 32]],
+        },
+    );
+
+    //the root follows the accounts of ReceiveMessage, ahead of the accounts of the receiver
+    assert_eq!(
+        batched.accounts[..unresolved.accounts.len()],
+        unresolved.accounts
+    );
+    assert_eq!(
+        batched.accounts[unresolved.accounts.len()..],
+        [AccountMeta::new_readonly(
+            gateway.message_root_address(5, 3).0,
+            false
+        )]
+    );
+    assert_ne!(batched.data, unresolved.data);
+}
+
+#[test]
+pub fn test_receive_message_without_meta_list() {
+    let gateway = Gateway::default();
+
+    let payer = Pubkey::new_unique();
+
+    let result = block_on(gateway.receive_message(
+        &AccountStore::default(),
+        &payer,
+        receive_data(Pubkey::new_unique()),
+    ));
+
+    assert!(matches!(
+        result,
+        Err(ClientError::ExtraAccountResolution(_))
+    ));
+}
+
+#[test]
+pub fn test_decode_config() {
+    let gateway = Gateway::default();
+
+    let owner = Pubkey::new_unique();
+
+    let mut store = AccountStore::default();
+
+    assert!(matches!(
+        block_on(gateway.get_config(&store)),
+        Err(ClientError::AccountNotFound(_))
+    ));
+
+    //reallocated accounts keep trailing bytes
+    let mut data = MessengerConfig::new(&owner, &owner, 1).pack().unwrap();
+    data.extend_from_slice(&[0; 16]);
+
+    store.insert(gateway.config_address().0, data);
+
+    let config = block_on(gateway.get_config(&store)).unwrap();
+
+    assert_eq!(config.owner, owner);
+    assert!(config.bridge_enabled());
+
+    //a client stored at the config address must not decode as the config
+    let client = MessageClient {
+        version: MessageClient::VERSION,
+        authority: owner,
+        destination_contract: owner,
+        notify_on_failure: false,
+        supported_chains: vec![],
+        allowed_contracts: vec![],
+        exsig: None,
+        paused: false,
+        pending_authority: None,
+        pending_exsig: None,
+        rate_limit: None,
+        optimistic: false,
+        ordered: false,
+    };
+
+    store.insert(gateway.config_address().0, client.pack().unwrap());
+
+    assert!(matches!(
+        block_on(gateway.get_config(&store)),
+        Err(ClientError::InvalidDiscriminator(_))
+    ));
+}
+
+#[test]
+pub fn test_decode_historical_config_versions() {
+    let gateway = Gateway::default();
+
+    let owner = Pubkey::new_unique();
+
+    let mut store = AccountStore::default();
+
+    //configs of the first deployment are read without migrating them
+    let legacy = MessengerConfigV0 {
+        owner,
+        next_tx_id: 42,
+        enabled_chains: vec![5],
+        whitelists: vec![],
+        bridge_enabled: true,
+        fee_currency: None,
+        bridge_operators: vec![],
+        bridge_supers: vec![],
+        bridge_a_team: vec![],
+        accountant: owner,
+        whitelist_only: false,
+        chainsig: Some([7; 32]),
+    };
+
+    store.insert(gateway.config_address().0, legacy.try_to_vec().unwrap());
+
+    let config = block_on(gateway.get_config(&store)).unwrap();
+
+    assert_eq!(config.version, MessengerConfig::VERSION);
+    assert_eq!(config.owner, owner);
+    assert_eq!(config.next_tx_id(), 42);
+    assert_eq!(config.chainsig(), Some([7; 32]));
+    assert!(!config.inbound_paused());
+
+    //layouts newer than the client are rejected instead of misread
+    let mut data = MessengerConfig::new(&owner, &owner, 1).pack().unwrap();
+    data[DISCRIMINATOR_LEN] = MessengerConfig::VERSION + 1;
+
+    store.insert(gateway.config_address().0, data);
+
+    assert!(matches!(
+        block_on(gateway.get_config(&store)),
+        Err(ClientError::UnsupportedVersion(_, version)) if version == MessengerConfig::VERSION + 1
+    ));
+}
+
+#[test]
+pub fn test_decode_gateway_errors() {
+    let paused = ProgramError::from(MessengerError::SourceChainPaused);
+
+    assert_eq!(
+        decode_program_error(&paused),
+        Some(MessengerError::SourceChainPaused)
+    );
+    assert!(is_retryable(MessengerError::SourceChainPaused));
+
+    //clear once the challenge period passed or the earlier messages of the channel arrived
+    assert!(is_retryable(MessengerError::ChallengePeriodActive));
+    assert!(is_retryable(MessengerError::OutOfOrderMessage));
+
+    let processed = InstructionError::Custom(MessengerError::MessageAlreadyProcessed.code());
+
+    assert_eq!(
+        decode_instruction_error(&processed),
+        Some(MessengerError::MessageAlreadyProcessed)
+    );
+    assert!(!is_retryable(MessengerError::MessageAlreadyProcessed));
+    assert!(!is_retryable(MessengerError::InvalidSignature));
+
+    //failures raised by the runtime or other programs are not gateway errors
+    assert_eq!(
+        decode_program_error(&ProgramError::AccountDataTooSmall),
+        None
+    );
+    assert_eq!(
+        decode_instruction_error(&InstructionError::Custom(6000)),
+        None
+    );
+}
@@ -0,0 +1,374 @@
+//! Machine readable description (IDL) of the gateway programs.
+//!
+//! Type layouts are taken from the borsh schema of the Rust types, so the exported JSON follows
+//! the programs without hand-copying layouts. Every type is referenced by its borsh declaration
+//! (`u64`, `Vec<u8>`, `Option<Pubkey>`, `Array<u8, 32>`, ...), primitives are not listed in
+//! `types`, everything else is.
+
+use std::collections::{BTreeMap, HashMap};
+
+use borsh::schema::{BorshSchema, Declaration, Definition, Fields};
+use serde::Serialize;
+use serde_json::{json, Value};
+use solana_program::{instruction::AccountMeta, pubkey::Pubkey, system_program, sysvar};
+use spl_discriminator::SplDiscriminate;
+
+use crate::{
+    instruction::{
+        InitializeExtraAccountMetaListInstruction, ProcessMessageInstruction,
+        UpdateExtraAccountMetaListInstruction,
+    },
+    EXTRA_ACCOUNT_METAS_SEED,
+};
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Idl {
+    pub name: String,
+    pub version: String,
+    /// None for interfaces which are implemented by other programs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub program_id: Option<String>,
+    pub instructions: Vec<IdlInstruction>,
+    pub accounts: Vec<IdlStateAccount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<IdlEvents>,
+    /// Custom error codes the program fails with
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<IdlErrorCode>,
+    pub types: BTreeMap<String, Value>,
+}
+
+impl Idl {
+    pub fn to_json(&self) -> String {
+        let mut json = serde_json::to_string_pretty(self).unwrap();
+        json.push('\n');
+
+        json
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct IdlInstruction {
+    pub name: String,
+    /// Bytes the instruction data starts with
+    pub discriminator: Vec<u8>,
+    /// Type of the data following the discriminator
+    pub args: Declaration,
+    pub accounts: Vec<IdlAccountItem>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct IdlAccountItem {
+    pub name: String,
+    pub signer: bool,
+    pub writable: bool,
+    /// Optional accounts are only passed in some cases, described by `docs`
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub docs: Option<String>,
+}
+
+impl IdlAccountItem {
+    pub fn new(name: &str, signer: bool, writable: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            signer,
+            writable,
+            optional: false,
+            docs: None,
+        }
+    }
+
+    pub fn optional(mut self, docs: &str) -> Self {
+        self.optional = true;
+        self.docs = Some(docs.to_string());
+
+        self
+    }
+
+    pub fn docs(mut self, docs: &str) -> Self {
+        self.docs = Some(docs.to_string());
+
+        self
+    }
+
+    /// Whether `meta` is a valid value for this account, used to check the IDL against builders
+    pub fn matches(&self, meta: &AccountMeta) -> bool {
+        self.signer == meta.is_signer && self.writable == meta.is_writable
+    }
+}
+
+/// Account derived from the program id and the seeds of its address
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct IdlStateAccount {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: Declaration,
+    pub seeds: Vec<IdlSeed>,
+    /// Bytes the account data starts with, before the encoding of `ty`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discriminator: Option<Vec<u8>>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IdlSeed {
+    /// Utf-8 constant
+    Const { value: String },
+    /// Named value encoded as `ty`, pubkeys as their 32 bytes and integers as little endian
+    Arg {
+        name: String,
+        #[serde(rename = "type")]
+        ty: Declaration,
+    },
+}
+
+impl IdlSeed {
+    pub fn constant(value: &[u8]) -> Self {
+        Self::Const {
+            value: String::from_utf8(value.to_vec()).unwrap(),
+        }
+    }
+
+    pub fn arg(name: &str, ty: &str) -> Self {
+        Self::Arg {
+            name: name.to_string(),
+            ty: ty.to_string(),
+        }
+    }
+}
+
+/// Events logged with `sol_log_data` as `[tag, borsh(event)]`
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct IdlEvents {
+    pub tag: String,
+    #[serde(rename = "type")]
+    pub ty: Declaration,
+}
+
+/// Error returned as `ProgramError::Custom(code)`
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct IdlErrorCode {
+    pub code: u32,
+    pub name: String,
+    pub msg: String,
+}
+
+/// Type definitions collected from borsh schemas
+#[derive(Default)]
+pub struct IdlTypes {
+    definitions: HashMap<Declaration, Definition>,
+    custom: BTreeMap<String, Value>,
+}
+
+impl IdlTypes {
+    pub fn add<T: BorshSchema>(&mut self) -> Declaration {
+        T::add_definitions_recursively(&mut self.definitions);
+
+        T::declaration()
+    }
+
+    /// Adds a type which is not borsh encoded, `definition` describes its layout
+    pub fn add_custom(&mut self, declaration: &str, definition: Value) -> Declaration {
+        self.custom.insert(declaration.to_string(), definition);
+
+        declaration.to_string()
+    }
+
+    /// Instructions of a program whose instruction data is a borsh enum, every variant is an
+    /// instruction discriminated by its index
+    pub fn add_instruction_enum<T: BorshSchema>(
+        &mut self,
+        accounts: impl Fn(&str) -> Vec<IdlAccountItem>,
+    ) -> Vec<IdlInstruction> {
+        let declaration = self.add::<T>();
+
+        let variants = match self.definitions.get(&declaration) {
+            Some(Definition::Enum { variants }) => variants.clone(),
+            _ => panic!("{} is not an enum", declaration),
+        };
+
+        variants
+            .into_iter()
+            .enumerate()
+            .map(|(index, (name, args))| IdlInstruction {
+                discriminator: vec![index as u8],
+                accounts: accounts(&name),
+                name,
+                args,
+            })
+            .collect()
+    }
+
+    pub fn into_json(self) -> BTreeMap<String, Value> {
+        let mut types: BTreeMap<String, Value> = self
+            .definitions
+            .into_iter()
+            .map(|(declaration, definition)| (declaration, definition_to_json(definition)))
+            .collect();
+
+        types.extend(self.custom);
+
+        types
+    }
+}
+
+fn definition_to_json(definition: Definition) -> Value {
+    match definition {
+        Definition::Array { length, elements } => {
+            json!({ "kind": "array", "length": length, "elements": elements })
+        }
+        Definition::Sequence { elements } => json!({ "kind": "vec", "elements": elements }),
+        Definition::Tuple { elements } => json!({ "kind": "tuple", "elements": elements }),
+        Definition::Enum { variants } => json!({
+            "kind": "enum",
+            "variants": variants
+                .into_iter()
+                .map(|(name, ty)| json!({ "name": name, "type": ty }))
+                .collect::<Vec<Value>>(),
+        }),
+        Definition::Struct {
+            fields: Fields::NamedFields(fields),
+        } => json!({
+            "kind": "struct",
+            "fields": fields
+                .into_iter()
+                .map(|(name, ty)| json!({ "name": name, "type": ty }))
+                .collect::<Vec<Value>>(),
+        }),
+        Definition::Struct {
+            fields: Fields::UnnamedFields(elements),
+        } => json!({ "kind": "tuple", "elements": elements }),
+        Definition::Struct {
+            fields: Fields::Empty,
+        } => json!({ "kind": "struct", "fields": [] }),
+    }
+}
+
+/// IDL of the message hook interface which receiving programs implement. Its instructions are
+/// not borsh encoded: they start with an 8 byte spl discriminator followed by the raw payload or
+/// a pod slice of `ExtraAccountMeta`.
+pub fn idl() -> Idl {
+    let mut types = IdlTypes::default();
+
+    let pubkey = types.add::<Pubkey>();
+
+    let process_message = types.add_custom(
+        "ProcessMessage",
+        json!({
+            "kind": "struct",
+            "fields": [{ "name": "data", "type": "Remainder<u8>" }],
+        }),
+    );
+
+    types.add_custom(
+        "Remainder<u8>",
+        json!({ "kind": "remainder", "elements": "u8" }),
+    );
+
+    types.add_custom(
+        "ExtraAccountMeta",
+        json!({
+            "kind": "struct",
+            "fields": [
+                { "name": "discriminator", "type": "u8" },
+                { "name": "address_config", "type": "Array<u8, 32>" },
+                { "name": "is_signer", "type": "bool" },
+                { "name": "is_writable", "type": "bool" },
+            ],
+        }),
+    );
+
+    types.add_custom(
+        "Array<u8, 32>",
+        json!({ "kind": "array", "length": 32, "elements": "u8" }),
+    );
+
+    let extra_account_metas = types.add_custom(
+        "ExtraAccountMetas",
+        json!({
+            "kind": "struct",
+            "fields": [{ "name": "extra_account_metas", "type": "Vec<ExtraAccountMeta>" }],
+        }),
+    );
+
+    types.add_custom(
+        "Vec<ExtraAccountMeta>",
+        json!({ "kind": "vec", "elements": "ExtraAccountMeta" }),
+    );
+
+    let extra_account_meta_list = types.add_custom(
+        "ExtraAccountMetaList",
+        json!({
+            "kind": "tlv",
+            "discriminator": ProcessMessageInstruction::SPL_DISCRIMINATOR_SLICE,
+            "value": "Vec<ExtraAccountMeta>",
+        }),
+    );
+
+    let meta_list_accounts = |with_system_program: bool| {
+        let mut accounts = vec![
+            IdlAccountItem::new("extra_account_metas", false, true),
+            IdlAccountItem::new("message", false, false),
+            IdlAccountItem::new("authority", true, false),
+        ];
+
+        if with_system_program {
+            accounts.push(
+                IdlAccountItem::new("system_program", false, false)
+                    .docs(&system_program::id().to_string()),
+            );
+        }
+
+        accounts
+    };
+
+    Idl {
+        name: "message_hook".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        program_id: None,
+        instructions: vec![
+            IdlInstruction {
+                name: "ProcessMessage".to_string(),
+                discriminator: ProcessMessageInstruction::SPL_DISCRIMINATOR_SLICE.to_vec(),
+                args: process_message,
+                accounts: vec![
+                    IdlAccountItem::new("message", false, false)
+                        .docs("Gateway message client of the receiving program"),
+                    IdlAccountItem::new("sysvar_instructions", false, false)
+                        .docs(&sysvar::instructions::id().to_string()),
+                    IdlAccountItem::new("extra_account_metas", false, false),
+                    IdlAccountItem::new("remaining_accounts", false, false)
+                        .optional("Accounts resolved from the extra account meta list"),
+                ],
+            },
+            IdlInstruction {
+                name: "InitializeExtraAccountMetaList".to_string(),
+                discriminator: InitializeExtraAccountMetaListInstruction::SPL_DISCRIMINATOR_SLICE
+                    .to_vec(),
+                args: extra_account_metas.clone(),
+                accounts: meta_list_accounts(true),
+            },
+            IdlInstruction {
+                name: "UpdateExtraAccountMetaList".to_string(),
+                discriminator: UpdateExtraAccountMetaListInstruction::SPL_DISCRIMINATOR_SLICE
+                    .to_vec(),
+                args: extra_account_metas,
+                accounts: meta_list_accounts(false),
+            },
+        ],
+        accounts: vec![IdlStateAccount {
+            name: "ExtraAccountMetaList".to_string(),
+            ty: extra_account_meta_list,
+            seeds: vec![
+                IdlSeed::constant(EXTRA_ACCOUNT_METAS_SEED),
+                IdlSeed::arg("message", &pubkey),
+            ],
+            discriminator: None,
+        }],
+        events: None,
+        errors: vec![],
+        types: types.into_json(),
+    }
+}
@@ -1,4 +1,6 @@
 mod error;
+#[cfg(not(target_os = "solana"))]
+pub mod idl;
 pub mod instruction;
 pub mod offchain;
 pub mod onchain;
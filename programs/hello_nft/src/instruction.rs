@@ -5,7 +5,7 @@ use message_hook::{get_extra_account_metas_address, instruction::MessageHookInst
 use mpl_token_auth_rules::ID as MPL_TOKEN_AUTH;
 use mpl_token_metadata::ID as MPL_TOKEN_METADATA;
 
-use mv3_contract_solana::{constants::MESSAGE_SEED, utils::get_message_pda};
+use mv3_contract_solana::utils::get_message_pda;
 use solana_program::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
@@ -160,6 +160,7 @@ pub fn init_collection(
 
 pub fn init_extra_account_meta_list(
     program_id: Pubkey,
+    gateway_program_id: Pubkey,
     authority: Pubkey,
     extra_account_metas: Vec<ExtraAccountMeta>,
 ) -> Instruction {
@@ -168,7 +169,7 @@ pub fn init_extra_account_meta_list(
     }
     .pack();
 
-    let message = get_message_pda(&program_id);
+    let message = get_message_pda(&program_id, &gateway_program_id);
 
     let extra_account_meta_key = get_extra_account_metas_address(&message, &program_id);
 
@@ -190,6 +191,11 @@ pub fn init_extra_account_meta_list(
                 is_writable: true,
                 pubkey: system_program::id(),
             },
+            AccountMeta {
+                is_signer: false,
+                is_writable: false,
+                pubkey: gateway_program_id,
+            },
         ],
         data,
     }
@@ -197,6 +203,7 @@ pub fn init_extra_account_meta_list(
 
 pub fn update_extra_account_meta_list(
     program_id: Pubkey,
+    gateway_program_id: Pubkey,
     authority: Pubkey,
     extra_account_metas: Vec<ExtraAccountMeta>,
 ) -> Instruction {
@@ -205,10 +212,7 @@ pub fn update_extra_account_meta_list(
     }
     .pack();
 
-    let (message_pda, _) = Pubkey::find_program_address(
-        &[MESSAGE_SEED, program_id.as_ref()],
-        &mv3_contract_solana::id(),
-    );
+    let message_pda = get_message_pda(&program_id, &gateway_program_id);
 
     let extra_account_meta_address = get_extra_account_metas_address(&message_pda, &program_id);
 
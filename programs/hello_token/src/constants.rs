@@ -1,3 +1 @@
 pub const TOKEN_SEED: &[u8] = b"token";
-
-pub const MV3_KEY: &str = "mv3PxTJXnsExfkFtwbKCo35fGKdtfcowo9xZsmXQ2qJ";
@@ -1,6 +1,3 @@
-use std::str::FromStr;
-
-use mv3_contract_solana::constants::MESSAGE_SEED;
 use solana_program::{
     account_info::AccountInfo,
     entrypoint::ProgramResult,
@@ -11,8 +8,6 @@ use solana_program::{
     system_instruction,
 };
 
-use crate::constants::MV3_KEY;
-
 pub fn check_seeds(
     account: &Pubkey,
     seeds: &[&[u8]],
@@ -73,12 +68,3 @@ pub fn create_account<'a, 'b>(
 
     Ok(())
 }
-
-pub fn get_message_pda(program_id: &Pubkey) -> Pubkey {
-    let (message_key, _) = Pubkey::find_program_address(
-        &[MESSAGE_SEED, program_id.as_ref()],
-        &Pubkey::from_str(MV3_KEY).unwrap(),
-    );
-
-    message_key
-}
@@ -0,0 +1,345 @@
+//! BLS signatures over BN254 verified with the alt_bn128 syscalls. Points use the big endian
+//! encoding of the EVM precompiles (EIP-196/197), so the same keys and signatures are checked by
+//! Solidity verifiers.
+//!
+//! Validators sign in G1 and publish their keys in G1, which the gateway sums with the addition
+//! syscall for the signers of a message. There is no G2 addition syscall, so the relayer supplies
+//! the matching sum of the G2 keys and both sums are bound by a random linear combination:
+//! with `γ = keccak256(message || apk1 || apk2 || σ) mod r` the proof holds when
+//! `e(-(σ + γ·apk1), g2) · e(H(m) + γ·g1, apk2) == 1`.
+//!
+//! `H(m)` is found by try-and-increment: `x = keccak256(m || counter) mod p` for the first counter
+//! with `x³ + 3` a square, `y` being the smaller of its square roots. The relayer supplies `y`,
+//! which the syscalls reject when the point is not on the curve, and for every counter before it
+//! a square root of `-(x³ + 3)`. As `-1` is not a square modulo `p`, those prove `x³ + 3` is not
+//! one, so a message has a single point.
+//!
+//! Keys only join the set with a signature over `possession_message(key)`, otherwise a key
+//! picked as another key minus the sum of the rest would let its owner sign for all of them.
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use ethnum::U256;
+use solana_program::alt_bn128::prelude::{
+    alt_bn128_addition, alt_bn128_multiplication, alt_bn128_pairing,
+};
+
+use crate::{error::MessengerError, utils::keccak256};
+
+/// Uncompressed G1 point, `x || y`
+pub type G1Point = [u8; 64];
+
+/// Uncompressed G2 point, `x_im || x_re || y_im || y_re`
+pub type G2Point = [u8; 128];
+
+/// Modulus of the base field
+pub const FIELD_MODULUS: U256 = U256::from_words(
+    0x30644e72e131a029b85045b68181585d,
+    0x97816a916871ca8d3c208c16d87cfd47,
+);
+
+/// Order of G1 and G2
+pub const GROUP_ORDER: U256 = U256::from_words(
+    0x30644e72e131a029b85045b68181585d,
+    0x2833e84879b9709143e1f593f0000001,
+);
+
+pub const G1_GENERATOR: G1Point = {
+    let mut point = [0; 64];
+    point[31] = 1;
+    point[63] = 2;
+
+    point
+};
+
+pub const G2_GENERATOR: G2Point = {
+    let words = [
+        (
+            0x198e9393920d483a7260bfb731fb5d25,
+            0xf1aa493335a9e71297e485b7aef312c2,
+        ),
+        (
+            0x1800deef121f1e76426a00665e5c4479,
+            0x674322d4f75edadd46debd5cd992f6ed,
+        ),
+        (
+            0x090689d0585ff075ec9e99ad690c3395,
+            0xbc4b313370b38ef355acdadcd122975b,
+        ),
+        (
+            0x12c85ea5db8c6deb4aab71808dcb408f,
+            0xe3d1e7690c43d37b4ce6cc0166fa7daa,
+        ),
+    ];
+
+    let mut point = [0; 128];
+    let mut i = 0;
+
+    while i < 4 {
+        let (hi, lo): (u128, u128) = words[i];
+        let (hi, lo) = (hi.to_be_bytes(), lo.to_be_bytes());
+        let mut j = 0;
+
+        while j < 16 {
+            point[32 * i + j] = hi[j];
+            point[32 * i + 16 + j] = lo[j];
+            j += 1;
+        }
+
+        i += 1;
+    }
+
+    point
+};
+
+/// Tag of the messages validators prove they hold the secret of their key with
+pub const POSSESSION_TAG: &[u8] = b"MessageV3 BLS proof of possession";
+
+/// Aggregated signature of the validators over a message, carried in place of the chainsig
+/// signature of `ReceiveMessage`
+#[derive(
+    BorshDeserialize, BorshSerialize, BorshSchema, Clone, Debug, PartialEq, Eq, PartialOrd,
+)]
+pub struct BlsProof {
+    /// Bit `i % 8` of byte `i / 8` is set when validator `i` signed, one byte per 8 validators
+    pub signers: Vec<u8>,
+    /// Sum of the signatures of the signers
+    pub signature: G1Point,
+    /// Sum of the G2 keys of the signers
+    pub aggregate_key: G2Point,
+    /// Square roots of `-(x³ + 3)` for the counters skipped before the one `H(m)` was found with
+    pub skipped_roots: Vec<[u8; 32]>,
+    /// `y` coordinate of `H(m)`
+    pub message_y: [u8; 32],
+}
+
+/// G1 key of a validator joining the set
+#[derive(
+    BorshDeserialize, BorshSerialize, BorshSchema, Clone, Debug, PartialEq, Eq, PartialOrd,
+)]
+pub struct BlsKey {
+    pub key: G1Point,
+    /// Signature of the validator alone over `possession_message(key)`
+    pub possession: BlsProof,
+}
+
+impl BlsProof {
+    /// Whether validator `index` signed
+    pub fn signed(&self, index: usize) -> bool {
+        self.signers
+            .get(index / 8)
+            .is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+    }
+}
+
+/// `x` coordinate candidate of `H(message)` for `counter`
+pub fn hash_to_field(message: &[u8; 32], counter: u8) -> [u8; 32] {
+    let mut input = [0; 33];
+    input[..32].copy_from_slice(message);
+    input[32] = counter;
+
+    (U256::from_be_bytes(keccak256(&input)) % FIELD_MODULUS).to_be_bytes()
+}
+
+fn add_mod(a: U256, b: U256) -> U256 {
+    //both are below the modulus, which is below 2^254
+    let sum = a + b;
+
+    if sum >= FIELD_MODULUS {
+        sum - FIELD_MODULUS
+    } else {
+        sum
+    }
+}
+
+fn mul_mod(a: U256, b: U256) -> U256 {
+    (0..256 - b.leading_zeros())
+        .rev()
+        .fold(U256::ZERO, |product, bit| {
+            let product = add_mod(product, product);
+
+            if (b >> bit) & 1 == 1 {
+                add_mod(product, a)
+            } else {
+                product
+            }
+        })
+}
+
+/// `x³ + 3`, the square of the `y` coordinates of the points at `x`
+pub fn curve_rhs(x: U256) -> U256 {
+    add_mod(mul_mod(mul_mod(x, x), x), U256::new(3))
+}
+
+/// `H(message)` for the `skipped_roots` and `y` supplied by the relayer. Only the skipped counters
+/// and `y` are checked here, whether the point is on the curve is checked by the syscalls it is
+/// passed to.
+pub fn message_point(
+    message: &[u8; 32],
+    skipped_roots: &[[u8; 32]],
+    y: &[u8; 32],
+) -> Result<G1Point, MessengerError> {
+    let counter =
+        u8::try_from(skipped_roots.len()).map_err(|_| MessengerError::InvalidSignature)?;
+
+    for (skipped, root) in skipped_roots.iter().enumerate() {
+        let root = U256::from_be_bytes(*root);
+        let x = U256::from_be_bytes(hash_to_field(message, skipped as u8));
+
+        //a zero root would also pass for `x³ + 3 = 0`, which is a square
+        if root == 0 || root >= FIELD_MODULUS || add_mod(mul_mod(root, root), curve_rhs(x)) != 0 {
+            return Err(MessengerError::InvalidSignature);
+        }
+    }
+
+    if U256::from_be_bytes(*y) > (FIELD_MODULUS - 1) / 2 {
+        return Err(MessengerError::InvalidSignature);
+    }
+
+    let mut point = [0; 64];
+    point[..32].copy_from_slice(&hash_to_field(message, counter));
+    point[32..].copy_from_slice(y);
+
+    Ok(point)
+}
+
+/// Message a validator signs with its key alone to prove it holds the secret of `key`
+pub fn possession_message(key: &G1Point) -> [u8; 32] {
+    let mut input = Vec::with_capacity(POSSESSION_TAG.len() + 64);
+    input.extend_from_slice(POSSESSION_TAG);
+    input.extend_from_slice(key);
+
+    keccak256(&input)
+}
+
+pub fn add(a: &G1Point, b: &G1Point) -> Result<G1Point, MessengerError> {
+    let mut input = [0; 128];
+    input[..64].copy_from_slice(a);
+    input[64..].copy_from_slice(b);
+
+    alt_bn128_addition(&input)
+        .ok()
+        .and_then(|sum| sum.try_into().ok())
+        .ok_or(MessengerError::InvalidSignature)
+}
+
+pub fn mul(point: &G1Point, scalar: &[u8; 32]) -> Result<G1Point, MessengerError> {
+    let mut input = [0; 96];
+    input[..64].copy_from_slice(point);
+    input[64..].copy_from_slice(scalar);
+
+    alt_bn128_multiplication(&input)
+        .ok()
+        .and_then(|product| product.try_into().ok())
+        .ok_or(MessengerError::InvalidSignature)
+}
+
+pub fn negate(point: &G1Point) -> G1Point {
+    if *point == [0; 64] {
+        return *point;
+    }
+
+    let mut y = [0; 32];
+    y.copy_from_slice(&point[32..]);
+
+    let mut negated = *point;
+    negated[32..].copy_from_slice(&(FIELD_MODULUS - U256::from_be_bytes(y)).to_be_bytes());
+
+    negated
+}
+
+/// Checks `key` is a point of G1 other than the identity
+pub fn check_public_key(key: &G1Point) -> Result<(), MessengerError> {
+    if *key == [0; 64] || add(key, &[0; 64]).is_err() {
+        return Err(MessengerError::InvalidValidatorSet);
+    }
+
+    Ok(())
+}
+
+/// Checks `key` is a valid key whose validator proved it holds its secret
+pub fn check_possession(key: &BlsKey) -> Result<(), MessengerError> {
+    check_public_key(&key.key)?;
+
+    verify(
+        &possession_message(&key.key),
+        &key.possession,
+        &[key.key],
+        1,
+    )
+    .map_err(|_| MessengerError::InvalidValidatorSet)
+}
+
+/// Challenge binding the two sums of the keys of the signers
+pub fn challenge(
+    message: &[u8; 32],
+    aggregate_key_g1: &G1Point,
+    aggregate_key_g2: &G2Point,
+    signature: &G1Point,
+) -> [u8; 32] {
+    let mut input = Vec::with_capacity(32 + 64 + 128 + 64);
+    input.extend_from_slice(message);
+    input.extend_from_slice(aggregate_key_g1);
+    input.extend_from_slice(aggregate_key_g2);
+    input.extend_from_slice(signature);
+
+    (U256::from_be_bytes(keccak256(&input)) % GROUP_ORDER).to_be_bytes()
+}
+
+/// Checks `proof` holds signatures over `message` of at least `threshold` of the validators with
+/// the G1 keys `keys`
+pub fn verify(
+    message: &[u8; 32],
+    proof: &BlsProof,
+    keys: &[G1Point],
+    threshold: u16,
+) -> Result<(), MessengerError> {
+    if proof.signers.len() != keys.len().div_ceil(8) {
+        return Err(MessengerError::InvalidSignature);
+    }
+
+    if (keys.len()..proof.signers.len() * 8).any(|index| proof.signed(index)) {
+        return Err(MessengerError::InvalidSignature);
+    }
+
+    let mut aggregate_key = [0; 64];
+    let mut signers = 0;
+
+    for (index, key) in keys.iter().enumerate() {
+        if proof.signed(index) {
+            aggregate_key = add(&aggregate_key, key)?;
+            signers += 1;
+        }
+    }
+
+    if signers < threshold {
+        return Err(MessengerError::BlsThresholdNotMet);
+    }
+
+    let gamma = challenge(
+        message,
+        &aggregate_key,
+        &proof.aggregate_key,
+        &proof.signature,
+    );
+
+    let left = negate(&add(&proof.signature, &mul(&aggregate_key, &gamma)?)?);
+
+    let right = add(
+        &message_point(message, &proof.skipped_roots, &proof.message_y)?,
+        &mul(&G1_GENERATOR, &gamma)?,
+    )?;
+
+    let mut input = Vec::with_capacity(2 * (64 + 128));
+    input.extend_from_slice(&left);
+    input.extend_from_slice(&G2_GENERATOR);
+    input.extend_from_slice(&right);
+    input.extend_from_slice(&proof.aggregate_key);
+
+    let output = alt_bn128_pairing(&input).map_err(|_| MessengerError::InvalidSignature)?;
+
+    if output.last() != Some(&1) {
+        return Err(MessengerError::InvalidSignature);
+    }
+
+    Ok(())
+}
@@ -1,16 +1,33 @@
 pub const CONFIG_SEED: &[u8] = b"config";
+pub const ROLES_SEED: &[u8] = b"roles";
+pub const CHAIN_SEED: &[u8] = b"chain";
 pub const MESSAGE_SEED: &[u8] = b"message";
 pub const MESSAGE_CLIENT_SEED: &[u8] = b"message-client";
 pub const MESSAGE_CLIENT_TREASURY_SEED: &[u8] = b"message-client-treasury";
 pub const GLOBAL_TREASURY: &[u8] = b"global-treasury";
+pub const CHAIN_RATE_LIMIT_SEED: &[u8] = b"chain-rate-limit";
+pub const CLIENT_RATE_LIMIT_SEED: &[u8] = b"client-rate-limit";
+pub const VALIDATORS_SEED: &[u8] = b"validators";
+pub const OPTIMISTIC_SEED: &[u8] = b"optimistic";
+pub const BOND_SEED: &[u8] = b"bond";
+pub const COMMITMENT_SEED: &[u8] = b"commitment";
+pub const ROOT_SEED: &[u8] = b"root";
+pub const ACCUMULATOR_SEED: &[u8] = b"accumulator";
+pub const CHANNEL_SEED: &[u8] = b"channel";
 
 //TODO: set these two once caller program is implemented
 pub const CALLER_PROGRAM: &str = "";
 
 pub const CALLER_INSTRUCTION_DISCRIMINATOR: u8 = 1;
 
+/// Chain ID Solana is known by to the other gateways. Configs stored before the chain ID are
+/// upgraded with it.
 pub const SOLANA_CHAIN_ID: u64 = 19999999991;
 
 pub const PREFIX: &str = "\x19Ethereum Signed Message:\n";
 
 pub const TX_FEE: u64 = 5000000;
+
+/// Seconds the previous exsig of a client is still accepted once a rotation activated, for the
+/// messages signed before the rotation which are still in flight
+pub const EXSIG_GRACE_PERIOD: i64 = 24 * 60 * 60;
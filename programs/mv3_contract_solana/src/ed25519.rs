@@ -0,0 +1,142 @@
+//! Ed25519 signatures of validators over inbound messages. They are checked by an Ed25519
+//! program instruction right before `ReceiveMessage`, which the gateway reads back through the
+//! instructions sysvar. Only instructions holding their keys, signatures and message inline are
+//! accepted, so a signature can't be taken from another instruction of the transaction.
+
+use solana_program::{
+    account_info::AccountInfo,
+    ed25519_program,
+    instruction::Instruction,
+    program_error::ProgramError,
+    pubkey::{Pubkey, PUBKEY_BYTES},
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked, ID},
+};
+
+use crate::{error::MessengerError, utils::check_keys_eq};
+
+pub const SIGNATURE_LEN: usize = 64;
+
+/// Signature count and padding byte preceding the offsets
+pub const OFFSETS_START: usize = 2;
+
+/// Seven little endian u16: signature offset and instruction, public key offset and
+/// instruction, message offset, size and instruction
+pub const OFFSETS_LEN: usize = 14;
+
+/// Instruction index the Ed25519 program reads as the instruction holding the offsets
+pub const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, MessengerError> {
+    data.get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .ok_or(MessengerError::InvalidPreInstruction)
+}
+
+/// Public keys whose signatures over `message` were checked by the Ed25519 program instruction
+/// with data `data`. Signatures over any other message are rejected.
+pub fn signers(data: &[u8], message: &[u8; 32]) -> Result<Vec<Pubkey>, MessengerError> {
+    let count = usize::from(*data.first().ok_or(MessengerError::InvalidPreInstruction)?);
+
+    let mut signers = Vec::with_capacity(count);
+
+    for index in 0..count {
+        let start = OFFSETS_START + index * OFFSETS_LEN;
+
+        let offsets = (0..7)
+            .map(|field| read_u16(data, start + 2 * field))
+            .collect::<Result<Vec<u16>, MessengerError>>()?;
+
+        let (public_key_offset, message_offset, message_size) =
+            (offsets[2], offsets[4], offsets[5]);
+
+        if [offsets[1], offsets[3], offsets[6]] != [CURRENT_INSTRUCTION; 3] {
+            return Err(MessengerError::InvalidPreInstruction);
+        }
+
+        let signed = data
+            .get(
+                usize::from(message_offset)
+                    ..usize::from(message_offset) + usize::from(message_size),
+            )
+            .ok_or(MessengerError::InvalidPreInstruction)?;
+
+        if signed != message {
+            return Err(MessengerError::InvalidPreInstruction);
+        }
+
+        let public_key = data
+            .get(usize::from(public_key_offset)..usize::from(public_key_offset) + PUBKEY_BYTES)
+            .ok_or(MessengerError::InvalidPreInstruction)?;
+
+        signers.push(Pubkey::try_from(public_key).map_err(|_| MessengerError::InvalidInstruction)?);
+    }
+
+    Ok(signers)
+}
+
+/// Public keys whose signatures over `message` were checked by the Ed25519 program instruction
+/// right before the executing one, read from the instructions sysvar `sysvar_instructions`
+pub fn previous_instruction_signers(
+    sysvar_instructions: &AccountInfo,
+    message: &[u8; 32],
+) -> Result<Vec<Pubkey>, ProgramError> {
+    check_keys_eq(sysvar_instructions.key, &ID)?;
+
+    let current_ix_index = load_current_index_checked(sysvar_instructions)?;
+
+    let previous_ix_index = current_ix_index
+        .checked_sub(1)
+        .ok_or(MessengerError::InvalidInstructionIndex)?;
+
+    let previous_ix =
+        load_instruction_at_checked(usize::from(previous_ix_index), sysvar_instructions)
+            .map_err(|_| MessengerError::InvalidInstructionIndex)?;
+
+    if previous_ix.program_id != ed25519_program::ID {
+        return Err(MessengerError::InvalidPreInstruction.into());
+    }
+
+    Ok(signers(&previous_ix.data, message)?)
+}
+
+/// Ed25519 program instruction checking `signatures` over `message`, to be placed right before
+/// the `ReceiveMessage` of the message
+pub fn verify_instruction(
+    signatures: &[(Pubkey, [u8; SIGNATURE_LEN])],
+    message: &[u8; 32],
+) -> Instruction {
+    let entries_start = OFFSETS_START + signatures.len() * OFFSETS_LEN;
+    let message_offset = entries_start + signatures.len() * (PUBKEY_BYTES + SIGNATURE_LEN);
+
+    let mut data = vec![signatures.len() as u8, 0];
+    let mut entries = Vec::with_capacity(message_offset - entries_start + message.len());
+
+    for (public_key, signature) in signatures {
+        let public_key_offset = entries_start + entries.len();
+        let signature_offset = public_key_offset + PUBKEY_BYTES;
+
+        for field in [
+            signature_offset as u16,
+            CURRENT_INSTRUCTION,
+            public_key_offset as u16,
+            CURRENT_INSTRUCTION,
+            message_offset as u16,
+            message.len() as u16,
+            CURRENT_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+
+        entries.extend_from_slice(public_key.as_ref());
+        entries.extend_from_slice(signature);
+    }
+
+    entries.extend_from_slice(message);
+    data.extend_from_slice(&entries);
+
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
@@ -0,0 +1,67 @@
+//! EIP-712 hashing of the digests signed for inbound messages. The gateway program ID is the
+//! `salt` of the domain, so EVM signers compute the digest with the standard
+//! `keccak256(abi.encodePacked("\x19\x01", domainSeparator, structHash))` of their typed data
+//! libraries.
+
+use solana_program::pubkey::Pubkey;
+
+use crate::utils::keccak256;
+
+pub const DOMAIN_NAME: &str = "MessageV3";
+
+pub const DOMAIN_VERSION: &str = "1";
+
+pub const DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,bytes32 salt)";
+
+pub const MESSAGE_TYPE: &str = "Message(uint256 txId,uint256 sourceChainId,uint256 destChainId,bytes32 sender,bytes32 receiver,bytes32 gateway,bytes32 payloadHash)";
+
+pub const ROOT_TYPE: &str = "MessageRoot(uint256 sourceChainId,uint256 destChainId,uint256 batch,bytes32 root,bytes32 gateway)";
+
+/// ABI encoding of an unsigned integer, big endian left padded to 32 bytes
+pub fn encode_uint(value: u128) -> [u8; 32] {
+    let mut encoded = [0; 32];
+    encoded[16..].copy_from_slice(&value.to_be_bytes());
+
+    encoded
+}
+
+/// Encoding of `string` and `bytes` members, which are hashed
+pub fn encode_bytes(value: &[u8]) -> [u8; 32] {
+    keccak256(value)
+}
+
+/// `hashStruct` of a struct of type `type_string` with the encoded `members`
+pub fn hash_struct(type_string: &str, members: &[[u8; 32]]) -> [u8; 32] {
+    let mut encoded = Vec::with_capacity(32 * (members.len() + 1));
+    encoded.extend_from_slice(&keccak256(type_string.as_bytes()));
+
+    for member in members {
+        encoded.extend_from_slice(member);
+    }
+
+    keccak256(&encoded)
+}
+
+/// Domain of the gateway deployed at `gateway` on the chain `chain_id`
+pub fn domain_separator(chain_id: u64, gateway: &Pubkey) -> [u8; 32] {
+    hash_struct(
+        DOMAIN_TYPE,
+        &[
+            encode_bytes(DOMAIN_NAME.as_bytes()),
+            encode_bytes(DOMAIN_VERSION.as_bytes()),
+            encode_uint(u128::from(chain_id)),
+            gateway.to_bytes(),
+        ],
+    )
+}
+
+/// Hash signed for the struct hashed to `struct_hash` in the domain `domain_separator`
+pub fn typed_data_hash(domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> [u8; 32] {
+    let mut encoded = Vec::with_capacity(2 + 32 + 32);
+    encoded.extend_from_slice(b"\x19\x01");
+    encoded.extend_from_slice(domain_separator);
+    encoded.extend_from_slice(struct_hash);
+
+    keccak256(&encoded)
+}
@@ -1,52 +1,217 @@
 use solana_program::program_error::ProgramError;
 use thiserror::Error;
 
-#[derive(Clone, Debug, Error)]
+/// Failures of the gateway, returned as `ProgramError::Custom(code)`. Codes are part of the
+/// interface relied on by relayers and clients: they are assigned explicitly, never reordered and
+/// never reused for another failure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+#[repr(u32)]
 pub enum MessengerError {
     #[error("MessageV3: Pubkey missmatch")]
-    PublicKeyMissmatch,
+    PublicKeyMissmatch = 0,
     #[error("MessageV3: Bridge is not enabled")]
-    BrigdeNotEnabled,
+    BrigdeNotEnabled = 1,
     #[error("MessageV3: caller is not an operator")]
-    CallerNotOperator,
+    CallerNotOperator = 2,
     #[error("MessageV3: caller is not an ateam")]
-    CallerNotATeam,
+    CallerNotATeam = 3,
     #[error("MessageV3: caller is not a super")]
-    CallerNotSuper,
+    CallerNotSuper = 4,
     #[error("MessageV3: chain not supported")]
-    ChainNotSupported,
+    ChainNotSupported = 5,
     #[error("MessageV3: caller not whitelisted")]
-    CallerNotWhitelisted,
+    CallerNotWhitelisted = 6,
     #[error("MessageV3: Config already initialized")]
-    ConfigInitialized,
+    ConfigInitialized = 7,
     #[error("MessageV3: Invalid account seeds")]
-    InvalidAccountSeeds,
+    InvalidAccountSeeds = 8,
     #[error("MessageV3: Account not signer")]
-    AccountNotSigner,
+    AccountNotSigner = 9,
     #[error("MessageV3: Invalid role for action")]
-    InvalidRoleForAction,
+    InvalidRoleForAction = 10,
     #[error("MessageV3: Invalid instruction index")]
-    InvalidInstructionIndex,
+    InvalidInstructionIndex = 11,
     #[error("MessageV3: Invalid pre-instruction")]
-    InvalidPreInstruction,
+    InvalidPreInstruction = 12,
     #[error("MessageV3: Exsig already added for given recipient")]
-    ExsigExists,
+    ExsigExists = 13,
     #[error("MessageV3: Invalid signature")]
-    InvalidSignature,
+    InvalidSignature = 14,
     #[error("MessageV3: Invalid instruction")]
-    InvalidInstruction,
+    InvalidInstruction = 15,
     #[error("MessageV3: Missing validation account info!")]
-    MissingValidationAccountInfo,
+    MissingValidationAccountInfo = 16,
     #[error("MessageV3: Invalid client program id!")]
-    InvalidClientProgramId,
+    InvalidClientProgramId = 17,
     #[error("MessageV3: Invalid client program update authority!")]
-    InvalidUpdateAuthority,
+    InvalidUpdateAuthority = 18,
     #[error("MessageV3: Message already processed!")]
-    MessageAlreadyProcessed,
+    MessageAlreadyProcessed = 19,
+    #[error("MessageV3: caller is not an owner")]
+    CallerNotOwner = 20,
+    #[error("MessageV3: Inbound messages are paused")]
+    InboundPaused = 21,
+    #[error("MessageV3: Outbound messages are paused")]
+    OutboundPaused = 22,
+    #[error("MessageV3: Source chain is paused")]
+    SourceChainPaused = 23,
+    #[error("MessageV3: Destination chain is paused")]
+    DestinationChainPaused = 24,
+    #[error("MessageV3: Message client is paused")]
+    ClientPaused = 25,
+    #[error("MessageV3: Client authority can only be changed through authority transfer")]
+    AuthorityTransferRequired = 26,
+    #[error("MessageV3: Caller is not the pending client authority")]
+    NotPendingAuthority = 27,
+    #[error("MessageV3: Message client has to be paused before closing")]
+    ClientNotPaused = 28,
+    #[error("MessageV3: Rate limit exceeded, retry later")]
+    RateLimitExceeded = 29,
+    #[error("MessageV3: Account is not owned by the gateway")]
+    InvalidAccountOwner = 30,
+    #[error("MessageV3: Account holds a different type")]
+    InvalidAccountDiscriminator = 31,
+    #[error("MessageV3: Account was already migrated")]
+    AccountAlreadyMigrated = 32,
+    #[error("MessageV3: Account layout version is not supported, migrate it first")]
+    UnsupportedAccountVersion = 33,
+    #[error("MessageV3: Message payload is missing")]
+    MissingMessagePayload = 34,
+    #[error("MessageV3: Message payload is malformed")]
+    InvalidMessagePayload = 35,
+    #[error("MessageV3: Arithmetic overflow")]
+    ArithmeticOverflow = 36,
+    #[error("MessageV3: Caller program is not configured")]
+    CallerProgramNotConfigured = 37,
+    #[error("MessageV3: Fewer confirmations than the destination chain requires")]
+    InsufficientConfirmations = 38,
+    #[error("MessageV3: Chain name is empty or too long")]
+    InvalidChainName = 39,
+    #[error("MessageV3: Address does not match the address format of the chain")]
+    InvalidForeignAddress = 40,
+    #[error("MessageV3: Validator set is invalid")]
+    InvalidValidatorSet = 41,
+    #[error("MessageV3: Fewer BLS signers than the validator threshold")]
+    BlsThresholdNotMet = 42,
+    #[error("MessageV3: Fewer Ed25519 signers than the validator threshold")]
+    Ed25519ThresholdNotMet = 43,
+    #[error("MessageV3: Optimistic delivery config is invalid")]
+    InvalidOptimisticConfig = 44,
+    #[error("MessageV3: Operator bond is below the minimum")]
+    InsufficientBond = 45,
+    #[error("MessageV3: Challenge period has not ended")]
+    ChallengePeriodActive = 46,
+    #[error("MessageV3: Challenge period has ended")]
+    ChallengePeriodEnded = 47,
+    #[error("MessageV3: Message commitment was challenged")]
+    CommitmentChallenged = 48,
+    #[error("MessageV3: Caller is not a registered watcher")]
+    CallerNotWatcher = 49,
+    #[error("MessageV3: Client does not accept optimistic delivery")]
+    ClientNotOptimistic = 50,
+    #[error("MessageV3: Message root of the batch was already posted")]
+    RootAlreadyPosted = 51,
+    #[error("MessageV3: Merkle proof does not match the message root")]
+    InvalidMerkleProof = 52,
+    #[error("MessageV3: Outbound message accumulator is full")]
+    AccumulatorFull = 53,
+    #[error("MessageV3: Message is not the next one of its ordered channel")]
+    OutOfOrderMessage = 54,
+    #[error("MessageV3: Evidence does not contradict the commitment")]
+    NoFraudProven = 55,
+    #[error("MessageV3: Previous exsig is still accepted, rotate again after its grace period")]
+    ExsigRotationInProgress = 56,
+    #[error("MessageV3: Message commitment is disputed by a watcher")]
+    CommitmentDisputed = 57,
+    #[error("MessageV3: Message commitment is not disputed")]
+    CommitmentNotDisputed = 58,
+    #[error("MessageV3: Dispute period has not ended")]
+    DisputePeriodActive = 59,
+    #[error("MessageV3: Message is larger than the byte limit, it can never be sent")]
+    MessageTooLarge = 60,
+}
+
+impl MessengerError {
+    /// Code the error is returned with
+    pub fn code(self) -> u32 {
+        self as u32
+    }
+
+    /// Error returned with `code`, `None` for codes the gateway does not define
+    pub fn from_code(code: u32) -> Option<Self> {
+        use MessengerError::*;
+
+        let error = match code {
+            0 => PublicKeyMissmatch,
+            1 => BrigdeNotEnabled,
+            2 => CallerNotOperator,
+            3 => CallerNotATeam,
+            4 => CallerNotSuper,
+            5 => ChainNotSupported,
+            6 => CallerNotWhitelisted,
+            7 => ConfigInitialized,
+            8 => InvalidAccountSeeds,
+            9 => AccountNotSigner,
+            10 => InvalidRoleForAction,
+            11 => InvalidInstructionIndex,
+            12 => InvalidPreInstruction,
+            13 => ExsigExists,
+            14 => InvalidSignature,
+            15 => InvalidInstruction,
+            16 => MissingValidationAccountInfo,
+            17 => InvalidClientProgramId,
+            18 => InvalidUpdateAuthority,
+            19 => MessageAlreadyProcessed,
+            20 => CallerNotOwner,
+            21 => InboundPaused,
+            22 => OutboundPaused,
+            23 => SourceChainPaused,
+            24 => DestinationChainPaused,
+            25 => ClientPaused,
+            26 => AuthorityTransferRequired,
+            27 => NotPendingAuthority,
+            28 => ClientNotPaused,
+            29 => RateLimitExceeded,
+            30 => InvalidAccountOwner,
+            31 => InvalidAccountDiscriminator,
+            32 => AccountAlreadyMigrated,
+            33 => UnsupportedAccountVersion,
+            34 => MissingMessagePayload,
+            35 => InvalidMessagePayload,
+            36 => ArithmeticOverflow,
+            37 => CallerProgramNotConfigured,
+            38 => InsufficientConfirmations,
+            39 => InvalidChainName,
+            40 => InvalidForeignAddress,
+            41 => InvalidValidatorSet,
+            42 => BlsThresholdNotMet,
+            43 => Ed25519ThresholdNotMet,
+            44 => InvalidOptimisticConfig,
+            45 => InsufficientBond,
+            46 => ChallengePeriodActive,
+            47 => ChallengePeriodEnded,
+            48 => CommitmentChallenged,
+            49 => CallerNotWatcher,
+            50 => ClientNotOptimistic,
+            51 => RootAlreadyPosted,
+            52 => InvalidMerkleProof,
+            53 => AccumulatorFull,
+            54 => OutOfOrderMessage,
+            55 => NoFraudProven,
+            56 => ExsigRotationInProgress,
+            57 => CommitmentDisputed,
+            58 => CommitmentNotDisputed,
+            59 => DisputePeriodActive,
+            60 => MessageTooLarge,
+            _ => return None,
+        };
+
+        Some(error)
+    }
 }
 
 impl From<MessengerError> for ProgramError {
     fn from(value: MessengerError) -> Self {
-        ProgramError::Custom(value as u32)
+        ProgramError::Custom(value.code())
     }
 }
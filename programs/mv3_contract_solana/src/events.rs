@@ -0,0 +1,243 @@
+//BorshSchema derives helper structs for enum variants which are never read
+#![allow(dead_code)]
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{log::sol_log_data, pubkey::Pubkey};
+
+use crate::{
+    instruction::{PauseTarget, RateLimitTarget},
+    state::{
+        account::AccountKind,
+        chain::{AddressFormat, ChainFees},
+        config::{DigestScheme, ForeignAddress, Role},
+        rate_limit::RateLimitConfig,
+    },
+};
+
+/// Prefix of every event logged by the gateway, events are logged as
+/// `Program data: <base64(EVENT_TAG)> <base64(borsh(GatewayEvent))>`.
+pub const EVENT_TAG: &[u8] = b"mv3-event";
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, Debug, Clone, PartialEq)]
+pub enum GatewayEvent {
+    MessageSent {
+        tx_id: u128,
+        sender: Pubkey,
+        recipient: ForeignAddress,
+        chain: u64,
+        confirmations: u16,
+        data_len: u64,
+    },
+    MessageVerified {
+        tx_id: u128,
+        source_chain_id: u64,
+        sender: ForeignAddress,
+        receiver: Pubkey,
+    },
+    MessageExecuted {
+        tx_id: u128,
+        receiver: Pubkey,
+    },
+    MessageFailed {
+        tx_id: u128,
+        receiver: Pubkey,
+        error: String,
+    },
+    ConfigInitialized {
+        owner: Pubkey,
+        accountant: Pubkey,
+    },
+    ConfigChanged {
+        bridge_enabled: Option<bool>,
+        accountant: Option<Pubkey>,
+        whitelist_only: Option<bool>,
+        chainsig: Option<ForeignAddress>,
+        digest_scheme: Option<DigestScheme>,
+    },
+    RoleChanged {
+        user: Pubkey,
+        role: Role,
+        is_active: bool,
+    },
+    PauseChanged {
+        authority: Pubkey,
+        target: PauseTarget,
+        paused: bool,
+    },
+    RateLimitChanged {
+        target: RateLimitTarget,
+        limit: Option<RateLimitConfig>,
+    },
+    ClientConfigured {
+        destination_contract: Pubkey,
+        authority: Pubkey,
+        registered: bool,
+    },
+    ClientAuthorityTransferStarted {
+        destination_contract: Pubkey,
+        pending_authority: Option<Pubkey>,
+    },
+    ClientAuthorityTransferred {
+        destination_contract: Pubkey,
+        authority: Pubkey,
+    },
+    ClientClosed {
+        destination_contract: Pubkey,
+        lamports: u64,
+    },
+    ExsigChanged {
+        destination_contract: Pubkey,
+        exsig: Option<ForeignAddress>,
+        activates_at: i64,
+    },
+    TreasuryDeposit {
+        destination_contract: Pubkey,
+        payer: Pubkey,
+        amount: u64,
+    },
+    TreasuryWithdraw {
+        destination_contract: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+    },
+    FeeCollected {
+        destination_contract: Pubkey,
+        amount: u64,
+    },
+    AccountMigrated {
+        account: Pubkey,
+        kind: AccountKind,
+    },
+    ChainRegistered {
+        chain_id: u64,
+        name: String,
+        address_format: AddressFormat,
+        min_confirmations: u16,
+        fees: ChainFees,
+        enabled: bool,
+        registered: bool,
+    },
+    BlsValidatorsSet {
+        validators: u16,
+        threshold: u16,
+        enabled: bool,
+    },
+    Ed25519ValidatorsSet {
+        validators: u16,
+        threshold: u16,
+    },
+    OptimisticConfigSet {
+        challenge_period: i64,
+        min_bond: u64,
+        watchers: u16,
+    },
+    ClientOptimisticSet {
+        destination_contract: Pubkey,
+        enabled: bool,
+    },
+    BondDeposit {
+        operator: Pubkey,
+        amount: u64,
+    },
+    BondWithdraw {
+        operator: Pubkey,
+        amount: u64,
+    },
+    CommitmentPosted {
+        tx_id: u128,
+        operator: Pubkey,
+        receiver: Pubkey,
+        executable_at: i64,
+    },
+    CommitmentChallenged {
+        tx_id: u128,
+        watcher: Pubkey,
+        operator: Pubkey,
+        slashed: u64,
+    },
+    MessageRootPosted {
+        source_chain_id: u64,
+        batch: u64,
+        root: [u8; 32],
+    },
+    MessageAccumulated {
+        tx_id: u128,
+        index: u64,
+        leaf: [u8; 32],
+        root: [u8; 32],
+    },
+    ClientOrderedSet {
+        destination_contract: Pubkey,
+        enabled: bool,
+    },
+    CommitmentDisputed {
+        tx_id: u128,
+        watcher: Pubkey,
+        operator: Pubkey,
+        ends_at: i64,
+    },
+    DisputeCleared {
+        tx_id: u128,
+        operator: Pubkey,
+    },
+}
+
+impl GatewayEvent {
+    pub fn emit(&self) {
+        sol_log_data(&[EVENT_TAG, &self.try_to_vec().unwrap()]);
+    }
+}
+
+/// Collects gateway events from the log messages of a transaction. Only data logged while
+/// `program_id` is the executing program is taken into account, so events emitted by other
+/// programs (including ones invoked by the gateway) are skipped.
+#[cfg(not(target_os = "solana"))]
+pub fn parse_events_from_logs(program_id: &Pubkey, logs: &[String]) -> Vec<GatewayEvent> {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    let program_id = program_id.to_string();
+
+    let mut invoke_stack: Vec<&str> = vec![];
+    let mut events: Vec<GatewayEvent> = vec![];
+
+    for log in logs {
+        let Some(log) = log.strip_prefix("Program ") else {
+            continue;
+        };
+
+        if let Some(data) = log.strip_prefix("data: ") {
+            if invoke_stack.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+
+            let fields: Vec<Vec<u8>> = data
+                .split_whitespace()
+                .filter_map(|field| STANDARD.decode(field).ok())
+                .collect();
+
+            if let [tag, event] = fields.as_slice() {
+                if tag.as_slice() != EVENT_TAG {
+                    continue;
+                }
+
+                if let Ok(event) = GatewayEvent::try_from_slice(event) {
+                    events.push(event);
+                }
+            }
+
+            continue;
+        }
+
+        let mut words = log.split_whitespace();
+
+        match (words.next(), words.next()) {
+            (Some(program), Some("invoke")) => invoke_stack.push(program),
+            (Some(_), Some("success")) | (Some(_), Some("failed:")) => {
+                invoke_stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    events
+}
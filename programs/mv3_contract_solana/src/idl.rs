@@ -0,0 +1,422 @@
+use borsh::BorshSchema;
+use message_hook::idl::{
+    Idl, IdlAccountItem, IdlErrorCode, IdlEvents, IdlSeed, IdlStateAccount, IdlTypes,
+};
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    constants::{
+        ACCUMULATOR_SEED, BOND_SEED, CHAIN_RATE_LIMIT_SEED, CHAIN_SEED, CHANNEL_SEED,
+        CLIENT_RATE_LIMIT_SEED, COMMITMENT_SEED, CONFIG_SEED, GLOBAL_TREASURY, MESSAGE_CLIENT_SEED,
+        MESSAGE_CLIENT_TREASURY_SEED, MESSAGE_SEED, OPTIMISTIC_SEED, ROLES_SEED, ROOT_SEED,
+        VALIDATORS_SEED,
+    },
+    error::MessengerError,
+    events::{GatewayEvent, EVENT_TAG},
+    instruction::V3Instruction,
+    state::{
+        account::GatewayAccount,
+        accumulator::OutboundAccumulator,
+        batch::MessageRoot,
+        chain::ChainConfig,
+        channel::ChannelState,
+        config::{ForeignAddress, MessageClient, MessengerConfig, RoleRegistry},
+        message::{Message, MessagePayload},
+        optimistic::{MessageCommitment, OperatorBond, OptimisticConfig},
+        rate_limit::RateLimitState,
+        validators::ValidatorSet,
+    },
+};
+
+/// Accounts of a `V3Instruction` variant, in the order the processor reads them
+fn instruction_accounts(instruction: &str) -> Vec<IdlAccountItem> {
+    let payer = || IdlAccountItem::new("payer", true, true);
+    let authority = || IdlAccountItem::new("authority", true, true);
+    let config = || IdlAccountItem::new("config", false, true);
+    let message_client = |writable| IdlAccountItem::new("message_client", false, writable);
+    let treasury = || IdlAccountItem::new("client_treasury", false, true);
+    let system_program = || IdlAccountItem::new("system_program", false, false);
+    let sysvar_instructions = || IdlAccountItem::new("sysvar_instructions", false, false);
+    let roles = |writable| IdlAccountItem::new("roles", false, writable);
+    let chain = |writable| IdlAccountItem::new("chain", false, writable);
+
+    match instruction {
+        "InitializeConfig" => vec![payer(), config(), system_program(), roles(true)],
+        "AddUserPermission" => vec![authority(), config(), system_program(), roles(true)],
+        "ChangeConfig" => vec![authority(), config(), system_program()],
+        "Send" => vec![
+            config(),
+            IdlAccountItem::new("sender", true, true),
+            IdlAccountItem::new("message", false, true),
+            system_program(),
+            sysvar_instructions(),
+            IdlAccountItem::new("chain_rate_limit", false, true)
+                .docs("Rate limit bucket of the destination chain"),
+            message_client(false).docs("Message client of the sender, may be uninitialized"),
+            IdlAccountItem::new("client_rate_limit", false, true)
+                .docs("Rate limit bucket of the sender"),
+            chain(false).docs("Registry entry of the destination chain"),
+            roles(false),
+            IdlAccountItem::new("global_treasury", false, true)
+                .docs("Receives the fee of the destination chain"),
+            IdlAccountItem::new("accumulator", false, true)
+                .docs("Outbound message accumulator, created by the first message"),
+        ],
+        "SetExsig" => vec![authority(), message_client(true), system_program()],
+        "ConfigureClient" => vec![
+            payer(),
+            message_client(true),
+            IdlAccountItem::new("program", false, false),
+            IdlAccountItem::new("program_data", false, false)
+                .docs("Upgradeable loader program data of `program`"),
+            system_program(),
+            chain(false).optional("Registry entries of the supported chains, in the same order"),
+        ],
+        "ReceiveMessage" => vec![
+            payer(),
+            config(),
+            message_client(false),
+            IdlAccountItem::new("message", false, true),
+            treasury(),
+            IdlAccountItem::new("global_treasury", false, true),
+            IdlAccountItem::new("system_program", false, true),
+            sysvar_instructions().docs("Holds the Ed25519 program instruction preceding this one"),
+            IdlAccountItem::new("chain_rate_limit", false, true)
+                .docs("Rate limit bucket of the source chain"),
+            IdlAccountItem::new("client_rate_limit", false, true)
+                .docs("Rate limit bucket of the receiver"),
+            chain(false).docs("Registry entry of the source chain"),
+            IdlAccountItem::new("validators", false, false)
+                .docs("Validator set, only read while BLS or Ed25519 verification is enabled"),
+            IdlAccountItem::new("commitment", false, false)
+                .docs("Operator commitment to the message, only read for optimistic clients"),
+            IdlAccountItem::new("channel", false, true)
+                .docs("Channel of the sender to the receiver, only used for ordered clients"),
+            IdlAccountItem::new("remaining_accounts", false, false)
+                .optional("Extra account meta list of the receiver followed by its extra accounts"),
+        ],
+        "DepositWithdraw" => vec![payer(), message_client(false), treasury(), system_program()],
+        "SetPause" => vec![
+            authority(),
+            config(),
+            system_program(),
+            roles(false),
+            IdlAccountItem::new("target", false, true).optional(
+                "Registry entry of the chain or message client of the client, omitted for \
+                 `Inbound` and `Outbound` targets",
+            ),
+        ],
+        "SetRateLimit" => vec![
+            authority(),
+            config(),
+            system_program(),
+            IdlAccountItem::new("target", false, true)
+                .docs("Registry entry of the chain or message client of the client"),
+            IdlAccountItem::new("rate_limit", false, true)
+                .docs("Bucket of the chain or client, carried over to the new limit once created"),
+        ],
+        "TransferClientAuthority" => vec![authority(), message_client(true), system_program()],
+        "AcceptClientAuthority" => vec![
+            IdlAccountItem::new("new_authority", true, true),
+            message_client(true),
+        ],
+        "MigrateAccount" => vec![
+            authority(),
+            config(),
+            system_program(),
+            IdlAccountItem::new("account", false, true)
+                .optional("Account to migrate, omitted when migrating the config"),
+        ],
+        "MigrateConfig" => vec![
+            authority(),
+            config(),
+            system_program(),
+            roles(true),
+            chain(true).optional("Registry entries of the chains enabled by the legacy config"),
+            message_client(true).optional("Message clients to upgrade along with the config"),
+        ],
+        "RegisterChain" => vec![
+            authority(),
+            IdlAccountItem::new("config", false, false),
+            system_program(),
+            chain(true),
+        ],
+        "SetBlsValidators" => vec![
+            authority(),
+            config(),
+            system_program(),
+            IdlAccountItem::new("validators", false, true),
+        ],
+        "SetEd25519Validators" => vec![
+            authority(),
+            IdlAccountItem::new("config", false, false),
+            system_program(),
+            IdlAccountItem::new("validators", false, true),
+        ],
+        "SetOptimisticConfig" => vec![
+            authority(),
+            IdlAccountItem::new("config", false, false),
+            system_program(),
+            IdlAccountItem::new("optimistic_config", false, true),
+        ],
+        "SetClientOptimistic" => vec![
+            IdlAccountItem::new("authority", true, false),
+            message_client(true),
+        ],
+        "DepositWithdrawBond" => vec![
+            IdlAccountItem::new("operator", true, true),
+            IdlAccountItem::new("bond", false, true),
+            system_program(),
+        ],
+        "PostCommitment" => vec![
+            IdlAccountItem::new("operator", true, true),
+            roles(false),
+            IdlAccountItem::new("optimistic_config", false, false),
+            IdlAccountItem::new("bond", false, true).docs("Bond of the operator"),
+            message_client(false).docs("Message client of the receiver"),
+            IdlAccountItem::new("commitment", false, true),
+            system_program(),
+        ],
+        "ChallengeCommitment" => vec![
+            IdlAccountItem::new("watcher", true, false),
+            IdlAccountItem::new("optimistic_config", false, false),
+            IdlAccountItem::new("commitment", false, true),
+            IdlAccountItem::new("bond", false, true).docs("Bond of the operator of the commitment"),
+            IdlAccountItem::new("global_treasury", false, true).docs("Receives the slashed bond"),
+            IdlAccountItem::new("config", false, false),
+            IdlAccountItem::new("validators", false, false)
+                .docs("Validator set, only read while BLS or Ed25519 verification is enabled"),
+            sysvar_instructions().docs("Holds the Ed25519 program instruction preceding this one"),
+        ],
+        "PostMessageRoot" => vec![
+            payer(),
+            IdlAccountItem::new("config", false, false),
+            chain(false).docs("Registry entry of the source chain"),
+            IdlAccountItem::new("validators", false, false)
+                .docs("Validator set, only read while BLS or Ed25519 verification is enabled"),
+            sysvar_instructions().docs("Holds the Ed25519 program instruction preceding this one"),
+            IdlAccountItem::new("message_root", false, true),
+            system_program(),
+        ],
+        "ReceiveBatchedMessage" => {
+            let mut accounts = instruction_accounts("ReceiveMessage");
+
+            accounts.insert(
+                accounts.len() - 1,
+                IdlAccountItem::new("message_root", false, false)
+                    .docs("Root of the batch the message is proven against"),
+            );
+
+            accounts
+        }
+        "SetClientOrdered" => vec![
+            IdlAccountItem::new("authority", true, false),
+            message_client(true),
+        ],
+        "CloseClient" => vec![
+            authority(),
+            message_client(true),
+            treasury(),
+            system_program(),
+            IdlAccountItem::new("rate_limit", false, true)
+                .docs("Rate limit bucket of the client, closed if it exists"),
+        ],
+        "DisputeCommitment" => vec![
+            IdlAccountItem::new("watcher", true, true).docs("Pays the rent of the dispute"),
+            IdlAccountItem::new("optimistic_config", false, false),
+            IdlAccountItem::new("commitment", false, true),
+            IdlAccountItem::new("bond", false, true).docs("Bond of the operator of the commitment"),
+            system_program(),
+        ],
+        "SettleDispute" => vec![
+            IdlAccountItem::new("commitment", false, true),
+            IdlAccountItem::new("bond", false, true).docs("Bond of the operator of the commitment"),
+            IdlAccountItem::new("global_treasury", false, true).docs("Receives the slashed bond"),
+            IdlAccountItem::new("config", false, false),
+            IdlAccountItem::new("validators", false, false)
+                .docs("Validator set, only read while BLS or Ed25519 verification is enabled"),
+            sysvar_instructions().docs("Holds the Ed25519 program instruction preceding this one"),
+        ],
+        _ => panic!("Missing accounts of {}", instruction),
+    }
+}
+
+/// State account holding a `T` behind its discriminator
+fn gateway_account<T: GatewayAccount + BorshSchema>(
+    types: &mut IdlTypes,
+    name: &str,
+    seeds: Vec<IdlSeed>,
+) -> IdlStateAccount {
+    IdlStateAccount {
+        name: name.to_string(),
+        ty: types.add::<T>(),
+        seeds,
+        discriminator: Some(T::DISCRIMINATOR.to_vec()),
+    }
+}
+
+/// Every `MessengerError` with its stable code
+fn errors() -> Vec<IdlErrorCode> {
+    (0..)
+        .map_while(MessengerError::from_code)
+        .map(|error| IdlErrorCode {
+            code: error.code(),
+            name: format!("{:?}", error),
+            msg: error.to_string(),
+        })
+        .collect()
+}
+
+pub fn idl() -> Idl {
+    let mut types = IdlTypes::default();
+
+    let instructions = types.add_instruction_enum::<V3Instruction>(instruction_accounts);
+
+    let pubkey = types.add::<Pubkey>();
+
+    let foreign_address = types.add::<ForeignAddress>();
+
+    let state_account = |name: &str, ty: String, seeds: Vec<IdlSeed>| IdlStateAccount {
+        name: name.to_string(),
+        ty,
+        seeds,
+        discriminator: None,
+    };
+
+    let accounts = vec![
+        gateway_account::<MessengerConfig>(
+            &mut types,
+            "MessengerConfig",
+            vec![IdlSeed::constant(CONFIG_SEED)],
+        ),
+        gateway_account::<RoleRegistry>(
+            &mut types,
+            "RoleRegistry",
+            vec![IdlSeed::constant(ROLES_SEED)],
+        ),
+        gateway_account::<ChainConfig>(
+            &mut types,
+            "ChainConfig",
+            vec![
+                IdlSeed::constant(CHAIN_SEED),
+                IdlSeed::arg("chain_id", "u64"),
+            ],
+        ),
+        gateway_account::<ValidatorSet>(
+            &mut types,
+            "ValidatorSet",
+            vec![IdlSeed::constant(VALIDATORS_SEED)],
+        ),
+        gateway_account::<OptimisticConfig>(
+            &mut types,
+            "OptimisticConfig",
+            vec![IdlSeed::constant(OPTIMISTIC_SEED)],
+        ),
+        gateway_account::<OperatorBond>(
+            &mut types,
+            "OperatorBond",
+            vec![
+                IdlSeed::constant(BOND_SEED),
+                IdlSeed::arg("operator", &pubkey),
+            ],
+        ),
+        gateway_account::<MessageCommitment>(
+            &mut types,
+            "MessageCommitment",
+            vec![
+                IdlSeed::constant(COMMITMENT_SEED),
+                IdlSeed::arg("tx_id", "u128"),
+            ],
+        ),
+        gateway_account::<MessageRoot>(
+            &mut types,
+            "MessageRoot",
+            vec![
+                IdlSeed::constant(ROOT_SEED),
+                IdlSeed::arg("source_chain_id", "u64"),
+                IdlSeed::arg("batch", "u64"),
+            ],
+        ),
+        gateway_account::<OutboundAccumulator>(
+            &mut types,
+            "OutboundAccumulator",
+            vec![IdlSeed::constant(ACCUMULATOR_SEED)],
+        ),
+        gateway_account::<ChannelState>(
+            &mut types,
+            "ChannelState",
+            vec![
+                IdlSeed::constant(CHANNEL_SEED),
+                IdlSeed::arg("source_chain_id", "u64"),
+                IdlSeed::arg("sender", &foreign_address),
+                IdlSeed::arg("receiver", &pubkey),
+            ],
+        ),
+        gateway_account::<MessageClient>(
+            &mut types,
+            "MessageClient",
+            vec![
+                IdlSeed::constant(MESSAGE_CLIENT_SEED),
+                IdlSeed::arg("destination_contract", &pubkey),
+            ],
+        ),
+        state_account(
+            "ClientTreasury",
+            "nil".to_string(),
+            vec![
+                IdlSeed::constant(MESSAGE_CLIENT_SEED),
+                IdlSeed::arg("destination_contract", &pubkey),
+                IdlSeed::constant(MESSAGE_CLIENT_TREASURY_SEED),
+            ],
+        ),
+        state_account(
+            "GlobalTreasury",
+            "nil".to_string(),
+            vec![
+                IdlSeed::constant(MESSAGE_SEED),
+                IdlSeed::constant(GLOBAL_TREASURY),
+            ],
+        ),
+        gateway_account::<Message>(&mut types, "Message", vec![IdlSeed::constant(MESSAGE_SEED)]),
+        gateway_account::<MessagePayload>(
+            &mut types,
+            "ReceivedMessage",
+            vec![
+                IdlSeed::constant(MESSAGE_SEED),
+                IdlSeed::arg("tx_id", "u128"),
+            ],
+        ),
+        gateway_account::<RateLimitState>(
+            &mut types,
+            "ChainRateLimit",
+            vec![
+                IdlSeed::constant(CHAIN_RATE_LIMIT_SEED),
+                IdlSeed::arg("chain_id", "u64"),
+            ],
+        ),
+        gateway_account::<RateLimitState>(
+            &mut types,
+            "ClientRateLimit",
+            vec![
+                IdlSeed::constant(CLIENT_RATE_LIMIT_SEED),
+                IdlSeed::arg("destination_contract", &pubkey),
+            ],
+        ),
+    ];
+
+    let events = IdlEvents {
+        tag: String::from_utf8(EVENT_TAG.to_vec()).unwrap(),
+        ty: types.add::<GatewayEvent>(),
+    };
+
+    Idl {
+        name: "mv3_contract_solana".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        program_id: Some(crate::id().to_string()),
+        instructions,
+        accounts,
+        events: Some(events),
+        errors: errors(),
+        types: types.into_json(),
+    }
+}
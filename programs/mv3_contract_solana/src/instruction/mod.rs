@@ -1,20 +1,41 @@
-use borsh::{BorshDeserialize, BorshSerialize};
+//BorshSchema derives helper structs for enum variants which are never read
+#![allow(dead_code)]
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::{
+    bpf_loader_upgradeable,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     system_program, sysvar,
 };
 
 use crate::{
-    constants::{CONFIG_SEED, MESSAGE_CLIENT_SEED, MESSAGE_CLIENT_TREASURY_SEED, MESSAGE_SEED},
-    state::config::{ForeignAddress, MessageClient, Role},
-    utils::{get_client_treasury_pda, get_global_treasury_pda, get_message_client_pda},
+    bls::BlsKey,
+    constants::{
+        CHAIN_RATE_LIMIT_SEED, CLIENT_RATE_LIMIT_SEED, CONFIG_SEED, MESSAGE_CLIENT_SEED,
+        MESSAGE_SEED, ROLES_SEED,
+    },
+    eip712,
+    state::{
+        account::AccountKind,
+        chain::{AddressFormat, ChainFees},
+        config::{DigestScheme, ForeignAddress, MessageClient, Role},
+        rate_limit::RateLimitConfig,
+    },
+    utils::{
+        create_ecdsa_sig, get_bond_pda, get_chain_pda, get_chain_rate_limit_pda, get_channel_pda,
+        get_client_rate_limit_pda, get_client_treasury_pda, get_commitment_pda,
+        get_global_treasury_pda, get_message_client_pda, get_optimistic_config_pda, get_root_pda,
+        get_validators_pda, keccak256,
+    },
 };
 
-#[derive(BorshDeserialize, BorshSerialize, PartialEq, PartialOrd, Clone)]
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, PartialOrd, Clone)]
 pub enum V3Instruction {
     InitializeConfig {
         accountant: Pubkey,
+        /// ID of the chain the gateway is deployed on, fixed for the lifetime of the config
+        chain_id: u64,
     },
 
     AddUserPermission {
@@ -23,20 +44,21 @@ pub enum V3Instruction {
         role: Role,
     },
     ChangeConfig {
-        enabled_chains: Option<Vec<u32>>,
         bridge_enabled: Option<bool>,
         accountant: Option<Pubkey>,
         whitelist_only: Option<bool>,
         chainsig: Option<ForeignAddress>,
+        digest_scheme: Option<DigestScheme>,
     },
     Send {
         recipient: ForeignAddress,
-        chain: u32,
+        chain: u64,
         confirmations: u16,
         data: Vec<u8>,
     },
     SetExsig {
-        exsig: ForeignAddress,
+        exsig: Option<ForeignAddress>,
+        activation_delay: i64,
     },
     ConfigureClient {
         authority: Pubkey,
@@ -58,9 +80,139 @@ pub enum V3Instruction {
         action: DepositWithdraw,
         amount: u64,
     },
+    SetPause {
+        target: PauseTarget,
+        paused: bool,
+    },
+    TransferClientAuthority {
+        destination_contract: Pubkey,
+        new_authority: Option<Pubkey>,
+    },
+    AcceptClientAuthority {
+        destination_contract: Pubkey,
+    },
+    /// Closes the paused client of `destination_contract` along with its treasury and rate limit
+    /// bucket, returning their lamports to the authority. Ordered channels and commitments to
+    /// its messages are kept.
+    CloseClient {
+        destination_contract: Pubkey,
+    },
+    SetRateLimit {
+        target: RateLimitTarget,
+        limit: Option<RateLimitConfig>,
+    },
+    MigrateAccount {
+        kind: AccountKind,
+    },
+    MigrateConfig,
+    /// Registers `chain_id` or updates its entry, pauses and rate limit of the chain are kept
+    RegisterChain {
+        chain_id: u64,
+        name: String,
+        address_format: AddressFormat,
+        min_confirmations: u16,
+        fees: ChainFees,
+        enabled: bool,
+    },
+    /// Writes `keys` to the BLS validator set from `offset` on, dropping the keys after them.
+    /// Every key comes with the signature of its validator over `bls::possession_message`.
+    /// With `enabled` inbound messages are verified against the set instead of the chainsig.
+    SetBlsValidators {
+        offset: u16,
+        keys: Vec<BlsKey>,
+        threshold: u16,
+        enabled: bool,
+    },
+    /// Replaces the Ed25519 validator keys. With a threshold other than zero inbound messages
+    /// need signatures of at least `threshold` of them checked by a preceding Ed25519 program
+    /// instruction.
+    SetEd25519Validators {
+        keys: Vec<Pubkey>,
+        threshold: u16,
+    },
+    /// Creates or replaces the optimistic delivery parameters and the watchers
+    SetOptimisticConfig {
+        challenge_period: i64,
+        min_bond: u64,
+        watchers: Vec<Pubkey>,
+    },
+    /// Opts the client of `destination_contract` in or out of optimistic delivery
+    SetClientOptimistic {
+        destination_contract: Pubkey,
+        enabled: bool,
+    },
+    /// Bonds lamports of the signing operator, or withdraws them once none of its commitments
+    /// can be challenged anymore
+    DepositWithdrawBond {
+        action: DepositWithdraw,
+        amount: u64,
+    },
+    /// Commits the signing operator to the message `tx_id` to `receiver` with digest hash
+    /// `hash`, executable without validator signatures after the challenge period. Replaces a
+    /// challenged commitment to the message.
+    PostCommitment {
+        tx_id: u128,
+        receiver: Pubkey,
+        hash: [u8; 32],
+    },
+    /// Proves the commitment to `tx_id` is fraudulent with the message `tx_id` the validators
+    /// signed, whose digest hash differs from the committed one. Blocks the message and slashes
+    /// the bond of the operator into the global treasury. `signature` is the BLS proof or
+    /// chainsig signature of the digest, Ed25519 signatures are carried by the preceding
+    /// instruction.
+    ChallengeCommitment {
+        tx_id: u128,
+        source_chain_id: u64,
+        sender: ForeignAddress,
+        receiver: Pubkey,
+        payload_hash: [u8; 32],
+        signature: Vec<u8>,
+    },
+    /// Stores the Merkle root over the digest hashes of the batch `batch` of messages from
+    /// `source_chain_id`. `signature` is the BLS proof or chainsig signature of its
+    /// `RootDigest`, Ed25519 signatures are carried by the preceding instruction.
+    PostMessageRoot {
+        source_chain_id: u64,
+        batch: u64,
+        root: [u8; 32],
+        signature: Vec<u8>,
+    },
+    /// Delivers a message like `ReceiveMessage`, proven by `proof` to be part of the posted
+    /// root of `batch` instead of signed by the validators
+    ReceiveBatchedMessage {
+        tx_id: u128,
+        dest_chain_id: u64,
+        receiver: Pubkey,
+        data: Vec<Vec<u8>>,
+        source_chain_id: u64,
+        sender: ForeignAddress,
+        batch: u64,
+        proof: Vec<[u8; 32]>,
+    },
+    /// Opts the client of `destination_contract` in or out of ordered channels, whose messages
+    /// carry a sequence number after the destination program and are executed in order
+    SetClientOrdered {
+        destination_contract: Pubkey,
+        enabled: bool,
+    },
+    /// Claims the commitment to `tx_id` is fraudulent on the word of the signing watcher alone.
+    /// Blocks the message and locks the bond of the operator until the dispute is settled by
+    /// `SettleDispute`, a challenge period after the claim.
+    DisputeCommitment {
+        tx_id: u128,
+    },
+    /// Settles the dispute of the commitment to `tx_id`. With `signature`, the BLS proof or
+    /// chainsig signature of the committed digest, the commitment stands and the dispute is
+    /// dropped, Ed25519 signatures are carried by the preceding instruction. Without it the
+    /// dispute period has to be over, the bond of the operator is slashed into the global
+    /// treasury and the commitment counts as challenged.
+    SettleDispute {
+        tx_id: u128,
+        signature: Option<Vec<u8>>,
+    },
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Debug)]
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone)]
 pub struct ReceiveMessage {
     pub tx_id: u128,
     pub dest_chain_id: u64,
@@ -72,29 +224,147 @@ pub struct ReceiveMessage {
 
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct SetExsig {
-    pub exsig: ForeignAddress,
+    pub exsig: Option<ForeignAddress>,
+    /// Seconds until the new exsig replaces the current one, 0 applies it immediately
+    pub activation_delay: i64,
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct SendMessage {
     pub recipient: ForeignAddress,
-    pub chain: u32,
+    pub chain: u64,
     pub confirmations: u16,
     pub data: Vec<u8>,
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct ChangeConfig {
-    pub enabled_chains: Option<Vec<u32>>,
     pub bridge_enabled: Option<bool>,
     pub accountant: Option<Pubkey>,
     pub whitelist_only: Option<bool>,
     pub chainsig: Option<ForeignAddress>,
+    pub digest_scheme: Option<DigestScheme>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, PartialOrd, Clone, Debug)]
+pub enum PauseTarget {
+    Inbound,
+    Outbound,
+    SourceChain(u64),
+    DestinationChain(u64),
+    Client(Pubkey),
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct SetPause {
+    pub target: PauseTarget,
+    pub paused: bool,
+}
+
+#[derive(
+    BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, PartialOrd, Clone, Copy, Debug,
+)]
+pub enum RateLimitTarget {
+    Chain(u64),
+    Client(Pubkey),
+}
+
+impl RateLimitTarget {
+    /// Seeds the bucket of the target is stored at
+    pub fn bucket_seeds(&self) -> (&'static [u8], Vec<u8>) {
+        match self {
+            RateLimitTarget::Chain(chain_id) => {
+                (CHAIN_RATE_LIMIT_SEED, chain_id.to_le_bytes().to_vec())
+            }
+            RateLimitTarget::Client(destination_contract) => (
+                CLIENT_RATE_LIMIT_SEED,
+                destination_contract.to_bytes().to_vec(),
+            ),
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct SetRateLimit {
+    pub target: RateLimitTarget,
+    pub limit: Option<RateLimitConfig>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TransferClientAuthority {
+    pub destination_contract: Pubkey,
+    pub new_authority: Option<Pubkey>,
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct InitializeConfig {
     pub accountant: Pubkey,
+    pub chain_id: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct RegisterChain {
+    pub chain_id: u64,
+    pub name: String,
+    pub address_format: AddressFormat,
+    pub min_confirmations: u16,
+    pub fees: ChainFees,
+    pub enabled: bool,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct SetBlsValidators {
+    pub offset: u16,
+    pub keys: Vec<BlsKey>,
+    pub threshold: u16,
+    pub enabled: bool,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct SetEd25519Validators {
+    pub keys: Vec<Pubkey>,
+    pub threshold: u16,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct SetOptimisticConfig {
+    pub challenge_period: i64,
+    pub min_bond: u64,
+    pub watchers: Vec<Pubkey>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PostCommitment {
+    pub tx_id: u128,
+    pub receiver: Pubkey,
+    pub hash: [u8; 32],
+}
+
+/// Message the validators signed under the `tx_id` of a commitment, contradicting it
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone)]
+pub struct FraudEvidence {
+    pub source_chain_id: u64,
+    pub sender: ForeignAddress,
+    pub receiver: Pubkey,
+    /// keccak256 of the payload
+    pub payload_hash: [u8; 32],
+    /// BLS proof or chainsig signature of the digest of the message
+    pub signature: Vec<u8>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PostMessageRoot {
+    pub source_chain_id: u64,
+    pub batch: u64,
+    pub root: [u8; 32],
+    pub signature: Vec<u8>,
+}
+
+/// Proof a message is a leaf of the root posted for `batch` of its source chain
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, PartialEq)]
+pub struct BatchProof {
+    pub batch: u64,
+    pub proof: Vec<[u8; 32]>,
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -104,7 +374,12 @@ pub struct AddUserPermission {
     pub role: Role,
 }
 
-pub fn initialize_config(accountant: Pubkey, payer: Pubkey, program_id: &Pubkey) -> Instruction {
+pub fn initialize_config(
+    accountant: Pubkey,
+    chain_id: u64,
+    payer: Pubkey,
+    program_id: &Pubkey,
+) -> Instruction {
     let mut accounts: Vec<AccountMeta> = vec![];
 
     let mut data: Vec<u8> = vec![];
@@ -129,10 +404,21 @@ pub fn initialize_config(accountant: Pubkey, payer: Pubkey, program_id: &Pubkey)
         is_writable: false,
     });
 
+    let (roles, _) = Pubkey::find_program_address(&[ROLES_SEED], program_id);
+
+    accounts.push(AccountMeta {
+        pubkey: roles,
+        is_signer: false,
+        is_writable: true,
+    });
+
     data.extend_from_slice(
-        &V3Instruction::InitializeConfig { accountant }
-            .try_to_vec()
-            .unwrap(),
+        &V3Instruction::InitializeConfig {
+            accountant,
+            chain_id,
+        }
+        .try_to_vec()
+        .unwrap(),
     );
 
     Instruction {
@@ -169,11 +455,11 @@ pub fn change_config(program_id: Pubkey, payer: Pubkey, data: ChangeConfig) -> I
 
     ix_data.extend_from_slice(
         &V3Instruction::ChangeConfig {
-            enabled_chains: data.enabled_chains,
             bridge_enabled: data.bridge_enabled,
             accountant: data.accountant,
             whitelist_only: data.whitelist_only,
             chainsig: data.chainsig,
+            digest_scheme: data.digest_scheme,
         }
         .try_to_vec()
         .unwrap(),
@@ -213,10 +499,12 @@ pub fn add_user_permission(
         is_writable: false,
     });
 
+    let (roles, _) = Pubkey::find_program_address(&[ROLES_SEED], &program_id);
+
     accounts.push(AccountMeta {
-        pubkey: solana_program::sysvar::rent::id(),
+        pubkey: roles,
         is_signer: false,
-        is_writable: false,
+        is_writable: true,
     });
 
     let mut ix_data: Vec<u8> = vec![];
@@ -249,7 +537,7 @@ pub fn receive_message(program_id: &Pubkey, data: ReceiveMessage, payer: Pubkey)
 
     let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
 
-    let (client, _) = get_message_client_pda(data.receiver);
+    let (client, _) = get_message_client_pda(data.receiver, program_id);
 
     accounts.push(AccountMeta {
         pubkey: config,
@@ -263,8 +551,8 @@ pub fn receive_message(program_id: &Pubkey, data: ReceiveMessage, payer: Pubkey)
         is_writable: false,
     });
 
-    let client_treasury = get_client_treasury_pda(data.receiver);
-    let global_treasury = get_global_treasury_pda();
+    let client_treasury = get_client_treasury_pda(data.receiver, program_id);
+    let global_treasury = get_global_treasury_pda(program_id);
 
     let (message, _) =
         Pubkey::find_program_address(&[MESSAGE_SEED, &data.tx_id.to_le_bytes()], program_id);
@@ -299,6 +587,59 @@ pub fn receive_message(program_id: &Pubkey, data: ReceiveMessage, payer: Pubkey)
         is_writable: false,
     });
 
+    let (chain_rate_limit, _) = get_chain_rate_limit_pda(data.source_chain_id, program_id);
+
+    accounts.push(AccountMeta {
+        pubkey: chain_rate_limit,
+        is_signer: false,
+        is_writable: true,
+    });
+
+    let (client_rate_limit, _) = get_client_rate_limit_pda(data.receiver, program_id);
+
+    accounts.push(AccountMeta {
+        pubkey: client_rate_limit,
+        is_signer: false,
+        is_writable: true,
+    });
+
+    let (source_chain, _) = get_chain_pda(data.source_chain_id, program_id);
+
+    accounts.push(AccountMeta {
+        pubkey: source_chain,
+        is_signer: false,
+        is_writable: false,
+    });
+
+    let (validators, _) = get_validators_pda(program_id);
+
+    accounts.push(AccountMeta {
+        pubkey: validators,
+        is_signer: false,
+        is_writable: false,
+    });
+
+    let (commitment, _) = get_commitment_pda(data.tx_id, program_id);
+
+    accounts.push(AccountMeta {
+        pubkey: commitment,
+        is_signer: false,
+        is_writable: false,
+    });
+
+    let (channel, _) = get_channel_pda(
+        data.source_chain_id,
+        &data.sender,
+        &data.receiver,
+        program_id,
+    );
+
+    accounts.push(AccountMeta {
+        pubkey: channel,
+        is_signer: false,
+        is_writable: true,
+    });
+
     let mut ix_data: Vec<u8> = vec![];
 
     ix_data.extend_from_slice(
@@ -321,8 +662,48 @@ pub fn receive_message(program_id: &Pubkey, data: ReceiveMessage, payer: Pubkey)
     }
 }
 
-pub fn configure_client(payer: Pubkey, data: MessageClient) -> Instruction {
-    let (addr, _) = get_message_client_pda(data.destination_contract);
+/// `ReceiveMessage` of a message of a batch, the account of the root of the batch follows the
+/// accounts of `receive_message`
+pub fn receive_batched_message(
+    program_id: &Pubkey,
+    data: ReceiveMessage,
+    proof: BatchProof,
+    payer: Pubkey,
+) -> Instruction {
+    let (root, _) = get_root_pda(data.source_chain_id, proof.batch, program_id);
+
+    let ix_data = V3Instruction::ReceiveBatchedMessage {
+        tx_id: data.tx_id,
+        dest_chain_id: data.dest_chain_id,
+        receiver: data.receiver,
+        data: data.data.clone(),
+        source_chain_id: data.source_chain_id,
+        sender: data.sender,
+        batch: proof.batch,
+        proof: proof.proof,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let mut ix = receive_message(program_id, data, payer);
+
+    ix.accounts.push(AccountMeta {
+        pubkey: root,
+        is_signer: false,
+        is_writable: false,
+    });
+    ix.data = ix_data;
+
+    ix
+}
+
+pub fn configure_client(program_id: Pubkey, payer: Pubkey, data: MessageClient) -> Instruction {
+    let (addr, _) = get_message_client_pda(data.destination_contract, &program_id);
+
+    let (program_data, _) = Pubkey::find_program_address(
+        &[data.destination_contract.as_ref()],
+        &bpf_loader_upgradeable::id(),
+    );
 
     let accounts: Vec<AccountMeta> = vec![
         AccountMeta {
@@ -340,6 +721,11 @@ pub fn configure_client(payer: Pubkey, data: MessageClient) -> Instruction {
             is_writable: false,
             pubkey: data.destination_contract,
         },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: program_data,
+        },
         AccountMeta {
             is_signer: false,
             is_writable: false,
@@ -347,6 +733,15 @@ pub fn configure_client(payer: Pubkey, data: MessageClient) -> Instruction {
         },
     ];
 
+    let accounts = accounts
+        .into_iter()
+        .chain(data.supported_chains.iter().map(|chain_id| AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: get_chain_pda(*chain_id, &program_id).0,
+        }))
+        .collect();
+
     let data = V3Instruction::ConfigureClient {
         authority: data.authority,
         destination_contract: data.destination_contract,
@@ -361,44 +756,134 @@ pub fn configure_client(payer: Pubkey, data: MessageClient) -> Instruction {
     let ix = Instruction {
         accounts,
         data,
-        program_id: crate::id(),
+        program_id,
     };
 
     ix
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Debug)]
+/// What the chainsig and exsig of an inbound message sign, binding the message to the program
+/// and the gateway deployment it is delivered to
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, PartialEq)]
 pub struct MessageDigest {
-    pub data: Vec<u8>,
     pub tx_id: u128,
-    pub sender: [u8; 32],
-    pub recipient: [u8; 32],
+    pub source_chain_id: u64,
     pub dest_chain_id: u64,
+    pub sender: ForeignAddress,
+    /// Program the message is delivered to
+    pub receiver: Pubkey,
+    /// Gateway program delivering the message
+    pub gateway: Pubkey,
+    /// keccak256 of the payload
+    pub payload_hash: [u8; 32],
+}
+
+impl MessageDigest {
+    /// Digest of `payload` sent by `sender` to `receiver` through the gateway deployed at
+    /// `gateway`, computed the same way by the gateway, signers and relayers
+    pub fn new(
+        tx_id: u128,
+        source_chain_id: u64,
+        dest_chain_id: u64,
+        sender: ForeignAddress,
+        receiver: Pubkey,
+        gateway: Pubkey,
+        payload: &[u8],
+    ) -> Self {
+        MessageDigest {
+            tx_id,
+            source_chain_id,
+            dest_chain_id,
+            sender,
+            receiver,
+            gateway,
+            payload_hash: keccak256(payload),
+        }
+    }
+
+    /// Hash the signers sign under `scheme`
+    pub fn hash(&self, scheme: DigestScheme) -> [u8; 32] {
+        match scheme {
+            DigestScheme::PersonalSign => create_ecdsa_sig(&self.try_to_vec().unwrap()),
+            DigestScheme::Eip712 => eip712::typed_data_hash(
+                &eip712::domain_separator(self.dest_chain_id, &self.gateway),
+                &self.struct_hash(),
+            ),
+        }
+    }
+
+    /// EIP-712 `hashStruct` of the digest as `eip712::MESSAGE_TYPE`
+    pub fn struct_hash(&self) -> [u8; 32] {
+        eip712::hash_struct(
+            eip712::MESSAGE_TYPE,
+            &[
+                eip712::encode_uint(self.tx_id),
+                eip712::encode_uint(u128::from(self.source_chain_id)),
+                eip712::encode_uint(u128::from(self.dest_chain_id)),
+                self.sender,
+                self.receiver.to_bytes(),
+                self.gateway.to_bytes(),
+                self.payload_hash,
+            ],
+        )
+    }
+}
+
+/// What the validators sign to vouch for every message of a batch, the leaves of `root` being
+/// the digest hashes of the messages
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, PartialEq)]
+pub struct RootDigest {
     pub source_chain_id: u64,
+    pub dest_chain_id: u64,
+    pub batch: u64,
+    pub root: [u8; 32],
+    /// Gateway program delivering the messages
+    pub gateway: Pubkey,
+}
+
+impl RootDigest {
+    /// Hash the signers sign under `scheme`
+    pub fn hash(&self, scheme: DigestScheme) -> [u8; 32] {
+        match scheme {
+            DigestScheme::PersonalSign => create_ecdsa_sig(&self.try_to_vec().unwrap()),
+            DigestScheme::Eip712 => eip712::typed_data_hash(
+                &eip712::domain_separator(self.dest_chain_id, &self.gateway),
+                &self.struct_hash(),
+            ),
+        }
+    }
+
+    /// EIP-712 `hashStruct` of the digest as `eip712::ROOT_TYPE`
+    pub fn struct_hash(&self) -> [u8; 32] {
+        eip712::hash_struct(
+            eip712::ROOT_TYPE,
+            &[
+                eip712::encode_uint(u128::from(self.source_chain_id)),
+                eip712::encode_uint(u128::from(self.dest_chain_id)),
+                eip712::encode_uint(u128::from(self.batch)),
+                self.root,
+                self.gateway.to_bytes(),
+            ],
+        )
+    }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, PartialEq, PartialOrd, Clone)]
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, PartialOrd, Clone)]
 pub enum DepositWithdraw {
     Deposit,
     Withdraw,
 }
 
 pub fn deposit_withdraw_sol(
+    program_id: Pubkey,
     payer: Pubkey,
     destination_contract: Pubkey,
     amount: u64,
     action: DepositWithdraw,
 ) -> Instruction {
-    let (address, _) = get_message_client_pda(destination_contract);
-
-    let (treasury, _) = Pubkey::find_program_address(
-        &[
-            MESSAGE_CLIENT_SEED,
-            destination_contract.as_ref(),
-            MESSAGE_CLIENT_TREASURY_SEED,
-        ],
-        &crate::id(),
-    );
+    let (address, _) = get_message_client_pda(destination_contract, &program_id);
+
+    let treasury = get_client_treasury_pda(destination_contract, &program_id);
 
     let accounts: Vec<AccountMeta> = vec![
         AccountMeta {
@@ -428,14 +913,19 @@ pub fn deposit_withdraw_sol(
         .unwrap();
 
     Instruction {
-        program_id: crate::id(),
+        program_id,
         accounts,
         data,
     }
 }
 
-pub fn set_exsig(authority: Pubkey, contract_address: Pubkey, data: SetExsig) -> Instruction {
-    let (pda, _) = get_message_client_pda(contract_address);
+pub fn set_exsig(
+    program_id: Pubkey,
+    authority: Pubkey,
+    contract_address: Pubkey,
+    data: SetExsig,
+) -> Instruction {
+    let (pda, _) = get_message_client_pda(contract_address, &program_id);
 
     let accounts: Vec<AccountMeta> = vec![
         AccountMeta {
@@ -455,13 +945,957 @@ pub fn set_exsig(authority: Pubkey, contract_address: Pubkey, data: SetExsig) ->
         },
     ];
 
-    let data = V3Instruction::SetExsig { exsig: data.exsig }
-        .try_to_vec()
-        .unwrap();
+    let data = V3Instruction::SetExsig {
+        exsig: data.exsig,
+        activation_delay: data.activation_delay,
+    }
+    .try_to_vec()
+    .unwrap();
 
     Instruction {
-        program_id: crate::id(),
+        program_id,
         accounts: accounts,
         data,
     }
 }
+
+/// Pausing a chain or client takes its registry entry or client as last account. The authority of
+/// a client can pause it as well, which `CloseClient` requires.
+pub fn set_pause(program_id: Pubkey, authority: Pubkey, data: SetPause) -> Instruction {
+    let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
+    let (roles, _) = Pubkey::find_program_address(&[ROLES_SEED], &program_id);
+
+    let mut accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: true,
+            pubkey: authority,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: config,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: system_program::id(),
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: roles,
+        },
+    ];
+
+    let target = match data.target {
+        PauseTarget::Inbound | PauseTarget::Outbound => None,
+        PauseTarget::SourceChain(chain_id) | PauseTarget::DestinationChain(chain_id) => {
+            Some(get_chain_pda(chain_id, &program_id).0)
+        }
+        PauseTarget::Client(destination_contract) => {
+            Some(get_message_client_pda(destination_contract, &program_id).0)
+        }
+    };
+
+    if let Some(target) = target {
+        accounts.push(AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: target,
+        });
+    }
+
+    let data = V3Instruction::SetPause {
+        target: data.target,
+        paused: data.paused,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn transfer_client_authority(
+    program_id: Pubkey,
+    authority: Pubkey,
+    data: TransferClientAuthority,
+) -> Instruction {
+    let (pda, _) = get_message_client_pda(data.destination_contract, &program_id);
+
+    let accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: true,
+            pubkey: authority,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: pda,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: system_program::id(),
+        },
+    ];
+
+    let data = V3Instruction::TransferClientAuthority {
+        destination_contract: data.destination_contract,
+        new_authority: data.new_authority,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn accept_client_authority(
+    program_id: Pubkey,
+    new_authority: Pubkey,
+    destination_contract: Pubkey,
+) -> Instruction {
+    let (pda, _) = get_message_client_pda(destination_contract, &program_id);
+
+    let accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: true,
+            pubkey: new_authority,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: pda,
+        },
+    ];
+
+    let data = V3Instruction::AcceptClientAuthority {
+        destination_contract,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn close_client(
+    program_id: Pubkey,
+    authority: Pubkey,
+    destination_contract: Pubkey,
+) -> Instruction {
+    let (pda, _) = get_message_client_pda(destination_contract, &program_id);
+
+    let treasury = get_client_treasury_pda(destination_contract, &program_id);
+    let (rate_limit, _) = get_client_rate_limit_pda(destination_contract, &program_id);
+
+    let accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: true,
+            pubkey: authority,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: pda,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: treasury,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: system_program::id(),
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: rate_limit,
+        },
+    ];
+
+    let data = V3Instruction::CloseClient {
+        destination_contract,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn set_rate_limit(program_id: Pubkey, authority: Pubkey, data: SetRateLimit) -> Instruction {
+    let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
+
+    let (target, bucket) = match data.target {
+        RateLimitTarget::Chain(chain_id) => (
+            get_chain_pda(chain_id, &program_id).0,
+            get_chain_rate_limit_pda(chain_id, &program_id).0,
+        ),
+        RateLimitTarget::Client(destination_contract) => (
+            get_message_client_pda(destination_contract, &program_id).0,
+            get_client_rate_limit_pda(destination_contract, &program_id).0,
+        ),
+    };
+
+    let accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: true,
+            pubkey: authority,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: config,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: system_program::id(),
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: target,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: bucket,
+        },
+    ];
+
+    let data = V3Instruction::SetRateLimit {
+        target: data.target,
+        limit: data.limit,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+/// Prefixes the legacy `account` of `kind` with its discriminator, client layouts are upgraded to
+/// their current version. The config is upgraded by `migrate_config`, before any other account.
+pub fn migrate_account(
+    program_id: Pubkey,
+    authority: Pubkey,
+    account: Pubkey,
+    kind: AccountKind,
+) -> Instruction {
+    let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
+
+    let mut accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: true,
+            pubkey: authority,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: config,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: system_program::id(),
+        },
+    ];
+
+    if account != config {
+        accounts.push(AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: account,
+        });
+    }
+
+    let data = V3Instruction::MigrateAccount { kind }.try_to_vec().unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+/// Upgrades the config and the clients of `destination_contracts` stored in older layouts to the
+/// current version, accounts already up to date are left untouched. Upgrading a config stored
+/// before the header split creates the role registry split out of it. Chains it enabled,
+/// `MessengerConfigV0::chain_ids`, are imported into the registry.
+pub fn migrate_config(
+    program_id: Pubkey,
+    authority: Pubkey,
+    chain_ids: &[u64],
+    destination_contracts: &[Pubkey],
+) -> Instruction {
+    let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
+    let (roles, _) = Pubkey::find_program_address(&[ROLES_SEED], &program_id);
+
+    let mut accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: true,
+            pubkey: authority,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: config,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: system_program::id(),
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: roles,
+        },
+    ];
+
+    for chain_id in chain_ids {
+        accounts.push(AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: get_chain_pda(*chain_id, &program_id).0,
+        });
+    }
+
+    for destination_contract in destination_contracts {
+        let (message_client, _) = Pubkey::find_program_address(
+            &[MESSAGE_CLIENT_SEED, destination_contract.as_ref()],
+            &program_id,
+        );
+
+        accounts.push(AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: message_client,
+        });
+    }
+
+    let data = V3Instruction::MigrateConfig.try_to_vec().unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn register_chain(program_id: Pubkey, authority: Pubkey, data: RegisterChain) -> Instruction {
+    let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
+    let (chain, _) = get_chain_pda(data.chain_id, &program_id);
+
+    let accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: true,
+            pubkey: authority,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: config,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: system_program::id(),
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: chain,
+        },
+    ];
+
+    let data = V3Instruction::RegisterChain {
+        chain_id: data.chain_id,
+        name: data.name,
+        address_format: data.address_format,
+        min_confirmations: data.min_confirmations,
+        fees: data.fees,
+        enabled: data.enabled,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn set_bls_validators(
+    program_id: Pubkey,
+    authority: Pubkey,
+    data: SetBlsValidators,
+) -> Instruction {
+    let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
+    let (validators, _) = get_validators_pda(&program_id);
+
+    let accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: true,
+            pubkey: authority,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: config,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: system_program::id(),
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: validators,
+        },
+    ];
+
+    let data = V3Instruction::SetBlsValidators {
+        offset: data.offset,
+        keys: data.keys,
+        threshold: data.threshold,
+        enabled: data.enabled,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn set_ed25519_validators(
+    program_id: Pubkey,
+    authority: Pubkey,
+    data: SetEd25519Validators,
+) -> Instruction {
+    let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
+    let (validators, _) = get_validators_pda(&program_id);
+
+    let accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: true,
+            pubkey: authority,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: config,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: system_program::id(),
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: validators,
+        },
+    ];
+
+    let data = V3Instruction::SetEd25519Validators {
+        keys: data.keys,
+        threshold: data.threshold,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn set_optimistic_config(
+    program_id: Pubkey,
+    authority: Pubkey,
+    data: SetOptimisticConfig,
+) -> Instruction {
+    let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
+    let (optimistic_config, _) = get_optimistic_config_pda(&program_id);
+
+    let accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: true,
+            pubkey: authority,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: config,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: system_program::id(),
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: optimistic_config,
+        },
+    ];
+
+    let data = V3Instruction::SetOptimisticConfig {
+        challenge_period: data.challenge_period,
+        min_bond: data.min_bond,
+        watchers: data.watchers,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn set_client_optimistic(
+    program_id: Pubkey,
+    authority: Pubkey,
+    destination_contract: Pubkey,
+    enabled: bool,
+) -> Instruction {
+    let (pda, _) = get_message_client_pda(destination_contract, &program_id);
+
+    let accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: false,
+            pubkey: authority,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: pda,
+        },
+    ];
+
+    let data = V3Instruction::SetClientOptimistic {
+        destination_contract,
+        enabled,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn deposit_withdraw_bond(
+    program_id: Pubkey,
+    operator: Pubkey,
+    amount: u64,
+    action: DepositWithdraw,
+) -> Instruction {
+    let (bond, _) = get_bond_pda(&operator, &program_id);
+
+    let accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: true,
+            pubkey: operator,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: bond,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: system_program::id(),
+        },
+    ];
+
+    let data = V3Instruction::DepositWithdrawBond { action, amount }
+        .try_to_vec()
+        .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn post_commitment(program_id: Pubkey, operator: Pubkey, data: PostCommitment) -> Instruction {
+    let (roles, _) = Pubkey::find_program_address(&[ROLES_SEED], &program_id);
+    let (optimistic_config, _) = get_optimistic_config_pda(&program_id);
+    let (bond, _) = get_bond_pda(&operator, &program_id);
+    let (client, _) = get_message_client_pda(data.receiver, &program_id);
+    let (commitment, _) = get_commitment_pda(data.tx_id, &program_id);
+
+    let accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: true,
+            pubkey: operator,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: roles,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: optimistic_config,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: bond,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: client,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: commitment,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: system_program::id(),
+        },
+    ];
+
+    let data = V3Instruction::PostCommitment {
+        tx_id: data.tx_id,
+        receiver: data.receiver,
+        hash: data.hash,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn challenge_commitment(
+    program_id: Pubkey,
+    watcher: Pubkey,
+    operator: Pubkey,
+    tx_id: u128,
+    evidence: FraudEvidence,
+) -> Instruction {
+    let (optimistic_config, _) = get_optimistic_config_pda(&program_id);
+    let (commitment, _) = get_commitment_pda(tx_id, &program_id);
+    let (bond, _) = get_bond_pda(&operator, &program_id);
+    let global_treasury = get_global_treasury_pda(&program_id);
+    let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
+    let (validators, _) = get_validators_pda(&program_id);
+
+    let accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: false,
+            pubkey: watcher,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: optimistic_config,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: commitment,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: bond,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: global_treasury,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: config,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: validators,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: sysvar::instructions::id(),
+        },
+    ];
+
+    let data = V3Instruction::ChallengeCommitment {
+        tx_id,
+        source_chain_id: evidence.source_chain_id,
+        sender: evidence.sender,
+        receiver: evidence.receiver,
+        payload_hash: evidence.payload_hash,
+        signature: evidence.signature,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn post_message_root(program_id: Pubkey, payer: Pubkey, data: PostMessageRoot) -> Instruction {
+    let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
+    let (source_chain, _) = get_chain_pda(data.source_chain_id, &program_id);
+    let (validators, _) = get_validators_pda(&program_id);
+    let (root, _) = get_root_pda(data.source_chain_id, data.batch, &program_id);
+
+    let accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: true,
+            pubkey: payer,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: config,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: source_chain,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: validators,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: sysvar::instructions::id(),
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: root,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: system_program::id(),
+        },
+    ];
+
+    let data = V3Instruction::PostMessageRoot {
+        source_chain_id: data.source_chain_id,
+        batch: data.batch,
+        root: data.root,
+        signature: data.signature,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn set_client_ordered(
+    program_id: Pubkey,
+    authority: Pubkey,
+    destination_contract: Pubkey,
+    enabled: bool,
+) -> Instruction {
+    let (pda, _) = get_message_client_pda(destination_contract, &program_id);
+
+    let accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: false,
+            pubkey: authority,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: pda,
+        },
+    ];
+
+    let data = V3Instruction::SetClientOrdered {
+        destination_contract,
+        enabled,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn dispute_commitment(
+    program_id: Pubkey,
+    watcher: Pubkey,
+    operator: Pubkey,
+    tx_id: u128,
+) -> Instruction {
+    let (optimistic_config, _) = get_optimistic_config_pda(&program_id);
+    let (commitment, _) = get_commitment_pda(tx_id, &program_id);
+    let (bond, _) = get_bond_pda(&operator, &program_id);
+
+    let accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: true,
+            is_writable: true,
+            pubkey: watcher,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: optimistic_config,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: commitment,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: bond,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: system_program::id(),
+        },
+    ];
+
+    let data = V3Instruction::DisputeCommitment { tx_id }
+        .try_to_vec()
+        .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn settle_dispute(
+    program_id: Pubkey,
+    operator: Pubkey,
+    tx_id: u128,
+    signature: Option<Vec<u8>>,
+) -> Instruction {
+    let (commitment, _) = get_commitment_pda(tx_id, &program_id);
+    let (bond, _) = get_bond_pda(&operator, &program_id);
+    let global_treasury = get_global_treasury_pda(&program_id);
+    let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
+    let (validators, _) = get_validators_pda(&program_id);
+
+    let accounts: Vec<AccountMeta> = vec![
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: commitment,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: bond,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: true,
+            pubkey: global_treasury,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: config,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: validators,
+        },
+        AccountMeta {
+            is_signer: false,
+            is_writable: false,
+            pubkey: sysvar::instructions::id(),
+        },
+    ];
+
+    let data = V3Instruction::SettleDispute { tx_id, signature }
+        .try_to_vec()
+        .unwrap();
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
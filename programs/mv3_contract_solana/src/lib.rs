@@ -1,7 +1,14 @@
+pub mod bls;
 pub mod constants;
+pub mod ed25519;
+pub mod eip712;
 pub mod entrypoint;
 pub mod error;
+pub mod events;
+#[cfg(not(target_os = "solana"))]
+pub mod idl;
 pub mod instruction;
+pub mod merkle;
 pub mod processor;
 pub mod state;
 pub mod utils;
@@ -0,0 +1,78 @@
+//! Merkle trees over the digest hashes of inbound messages, committed to by a validator-signed
+//! `MessageRoot`. Leaves are the digest hashes themselves and pairs are hashed in sorted order,
+//! so proofs carry no positions and verify with OpenZeppelin's `MerkleProof.verify`.
+
+use crate::utils::keccak256;
+
+/// Parent of the nodes `a` and `b`, independent of their order
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+
+    let mut encoded = [0; 64];
+    encoded[..32].copy_from_slice(first);
+    encoded[32..].copy_from_slice(second);
+
+    keccak256(&encoded)
+}
+
+/// Root reached from `leaf` through the siblings in `proof`
+pub fn process_proof(leaf: &[u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    proof
+        .iter()
+        .fold(*leaf, |node, sibling| hash_pair(&node, sibling))
+}
+
+/// Checks `proof` proves `leaf` is a leaf of the tree with root `root`
+pub fn verify(leaf: &[u8; 32], proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    process_proof(leaf, proof) == *root
+}
+
+/// Levels of the tree over `leaves` from the leaves up to the root. A node without a sibling is
+/// carried to the next level unchanged.
+fn levels(leaves: &[[u8; 32]]) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![leaves.to_vec()];
+
+    while levels.last().unwrap().len() > 1 {
+        let level = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => hash_pair(a, b),
+                [node] => *node,
+                _ => unreachable!(),
+            })
+            .collect();
+
+        levels.push(level);
+    }
+
+    levels
+}
+
+/// Root of the tree over `leaves`, `None` without leaves
+pub fn root(leaves: &[[u8; 32]]) -> Option<[u8; 32]> {
+    levels(leaves).last().unwrap().first().copied()
+}
+
+/// Proof of the leaf at `index` of the tree over `leaves`
+pub fn proof(leaves: &[[u8; 32]], index: usize) -> Option<Vec<[u8; 32]>> {
+    if index >= leaves.len() {
+        return None;
+    }
+
+    let levels = levels(leaves);
+
+    let mut position = index;
+    let mut proof = vec![];
+
+    for level in &levels[..levels.len() - 1] {
+        if let Some(sibling) = level.get(position ^ 1) {
+            proof.push(*sibling);
+        }
+
+        position /= 2;
+    }
+
+    Some(proof)
+}
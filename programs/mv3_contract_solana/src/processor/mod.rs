@@ -3,19 +3,41 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
+mod process_accept_client_authority;
 mod process_add_user_permission;
+mod process_challenge_commitment;
 mod process_change_config;
+mod process_close_client;
 mod process_configure_client;
 mod process_deposit_withdraw;
+mod process_deposit_withdraw_bond;
+mod process_dispute_commitment;
 mod process_initialize_config;
+mod process_migrate_account;
+mod process_migrate_config;
+mod process_post_commitment;
+mod process_post_message_root;
 mod process_receive_message;
+mod process_register_chain;
 mod process_send_message;
+mod process_set_bls_validators;
+mod process_set_client_optimistic;
+mod process_set_client_ordered;
+mod process_set_ed25519_validators;
 mod process_set_exsig;
+mod process_set_optimistic_config;
+mod process_set_pause;
+mod process_set_rate_limit;
+mod process_settle_dispute;
+mod process_transfer_client_authority;
 
 use crate::{
+    error::MessengerError,
     instruction::{
-        AddUserPermission, ChangeConfig, InitializeConfig, ReceiveMessage, SendMessage, SetExsig,
-        V3Instruction,
+        AddUserPermission, BatchProof, ChangeConfig, FraudEvidence, InitializeConfig,
+        PostCommitment, PostMessageRoot, ReceiveMessage, RegisterChain, SendMessage,
+        SetBlsValidators, SetEd25519Validators, SetExsig, SetOptimisticConfig, SetPause,
+        SetRateLimit, TransferClientAuthority, V3Instruction,
     },
     state::config::MessageClient,
 };
@@ -26,15 +48,22 @@ pub fn process_instruction(
     data: &[u8],
 ) -> ProgramResult {
     msg!("Deserializing ix! {}", data.len());
-    let instruction = try_from_slice_unchecked::<V3Instruction>(data)?;
+    let instruction = try_from_slice_unchecked::<V3Instruction>(data)
+        .map_err(|_| MessengerError::InvalidInstruction)?;
 
     match instruction {
-        V3Instruction::InitializeConfig { accountant } => {
+        V3Instruction::InitializeConfig {
+            accountant,
+            chain_id,
+        } => {
             msg!("MessageV3: Initialize Config!");
             process_initialize_config::process_initialize_config(
                 accounts,
                 program_id,
-                InitializeConfig { accountant },
+                InitializeConfig {
+                    accountant,
+                    chain_id,
+                },
             )?;
         }
         V3Instruction::AddUserPermission {
@@ -54,11 +83,11 @@ pub fn process_instruction(
             )?
         }
         V3Instruction::ChangeConfig {
-            enabled_chains,
             bridge_enabled,
             accountant,
             whitelist_only,
             chainsig,
+            digest_scheme,
         } => {
             msg!("MessageV3: Modify Config!");
 
@@ -69,8 +98,8 @@ pub fn process_instruction(
                     accountant,
                     whitelist_only,
                     chainsig,
-                    enabled_chains,
                     bridge_enabled,
+                    digest_scheme,
                 },
             )?
         }
@@ -89,9 +118,17 @@ pub fn process_instruction(
                 confirmations,
             },
         )?,
-        V3Instruction::SetExsig { exsig } => {
-            process_set_exsig::process_set_exsig(program_id, accounts, SetExsig { exsig })?
-        }
+        V3Instruction::SetExsig {
+            exsig,
+            activation_delay,
+        } => process_set_exsig::process_set_exsig(
+            program_id,
+            accounts,
+            SetExsig {
+                exsig,
+                activation_delay,
+            },
+        )?,
         V3Instruction::ConfigureClient {
             authority,
             destination_contract,
@@ -104,12 +141,19 @@ pub fn process_instruction(
                 program_id,
                 accounts,
                 MessageClient {
+                    version: MessageClient::VERSION,
                     allowed_contracts,
                     authority,
                     destination_contract,
                     exsig,
                     notify_on_failure,
                     supported_chains,
+                    paused: false,
+                    pending_authority: None,
+                    pending_exsig: None,
+                    rate_limit: None,
+                    optimistic: false,
+                    ordered: false,
                 },
             )?;
         }
@@ -132,6 +176,7 @@ pub fn process_instruction(
                     source_chain_id,
                     sender,
                 },
+                None,
                 program_id,
                 accounts,
             )?
@@ -141,6 +186,259 @@ pub fn process_instruction(
                 program_id, accounts, amount, action,
             )?;
         }
+        V3Instruction::SetPause { target, paused } => {
+            msg!("MessageV3: Set pause!");
+
+            process_set_pause::process_set_pause(accounts, program_id, SetPause { target, paused })?
+        }
+        V3Instruction::TransferClientAuthority {
+            destination_contract,
+            new_authority,
+        } => {
+            msg!("MessageV3: Transfer client authority!");
+
+            process_transfer_client_authority::process_transfer_client_authority(
+                program_id,
+                accounts,
+                TransferClientAuthority {
+                    destination_contract,
+                    new_authority,
+                },
+            )?
+        }
+        V3Instruction::AcceptClientAuthority {
+            destination_contract,
+        } => {
+            msg!("MessageV3: Accept client authority!");
+
+            process_accept_client_authority::process_accept_client_authority(
+                program_id,
+                accounts,
+                destination_contract,
+            )?
+        }
+        V3Instruction::CloseClient {
+            destination_contract,
+        } => {
+            msg!("MessageV3: Close client!");
+
+            process_close_client::process_close_client(program_id, accounts, destination_contract)?
+        }
+        V3Instruction::SetRateLimit { target, limit } => {
+            msg!("MessageV3: Set rate limit!");
+
+            process_set_rate_limit::process_set_rate_limit(
+                accounts,
+                program_id,
+                SetRateLimit { target, limit },
+            )?
+        }
+        V3Instruction::MigrateAccount { kind } => {
+            msg!("MessageV3: Migrate account!");
+
+            process_migrate_account::process_migrate_account(program_id, accounts, kind)?
+        }
+        V3Instruction::MigrateConfig => {
+            msg!("MessageV3: Migrate config!");
+
+            process_migrate_config::process_migrate_config(program_id, accounts)?
+        }
+        V3Instruction::RegisterChain {
+            chain_id,
+            name,
+            address_format,
+            min_confirmations,
+            fees,
+            enabled,
+        } => {
+            msg!("MessageV3: Register chain!");
+
+            process_register_chain::process_register_chain(
+                program_id,
+                accounts,
+                RegisterChain {
+                    chain_id,
+                    name,
+                    address_format,
+                    min_confirmations,
+                    fees,
+                    enabled,
+                },
+            )?
+        }
+        V3Instruction::SetBlsValidators {
+            offset,
+            keys,
+            threshold,
+            enabled,
+        } => {
+            msg!("MessageV3: Set BLS validators!");
+
+            process_set_bls_validators::process_set_bls_validators(
+                program_id,
+                accounts,
+                SetBlsValidators {
+                    offset,
+                    keys,
+                    threshold,
+                    enabled,
+                },
+            )?
+        }
+        V3Instruction::SetEd25519Validators { keys, threshold } => {
+            msg!("MessageV3: Set Ed25519 validators!");
+
+            process_set_ed25519_validators::process_set_ed25519_validators(
+                program_id,
+                accounts,
+                SetEd25519Validators { keys, threshold },
+            )?
+        }
+        V3Instruction::SetOptimisticConfig {
+            challenge_period,
+            min_bond,
+            watchers,
+        } => {
+            msg!("MessageV3: Set optimistic config!");
+
+            process_set_optimistic_config::process_set_optimistic_config(
+                program_id,
+                accounts,
+                SetOptimisticConfig {
+                    challenge_period,
+                    min_bond,
+                    watchers,
+                },
+            )?
+        }
+        V3Instruction::SetClientOptimistic {
+            destination_contract,
+            enabled,
+        } => {
+            msg!("MessageV3: Set client optimistic!");
+
+            process_set_client_optimistic::process_set_client_optimistic(
+                program_id,
+                accounts,
+                destination_contract,
+                enabled,
+            )?
+        }
+        V3Instruction::DepositWithdrawBond { action, amount } => {
+            msg!("MessageV3: Deposit or withdraw bond!");
+
+            process_deposit_withdraw_bond::process_deposit_withdraw_bond(
+                program_id, accounts, amount, action,
+            )?
+        }
+        V3Instruction::PostCommitment {
+            tx_id,
+            receiver,
+            hash,
+        } => {
+            msg!("MessageV3: Post commitment!");
+
+            process_post_commitment::process_post_commitment(
+                program_id,
+                accounts,
+                PostCommitment {
+                    tx_id,
+                    receiver,
+                    hash,
+                },
+            )?
+        }
+        V3Instruction::ChallengeCommitment {
+            tx_id,
+            source_chain_id,
+            sender,
+            receiver,
+            payload_hash,
+            signature,
+        } => {
+            msg!("MessageV3: Challenge commitment!");
+
+            process_challenge_commitment::process_challenge_commitment(
+                program_id,
+                accounts,
+                tx_id,
+                FraudEvidence {
+                    source_chain_id,
+                    sender,
+                    receiver,
+                    payload_hash,
+                    signature,
+                },
+            )?
+        }
+        V3Instruction::PostMessageRoot {
+            source_chain_id,
+            batch,
+            root,
+            signature,
+        } => {
+            msg!("MessageV3: Post message root!");
+
+            process_post_message_root::process_post_message_root(
+                program_id,
+                accounts,
+                PostMessageRoot {
+                    source_chain_id,
+                    batch,
+                    root,
+                    signature,
+                },
+            )?
+        }
+        V3Instruction::ReceiveBatchedMessage {
+            tx_id,
+            dest_chain_id,
+            receiver,
+            data,
+            source_chain_id,
+            sender,
+            batch,
+            proof,
+        } => {
+            msg!("MessageV3: Receive batched message!");
+
+            process_receive_message::process_receive_message(
+                ReceiveMessage {
+                    tx_id,
+                    dest_chain_id,
+                    receiver,
+                    data,
+                    source_chain_id,
+                    sender,
+                },
+                Some(BatchProof { batch, proof }),
+                program_id,
+                accounts,
+            )?
+        }
+        V3Instruction::SetClientOrdered {
+            destination_contract,
+            enabled,
+        } => {
+            msg!("MessageV3: Set client ordered!");
+
+            process_set_client_ordered::process_set_client_ordered(
+                program_id,
+                accounts,
+                destination_contract,
+                enabled,
+            )?
+        }
+        V3Instruction::DisputeCommitment { tx_id } => {
+            msg!("MessageV3: Dispute commitment!");
+
+            process_dispute_commitment::process_dispute_commitment(program_id, accounts, tx_id)?
+        }
+        V3Instruction::SettleDispute { tx_id, signature } => {
+            msg!("MessageV3: Settle dispute!");
+
+            process_settle_dispute::process_settle_dispute(program_id, accounts, tx_id, signature)?
+        }
     }
 
     Ok(())
@@ -0,0 +1,54 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::MessengerError,
+    events::GatewayEvent,
+    state::account::{load_message_client, GatewayAccount},
+    utils::{assert_account_signer, check_client_seeds},
+};
+
+pub fn process_accept_client_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    destination_contract: Pubkey,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let new_authority = next_account_info(accounts)?;
+
+    assert_account_signer(new_authority)?;
+
+    let message_client = next_account_info(accounts)?;
+
+    check_client_seeds(destination_contract, *message_client.key, program_id)?;
+
+    let mut decoded_client = load_message_client(message_client, program_id)?;
+
+    if decoded_client.pending_authority != Some(*new_authority.key) {
+        return Err(MessengerError::NotPendingAuthority.into());
+    }
+
+    decoded_client.authority = *new_authority.key;
+    decoded_client.pending_authority = None;
+
+    let serialized_client = decoded_client.pack()?;
+
+    message_client.realloc(serialized_client.len(), false)?;
+
+    message_client
+        .data
+        .borrow_mut()
+        .copy_from_slice(&serialized_client);
+
+    GatewayEvent::ClientAuthorityTransferred {
+        destination_contract,
+        authority: *new_authority.key,
+    }
+    .emit();
+
+    Ok(())
+}
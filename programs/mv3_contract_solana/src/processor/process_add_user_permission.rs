@@ -1,19 +1,20 @@
-use borsh::BorshSerialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    borsh0_10::try_from_slice_unchecked,
     entrypoint::ProgramResult,
     pubkey::Pubkey,
     system_program::ID,
-    sysvar::rent::{Rent, ID as RentPubkey},
 };
 
 use crate::{
-    constants::CONFIG_SEED,
+    constants::{CONFIG_SEED, ROLES_SEED},
     error::MessengerError,
+    events::GatewayEvent,
     instruction::AddUserPermission,
-    state::config::{MessengerConfig, Role, UserPermission},
-    utils::{check_keys_eq, check_seeds, transfer_sol},
+    state::{
+        account::{load_account, GatewayAccount},
+        config::{MessengerConfig, Role, RoleRegistry, UserPermission},
+    },
+    utils::{check_keys_eq, write_resized},
 };
 
 pub fn process_add_user_permission(
@@ -27,24 +28,25 @@ pub fn process_add_user_permission(
 
     let raw_config = next_account_info(accounts)?;
 
-    check_seeds(raw_config, &[CONFIG_SEED], program_id)?;
-
     let system_program = next_account_info(accounts)?;
 
     check_keys_eq(system_program.key, &ID)?;
 
-    let mut config: MessengerConfig =
-        try_from_slice_unchecked(&raw_config.data.borrow_mut()).unwrap();
+    let config: MessengerConfig = load_account(raw_config, program_id, &[CONFIG_SEED])?;
 
     check_keys_eq(authority.key, &config.owner)?;
 
+    let raw_roles = next_account_info(accounts)?;
+
+    let mut roles: RoleRegistry = load_account(raw_roles, program_id, &[ROLES_SEED])?;
+
     //TODO: add custom logic for permissions per action
 
     let permissions = match data.role {
-        Role::ATeam => &mut config.bridge_a_team,
-        Role::Whitelist => &mut config.whitelists,
-        Role::Operator => &mut config.bridge_operators,
-        Role::Super => &mut config.bridge_supers,
+        Role::ATeam => &mut roles.bridge_a_team,
+        Role::Whitelist => &mut roles.whitelists,
+        Role::Operator => &mut roles.bridge_operators,
+        Role::Super => &mut roles.bridge_supers,
         _ => {
             return Err(MessengerError::InvalidRoleForAction.into());
         }
@@ -57,27 +59,16 @@ pub fn process_add_user_permission(
             is_active: data.is_active,
             wallet: data.user,
         });
+    }
 
-        let rent = next_account_info(accounts)?;
-
-        check_keys_eq(rent.key, &RentPubkey)?;
-
-        let realloc_fee = Rent::default().minimum_balance(UserPermission::LEN);
-
-        transfer_sol(authority, raw_config, realloc_fee, system_program, None)?;
+    write_resized(authority, raw_roles, system_program, &roles.pack()?)?;
 
-        raw_config.realloc(
-            raw_config
-                .data_len()
-                .checked_add(UserPermission::LEN)
-                .unwrap(),
-            false,
-        )?;
+    GatewayEvent::RoleChanged {
+        user: data.user,
+        role: data.role,
+        is_active: data.is_active,
     }
+    .emit();
 
-    raw_config
-        .data
-        .borrow_mut()
-        .copy_from_slice(&config.try_to_vec().unwrap());
     Ok(())
 }
@@ -0,0 +1,123 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    constants::{BOND_SEED, COMMITMENT_SEED, CONFIG_SEED, OPTIMISTIC_SEED},
+    error::MessengerError,
+    events::GatewayEvent,
+    instruction::{FraudEvidence, MessageDigest},
+    state::{
+        account::{load_account, store_account},
+        config::MessengerConfig,
+        optimistic::{MessageCommitment, OperatorBond, OptimisticConfig},
+    },
+    utils::{
+        assert_account_signer, check_global_treasury_seeds, debit_lamports, load_validator_set,
+        verify_validator_signature,
+    },
+};
+
+pub fn process_challenge_commitment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    tx_id: u128,
+    evidence: FraudEvidence,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let watcher = next_account_info(accounts)?;
+
+    assert_account_signer(watcher)?;
+
+    let raw_optimistic_config = next_account_info(accounts)?;
+
+    let optimistic_config: OptimisticConfig =
+        load_account(raw_optimistic_config, program_id, &[OPTIMISTIC_SEED])?;
+
+    if !optimistic_config.is_watcher(watcher.key) {
+        return Err(MessengerError::CallerNotWatcher.into());
+    }
+
+    let raw_commitment = next_account_info(accounts)?;
+
+    let mut commitment: MessageCommitment = load_account(
+        raw_commitment,
+        program_id,
+        &[COMMITMENT_SEED, &tx_id.to_le_bytes()],
+    )?;
+
+    commitment.check_challengeable(Clock::get()?.unix_timestamp)?;
+
+    let raw_bond = next_account_info(accounts)?;
+
+    let mut bond: OperatorBond = load_account(
+        raw_bond,
+        program_id,
+        &[BOND_SEED, commitment.operator.as_ref()],
+    )?;
+
+    let global_treasury = next_account_info(accounts)?;
+
+    check_global_treasury_seeds(*global_treasury.key, program_id)?;
+
+    let raw_config = next_account_info(accounts)?;
+
+    let config = load_account::<MessengerConfig>(raw_config, program_id, &[CONFIG_SEED])?;
+
+    let raw_validators = next_account_info(accounts)?;
+
+    let validator_set = load_validator_set(raw_validators, &config, program_id)?;
+
+    let sysvar_instructions = next_account_info(accounts)?;
+
+    //the validators sign a single message per tx_id, so a signed message other than the
+    //committed one proves the commitment fraudulent
+    let hashed = MessageDigest {
+        tx_id,
+        source_chain_id: evidence.source_chain_id,
+        dest_chain_id: config.chain_id(),
+        sender: evidence.sender,
+        receiver: evidence.receiver,
+        gateway: *program_id,
+        payload_hash: evidence.payload_hash,
+    }
+    .hash(config.digest_scheme());
+
+    if hashed == commitment.hash {
+        return Err(MessengerError::NoFraudProven.into());
+    }
+
+    verify_validator_signature(
+        &config,
+        validator_set.as_ref(),
+        sysvar_instructions,
+        &hashed,
+        &evidence.signature,
+    )?;
+
+    //the whole bond is slashed, so the operator can't commit again before bonding anew
+    let slashed = bond.amount;
+
+    debit_lamports(raw_bond, global_treasury, slashed)?;
+
+    bond.amount = 0;
+    commitment.challenged = true;
+
+    store_account(raw_bond, &bond)?;
+    store_account(raw_commitment, &commitment)?;
+
+    GatewayEvent::CommitmentChallenged {
+        tx_id,
+        watcher: *watcher.key,
+        operator: commitment.operator,
+        slashed,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -1,20 +1,21 @@
-use borsh::BorshSerialize;
-
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    borsh0_10::try_from_slice_unchecked,
     entrypoint::ProgramResult,
     msg,
     pubkey::Pubkey,
-    rent::Rent,
     system_program::ID,
 };
 
 use crate::{
     constants::CONFIG_SEED,
+    error::MessengerError,
+    events::GatewayEvent,
     instruction::ChangeConfig,
-    state::config::MessengerConfig,
-    utils::{check_keys_eq, check_seeds, transfer_sol},
+    state::{
+        account::{load_account, store_account},
+        config::MessengerConfig,
+    },
+    utils::check_keys_eq,
 };
 
 pub fn process_change_config(
@@ -30,16 +31,12 @@ pub fn process_change_config(
 
     msg!("Config len {}", raw_config.data_len());
 
-    check_seeds(raw_config, &[CONFIG_SEED], program_id)?;
-
-    let mut config: Box<MessengerConfig> = Box::new(try_from_slice_unchecked::<MessengerConfig>(
-        &raw_config.data.borrow_mut(),
-    )?);
+    let mut config: MessengerConfig = load_account(raw_config, program_id, &[CONFIG_SEED])?;
 
     msg!("Config {:?}", config);
 
     if *authority.key != config.owner {
-        return ProgramResult::Err(solana_program::program_error::ProgramError::IllegalOwner);
+        return Err(MessengerError::CallerNotOwner.into());
     }
 
     check_keys_eq(authority.key, &config.owner)?;
@@ -50,44 +47,35 @@ pub fn process_change_config(
 
     check_keys_eq(system_program.key, &ID)?;
 
+    let event = GatewayEvent::ConfigChanged {
+        bridge_enabled: data.bridge_enabled,
+        accountant: data.accountant,
+        whitelist_only: data.whitelist_only,
+        chainsig: data.chainsig,
+        digest_scheme: data.digest_scheme,
+    };
+
     if let Some(new_accountant) = data.accountant {
         config.accountant = new_accountant;
     }
 
     if let Some(whitelist_only) = data.whitelist_only {
-        config.whitelist_only = whitelist_only;
+        config.set_whitelist_only(whitelist_only);
     }
 
     if let Some(bridge_enabled) = data.bridge_enabled {
-        config.bridge_enabled = bridge_enabled;
+        config.set_bridge_enabled(bridge_enabled);
     }
 
-    if let Some(chains) = data.enabled_chains {
-        config.enabled_chains = chains;
-    }
+    config.set_chainsig(data.chainsig);
 
-    config.chainsig = data.chainsig;
-
-    match config
-        .try_to_vec()
-        .unwrap()
-        .len()
-        .checked_sub(raw_config.data_len())
-    {
-        Some(len_diff) => {
-            msg!("Len diff {}", len_diff);
-            let additional_rent = Rent::default().minimum_balance(len_diff);
-
-            transfer_sol(authority, raw_config, additional_rent, system_program, None)?;
-        }
-        None => {}
+    if let Some(digest_scheme) = data.digest_scheme {
+        config.set_digest_scheme(digest_scheme);
     }
-    raw_config.realloc(config.try_to_vec().unwrap().len(), false)?;
 
-    raw_config
-        .data
-        .borrow_mut()
-        .copy_from_slice(&config.try_to_vec().unwrap());
+    store_account(raw_config, &config)?;
+
+    event.emit();
 
     Ok(())
 }
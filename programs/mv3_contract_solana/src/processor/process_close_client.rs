@@ -0,0 +1,100 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    system_program::ID,
+};
+
+use crate::{
+    constants::{CLIENT_RATE_LIMIT_SEED, MESSAGE_CLIENT_SEED, MESSAGE_CLIENT_TREASURY_SEED},
+    error::MessengerError,
+    events::GatewayEvent,
+    state::account::load_message_client,
+    utils::{
+        assert_account_signer, check_client_seeds, check_client_treasury_seeds, check_keys_eq,
+        check_seeds, close_account, transfer_sol,
+    },
+};
+
+pub fn process_close_client(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    destination_contract: Pubkey,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let authority = next_account_info(accounts)?;
+
+    assert_account_signer(authority)?;
+
+    let message_client = next_account_info(accounts)?;
+
+    check_client_seeds(destination_contract, *message_client.key, program_id)?;
+
+    let treasury = next_account_info(accounts)?;
+
+    let treasury_bump =
+        check_client_treasury_seeds(destination_contract, *treasury.key, program_id)?;
+
+    let system_program = next_account_info(accounts)?;
+
+    check_keys_eq(system_program.key, &ID)?;
+
+    let rate_limit = next_account_info(accounts)?;
+
+    check_seeds(
+        rate_limit,
+        &[CLIENT_RATE_LIMIT_SEED, destination_contract.as_ref()],
+        program_id,
+    )?;
+
+    let decoded_client = load_message_client(message_client, program_id)?;
+
+    if decoded_client.authority != *authority.key {
+        return Err(MessengerError::InvalidUpdateAuthority.into());
+    }
+
+    //pausing stops relayers from delivering, so no message can be mid-flight once it is closed.
+    //The authority pauses its client itself with `SetPause`.
+    if !decoded_client.paused {
+        return Err(MessengerError::ClientNotPaused.into());
+    }
+
+    let treasury_balance = treasury.lamports();
+
+    if treasury_balance > 0 {
+        transfer_sol(
+            treasury,
+            authority,
+            treasury_balance,
+            system_program,
+            Some(&[
+                MESSAGE_CLIENT_SEED,
+                destination_contract.as_ref(),
+                MESSAGE_CLIENT_TREASURY_SEED,
+                &[treasury_bump],
+            ]),
+        )?;
+    }
+
+    let client_balance = close_account(message_client, authority)?;
+
+    //the rate limit bucket only exists once the client sent or received under a limit. Channels
+    //are kept, so a client registered again continues the sequences remote senders already use,
+    //and commitments back the bonds of their operators until they are settled.
+    let rate_limit_balance = if rate_limit.owner == program_id {
+        close_account(rate_limit, authority)?
+    } else {
+        0
+    };
+
+    GatewayEvent::ClientClosed {
+        destination_contract,
+        lamports: treasury_balance
+            .saturating_add(client_balance)
+            .saturating_add(rate_limit_balance),
+    }
+    .emit();
+
+    Ok(())
+}
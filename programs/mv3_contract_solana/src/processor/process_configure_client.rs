@@ -1,31 +1,34 @@
-use std::ops::Add;
-
-use borsh::BorshSerialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
-    program::{invoke, invoke_signed},
+    program::invoke,
     pubkey::Pubkey,
     rent::Rent,
     system_instruction,
 };
 
 use crate::{
-    constants::MESSAGE_CLIENT_SEED, error::MessengerError, state::config::MessageClient,
-    utils::get_message_client_pda,
+    constants::MESSAGE_CLIENT_SEED,
+    error::MessengerError,
+    events::GatewayEvent,
+    state::{
+        account::{load_chain, load_owned_account, GatewayAccount, DISCRIMINATOR_LEN},
+        config::MessageClient,
+    },
+    utils::{get_message_client_pda, get_program_upgrade_authority, initialize_account},
 };
 
 pub fn process_configure_client(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    data: MessageClient,
+    mut data: MessageClient,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
 
     let payer = next_account_info(accounts_iter)?;
 
     if !payer.is_signer {
-        return Err(solana_program::program_error::ProgramError::MissingRequiredSignature.into());
+        return Err(MessengerError::AccountNotSigner.into());
     }
 
     let message_client = next_account_info(accounts_iter)?;
@@ -36,69 +39,108 @@ pub fn process_configure_client(
         return Err(MessengerError::InvalidClientProgramId.into());
     }
 
-    let (pda, bump) = get_message_client_pda(data.destination_contract);
+    let program_data = next_account_info(accounts_iter)?;
+
+    let (pda, bump) = get_message_client_pda(data.destination_contract, program_id);
 
     if *message_client.key != pda {
-        return Err(solana_program::program_error::ProgramError::InvalidSeeds.into());
+        return Err(MessengerError::InvalidAccountSeeds.into());
     }
 
     let system_program = next_account_info(accounts_iter)?;
 
-    if message_client.data_is_empty() {
-        let ix = system_instruction::create_account(
-            payer.key,
-            message_client.key,
-            Rent::default().minimum_balance(MessageClient::LEN as usize),
-            MessageClient::LEN,
-            &program_id,
-        );
-
-        invoke_signed(
-            &ix,
+    //registry entries of the supported chains follow, in the same order
+    let mut formats = Vec::with_capacity(data.supported_chains.len());
+
+    for chain_id in &data.supported_chains {
+        let chain = load_chain(next_account_info(accounts_iter)?, program_id, *chain_id)?;
+
+        formats.push(chain.address_format);
+    }
+
+    data.check_allowed_contracts(&formats)?;
+
+    let registered = message_client.data_is_empty();
+
+    if registered {
+        //only the upgrade authority of the destination program can register it as a client
+        let upgrade_authority = get_program_upgrade_authority(program, program_data)?;
+
+        if upgrade_authority != Some(*payer.key) {
+            return Err(MessengerError::InvalidUpdateAuthority.into());
+        }
+
+        //lamports sent to the address ahead of time don't block the registration
+        initialize_account(
+            payer,
+            message_client,
+            system_program,
+            DISCRIMINATOR_LEN as u64 + MessageClient::LEN,
+            program_id,
             &[
-                payer.to_owned(),
-                message_client.to_owned(),
-                system_program.to_owned(),
-            ],
-            &[&[
                 MESSAGE_CLIENT_SEED,
                 data.destination_contract.as_ref(),
                 &[bump],
-            ]],
+            ],
         )?;
+    } else {
+        let existing_client: MessageClient = load_owned_account(message_client, program_id)?;
+
+        if existing_client.authority != *payer.key {
+            return Err(MessengerError::InvalidUpdateAuthority.into());
+        }
+
+        if existing_client.authority != data.authority {
+            return Err(MessengerError::AuthorityTransferRequired.into());
+        }
+
+        data.paused = existing_client.paused;
+        data.pending_authority = existing_client.pending_authority;
+        data.rate_limit = existing_client.rate_limit;
+        data.optimistic = existing_client.optimistic;
+        data.ordered = existing_client.ordered;
+
+        //setting a different exsig here replaces it right away and drops any scheduled rotation
+        if data.exsig == existing_client.exsig {
+            data.pending_exsig = existing_client.pending_exsig;
+        }
     }
 
-    //TODO:add check for program update authority
+    let serialized_client = data.pack()?;
 
-    let data_diff = data
-        .try_to_vec()
-        .unwrap()
+    let data_diff = serialized_client
         .len()
-        .checked_sub(message_client.data_len())
-        .unwrap();
-
-    let additional_lamports = Rent::default().minimum_balance(data_diff);
+        .saturating_sub(message_client.data_len());
 
-    let transfer_ix =
-        system_instruction::transfer(payer.key, message_client.key, additional_lamports);
+    if data_diff > 0 {
+        let additional_lamports = Rent::default().minimum_balance(data_diff);
 
-    invoke(
-        &transfer_ix,
-        &[
-            payer.to_owned(),
-            message_client.to_owned(),
-            system_program.to_owned(),
-        ],
-    )?;
+        let transfer_ix =
+            system_instruction::transfer(payer.key, message_client.key, additional_lamports);
 
-    if data_diff > 0 {
-        message_client.realloc(message_client.data_len().add(data_diff), false)?;
+        invoke(
+            &transfer_ix,
+            &[
+                payer.to_owned(),
+                message_client.to_owned(),
+                system_program.to_owned(),
+            ],
+        )?;
     }
 
+    message_client.realloc(serialized_client.len(), false)?;
+
     message_client
         .data
         .borrow_mut()
-        .copy_from_slice(&data.try_to_vec().unwrap());
+        .copy_from_slice(&serialized_client);
+
+    GatewayEvent::ClientConfigured {
+        destination_contract: data.destination_contract,
+        authority: data.authority,
+        registered,
+    }
+    .emit();
 
     Ok(())
 }
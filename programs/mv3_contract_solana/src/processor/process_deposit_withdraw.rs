@@ -1,6 +1,5 @@
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    borsh0_10::try_from_slice_unchecked,
     entrypoint::ProgramResult,
     program::{invoke, invoke_signed},
     pubkey::Pubkey,
@@ -10,13 +9,14 @@ use solana_program::{
 use crate::{
     constants::{MESSAGE_CLIENT_SEED, MESSAGE_CLIENT_TREASURY_SEED},
     error::MessengerError,
+    events::GatewayEvent,
     instruction::DepositWithdraw,
-    state::config::MessageClient,
+    state::account::load_message_client,
     utils::check_client_treasury_seeds,
 };
 
 pub fn process_deposit_withdraw(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     amount: u64,
     action: DepositWithdraw,
@@ -27,9 +27,13 @@ pub fn process_deposit_withdraw(
     let treasury = next_account_info(accounts)?;
     let system_program = next_account_info(accounts)?;
 
-    let decoded_client = try_from_slice_unchecked::<MessageClient>(&message_client.data.borrow())?;
+    let decoded_client = load_message_client(message_client, program_id)?;
 
-    let bump = check_client_treasury_seeds(decoded_client.destination_contract, *treasury.key)?;
+    let bump = check_client_treasury_seeds(
+        decoded_client.destination_contract,
+        *treasury.key,
+        program_id,
+    )?;
 
     if decoded_client.authority != *payer.key {
         return Err(MessengerError::InvalidUpdateAuthority.into());
@@ -47,6 +51,13 @@ pub fn process_deposit_withdraw(
                     system_program.to_owned(),
                 ],
             )?;
+
+            GatewayEvent::TreasuryDeposit {
+                destination_contract: decoded_client.destination_contract,
+                payer: *payer.key,
+                amount,
+            }
+            .emit();
         }
         DepositWithdraw::Withdraw => {
             let ix = system_instruction::transfer(treasury.key, payer.key, amount);
@@ -64,6 +75,13 @@ pub fn process_deposit_withdraw(
                     &[bump],
                 ]],
             )?;
+
+            GatewayEvent::TreasuryWithdraw {
+                destination_contract: decoded_client.destination_contract,
+                recipient: *payer.key,
+                amount,
+            }
+            .emit();
         }
     }
 
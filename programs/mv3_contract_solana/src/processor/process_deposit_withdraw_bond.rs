@@ -0,0 +1,91 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    system_program::ID,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    constants::BOND_SEED,
+    error::MessengerError,
+    events::GatewayEvent,
+    instruction::DepositWithdraw,
+    state::{
+        account::{load_account, store_account},
+        optimistic::OperatorBond,
+    },
+    utils::{
+        assert_account_signer, check_keys_eq, create_state_account, debit_lamports, transfer_sol,
+    },
+};
+
+pub fn process_deposit_withdraw_bond(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    action: DepositWithdraw,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let operator = next_account_info(accounts)?;
+
+    assert_account_signer(operator)?;
+
+    let raw_bond = next_account_info(accounts)?;
+
+    let system_program = next_account_info(accounts)?;
+
+    check_keys_eq(system_program.key, &ID)?;
+
+    let seeds: &[&[u8]] = &[BOND_SEED, operator.key.as_ref()];
+
+    let mut bond = if raw_bond.data_is_empty() {
+        let bond = OperatorBond::new(*operator.key);
+
+        create_state_account(operator, raw_bond, system_program, program_id, seeds, &bond)?;
+
+        bond
+    } else {
+        load_account::<OperatorBond>(raw_bond, program_id, seeds)?
+    };
+
+    match action {
+        DepositWithdraw::Deposit => {
+            transfer_sol(operator, raw_bond, amount, system_program, None)?;
+
+            bond.amount = bond
+                .amount
+                .checked_add(amount)
+                .ok_or(MessengerError::ArithmeticOverflow)?;
+
+            GatewayEvent::BondDeposit {
+                operator: *operator.key,
+                amount,
+            }
+            .emit();
+        }
+        DepositWithdraw::Withdraw => {
+            //a commitment which can still be challenged has to be backed by the bond
+            if Clock::get()?.unix_timestamp < bond.locked_until {
+                return Err(MessengerError::ChallengePeriodActive.into());
+            }
+
+            bond.amount = bond
+                .amount
+                .checked_sub(amount)
+                .ok_or(MessengerError::InsufficientBond)?;
+
+            debit_lamports(raw_bond, operator, amount)?;
+
+            GatewayEvent::BondWithdraw {
+                operator: *operator.key,
+                amount,
+            }
+            .emit();
+        }
+    }
+
+    store_account(raw_bond, &bond)
+}
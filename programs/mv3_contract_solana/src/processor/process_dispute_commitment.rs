@@ -0,0 +1,94 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    system_program::ID,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    constants::{BOND_SEED, COMMITMENT_SEED, OPTIMISTIC_SEED},
+    error::MessengerError,
+    events::GatewayEvent,
+    state::{
+        account::{load_account, store_account, GatewayAccount},
+        optimistic::{CommitmentDispute, MessageCommitment, OperatorBond, OptimisticConfig},
+    },
+    utils::{assert_account_signer, check_keys_eq, write_resized},
+};
+
+pub fn process_dispute_commitment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    tx_id: u128,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let watcher = next_account_info(accounts)?;
+
+    assert_account_signer(watcher)?;
+
+    let raw_optimistic_config = next_account_info(accounts)?;
+
+    let optimistic_config: OptimisticConfig =
+        load_account(raw_optimistic_config, program_id, &[OPTIMISTIC_SEED])?;
+
+    if !optimistic_config.is_watcher(watcher.key) {
+        return Err(MessengerError::CallerNotWatcher.into());
+    }
+
+    let raw_commitment = next_account_info(accounts)?;
+
+    let mut commitment: MessageCommitment = load_account(
+        raw_commitment,
+        program_id,
+        &[COMMITMENT_SEED, &tx_id.to_le_bytes()],
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    commitment.check_challengeable(now)?;
+
+    if commitment.dispute.is_some() {
+        return Err(MessengerError::CommitmentDisputed.into());
+    }
+
+    let raw_bond = next_account_info(accounts)?;
+
+    let mut bond: OperatorBond = load_account(
+        raw_bond,
+        program_id,
+        &[BOND_SEED, commitment.operator.as_ref()],
+    )?;
+
+    let system_program = next_account_info(accounts)?;
+
+    check_keys_eq(system_program.key, &ID)?;
+
+    //the validators get a full challenge period to vouch for the committed message
+    let ends_at = now
+        .checked_add(optimistic_config.challenge_period)
+        .ok_or(MessengerError::ArithmeticOverflow)?;
+
+    commitment.dispute = Some(CommitmentDispute {
+        watcher: *watcher.key,
+        ends_at,
+    });
+
+    //the operator can't withdraw the bond at stake before the dispute is settled
+    bond.locked_until = bond.locked_until.max(ends_at);
+
+    write_resized(watcher, raw_commitment, system_program, &commitment.pack()?)?;
+    store_account(raw_bond, &bond)?;
+
+    GatewayEvent::CommitmentDisputed {
+        tx_id,
+        watcher: *watcher.key,
+        operator: commitment.operator,
+        ends_at,
+    }
+    .emit();
+
+    Ok(())
+}
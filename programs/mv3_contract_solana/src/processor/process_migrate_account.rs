@@ -0,0 +1,130 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    borsh0_10::try_from_slice_unchecked,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program::ID,
+};
+
+use crate::{
+    constants::{CONFIG_SEED, MESSAGE_CLIENT_SEED, MESSAGE_SEED},
+    error::MessengerError,
+    events::GatewayEvent,
+    state::{
+        account::{is_gateway_account, load_account, AccountKind, GatewayAccount},
+        config::{MessageClient, MessengerConfig},
+        message::{Message, MessagePayload},
+        rate_limit::RateLimitState,
+        version::{MessageV0, VersionedAccount},
+    },
+    utils::{assert_account_signer, check_keys_eq, check_seeds, write_resized},
+};
+
+pub fn process_migrate_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    kind: AccountKind,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let authority = next_account_info(accounts)?;
+
+    assert_account_signer(authority)?;
+
+    let raw_config = next_account_info(accounts)?;
+
+    check_seeds(raw_config, &[CONFIG_SEED], program_id)?;
+
+    let system_program = next_account_info(accounts)?;
+
+    check_keys_eq(system_program.key, &ID)?;
+
+    //configs are split into several accounts, which only `MigrateConfig` creates
+    if kind == AccountKind::Config {
+        return Err(MessengerError::UnsupportedAccountVersion.into());
+    }
+
+    let account = next_account_info(accounts)?;
+
+    let owner = load_account::<MessengerConfig>(raw_config, program_id, &[CONFIG_SEED])?.owner;
+
+    if *authority.key != owner {
+        return Err(MessengerError::CallerNotOwner.into());
+    }
+
+    if account.owner != program_id {
+        return Err(MessengerError::InvalidAccountOwner.into());
+    }
+
+    let migrated = match kind {
+        AccountKind::Config => return Err(MessengerError::UnsupportedAccountVersion.into()),
+        AccountKind::MessageClient => {
+            let client = unpack_outdated::<MessageClient>(account)?;
+
+            check_seeds(
+                account,
+                &[MESSAGE_CLIENT_SEED, client.destination_contract.as_ref()],
+                program_id,
+            )?;
+
+            client.pack()?
+        }
+        AccountKind::Message => {
+            check_seeds(account, &[MESSAGE_SEED], program_id)?;
+
+            Message::from(unpack_legacy::<MessageV0>(account)?).pack()?
+        }
+        AccountKind::ReceivedMessage => {
+            let message = unpack_legacy::<MessagePayload>(account)?;
+
+            check_seeds(
+                account,
+                &[MESSAGE_SEED, &message.tx_id.to_le_bytes()],
+                program_id,
+            )?;
+
+            message.pack()?
+        }
+        //rate limit seeds hold the chain or client they limit, which the state does not store
+        AccountKind::RateLimit(target) => {
+            let (bucket_seed, target_seed) = target.bucket_seeds();
+
+            check_seeds(account, &[bucket_seed, &target_seed], program_id)?;
+
+            unpack_legacy::<RateLimitState>(account)?.pack()?
+        }
+    };
+
+    write_resized(authority, account, system_program, &migrated)?;
+
+    GatewayEvent::AccountMigrated {
+        account: *account.key,
+        kind,
+    }
+    .emit();
+
+    Ok(())
+}
+
+/// Decodes an account written before discriminators were introduced
+fn unpack_legacy<T: BorshDeserialize>(account: &AccountInfo) -> Result<T, ProgramError> {
+    let data = account.data.borrow();
+
+    if is_gateway_account(&data) {
+        return Err(MessengerError::AccountAlreadyMigrated.into());
+    }
+
+    Ok(try_from_slice_unchecked(&data)?)
+}
+
+/// Decodes a versioned account stored in a layout older than the current one
+fn unpack_outdated<T: VersionedAccount>(account: &AccountInfo) -> Result<T, ProgramError> {
+    match T::unpack_versioned(&account.data.borrow())? {
+        (version, _) if Some(version) == T::VERSION => {
+            Err(MessengerError::AccountAlreadyMigrated.into())
+        }
+        (_, state) => Ok(state),
+    }
+}
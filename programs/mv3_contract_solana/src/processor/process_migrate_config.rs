@@ -0,0 +1,145 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    system_program::ID,
+};
+
+use crate::{
+    constants::{CHAIN_SEED, CONFIG_SEED, MESSAGE_CLIENT_SEED, ROLES_SEED},
+    error::MessengerError,
+    events::GatewayEvent,
+    state::{
+        account::{load_account, AccountKind, GatewayAccount},
+        config::{MessageClient, MessengerConfig},
+        version::{stored_version, unpack_legacy_config, VersionedAccount},
+    },
+    utils::{
+        assert_account_signer, check_keys_eq, check_seeds, create_state_account, write_resized,
+    },
+};
+
+pub fn process_migrate_config(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let authority = next_account_info(accounts)?;
+
+    assert_account_signer(authority)?;
+
+    let raw_config = next_account_info(accounts)?;
+
+    check_seeds(raw_config, &[CONFIG_SEED], program_id)?;
+
+    if raw_config.owner != program_id {
+        return Err(MessengerError::InvalidAccountOwner.into());
+    }
+
+    let system_program = next_account_info(accounts)?;
+
+    check_keys_eq(system_program.key, &ID)?;
+
+    let raw_roles = next_account_info(accounts)?;
+
+    let version = stored_version::<MessengerConfig>(&raw_config.data.borrow())?;
+
+    let chains = if version == MessengerConfig::VERSION {
+        let config: MessengerConfig = load_account(raw_config, program_id, &[CONFIG_SEED])?;
+
+        if *authority.key != config.owner {
+            return Err(MessengerError::CallerNotOwner.into());
+        }
+
+        vec![]
+    } else {
+        let legacy = unpack_legacy_config(&raw_config.data.borrow())?;
+
+        if *authority.key != legacy.owner {
+            return Err(MessengerError::CallerNotOwner.into());
+        }
+
+        //roles move to their own account and chains to the registry, the config keeps the header
+        let (config, roles, chains) = legacy.split();
+
+        write_resized(authority, raw_config, system_program, &config.pack()?)?;
+
+        create_state_account(
+            authority,
+            raw_roles,
+            system_program,
+            program_id,
+            &[ROLES_SEED],
+            &roles,
+        )?;
+
+        GatewayEvent::AccountMigrated {
+            account: *raw_config.key,
+            kind: AccountKind::Config,
+        }
+        .emit();
+
+        chains
+    };
+
+    //the registry accounts of the imported chains come first, in `MessengerConfigV0::chain_ids` order
+    for chain in chains {
+        let raw_chain = next_account_info(accounts)?;
+
+        let seeds: &[&[u8]] = &[CHAIN_SEED, &chain.chain_id.to_le_bytes()];
+
+        check_seeds(raw_chain, seeds, program_id)?;
+
+        //chains registered since keep their entry
+        if !raw_chain.data_is_empty() {
+            continue;
+        }
+
+        create_state_account(
+            authority,
+            raw_chain,
+            system_program,
+            program_id,
+            seeds,
+            &chain,
+        )?;
+
+        GatewayEvent::ChainRegistered {
+            chain_id: chain.chain_id,
+            name: chain.name,
+            address_format: chain.address_format,
+            min_confirmations: chain.min_confirmations,
+            fees: chain.fees,
+            enabled: chain.enabled,
+            registered: true,
+        }
+        .emit();
+    }
+
+    //every remaining account is a message client to upgrade along with the config
+    for message_client in accounts {
+        if message_client.owner != program_id {
+            return Err(MessengerError::InvalidAccountOwner.into());
+        }
+
+        let (version, client) = MessageClient::unpack_versioned(&message_client.data.borrow())?;
+
+        check_seeds(
+            message_client,
+            &[MESSAGE_CLIENT_SEED, client.destination_contract.as_ref()],
+            program_id,
+        )?;
+
+        if version == MessageClient::VERSION {
+            continue;
+        }
+
+        write_resized(authority, message_client, system_program, &client.pack()?)?;
+
+        GatewayEvent::AccountMigrated {
+            account: *message_client.key,
+            kind: AccountKind::MessageClient,
+        }
+        .emit();
+    }
+
+    Ok(())
+}
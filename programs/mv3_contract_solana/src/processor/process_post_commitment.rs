@@ -0,0 +1,133 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    system_program::ID,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    constants::{BOND_SEED, COMMITMENT_SEED, OPTIMISTIC_SEED, ROLES_SEED},
+    error::MessengerError,
+    events::GatewayEvent,
+    instruction::PostCommitment,
+    state::{
+        account::{load_account, load_message_client, store_account},
+        config::{Role, RoleRegistry},
+        optimistic::{MessageCommitment, OperatorBond, OptimisticConfig},
+    },
+    utils::{
+        assert_account_signer, check_client_seeds, check_keys_eq, check_seeds,
+        create_state_account, role_guard,
+    },
+};
+
+pub fn process_post_commitment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: PostCommitment,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let operator = next_account_info(accounts)?;
+
+    assert_account_signer(operator)?;
+
+    let raw_roles = next_account_info(accounts)?;
+
+    let roles: RoleRegistry = load_account(raw_roles, program_id, &[ROLES_SEED])?;
+
+    role_guard(&roles, operator, Role::Operator)?;
+
+    let raw_optimistic_config = next_account_info(accounts)?;
+
+    let optimistic_config: OptimisticConfig =
+        load_account(raw_optimistic_config, program_id, &[OPTIMISTIC_SEED])?;
+
+    let raw_bond = next_account_info(accounts)?;
+
+    let mut bond: OperatorBond =
+        load_account(raw_bond, program_id, &[BOND_SEED, operator.key.as_ref()])?;
+
+    if bond.amount < optimistic_config.min_bond {
+        return Err(MessengerError::InsufficientBond.into());
+    }
+
+    let message_client = next_account_info(accounts)?;
+
+    check_client_seeds(data.receiver, *message_client.key, program_id)?;
+
+    let decoded_client = load_message_client(message_client, program_id)?;
+
+    if !decoded_client.optimistic {
+        return Err(MessengerError::ClientNotOptimistic.into());
+    }
+
+    if decoded_client.paused {
+        return Err(MessengerError::ClientPaused.into());
+    }
+
+    let raw_commitment = next_account_info(accounts)?;
+
+    let seeds: &[&[u8]] = &[COMMITMENT_SEED, &data.tx_id.to_le_bytes()];
+
+    //a message is committed once, unless its commitment was proven fraudulent. A challenged
+    //commitment is replaced, so a bogus commitment can't block the message for good.
+    if raw_commitment.data_is_empty() {
+        check_seeds(raw_commitment, seeds, program_id)?;
+    } else {
+        let existing: MessageCommitment = load_account(raw_commitment, program_id, seeds)?;
+
+        if !existing.challenged {
+            return Err(MessengerError::MessageAlreadyProcessed.into());
+        }
+    }
+
+    let system_program = next_account_info(accounts)?;
+
+    check_keys_eq(system_program.key, &ID)?;
+
+    let executable_at = Clock::get()?
+        .unix_timestamp
+        .checked_add(optimistic_config.challenge_period)
+        .ok_or(MessengerError::ArithmeticOverflow)?;
+
+    let commitment = MessageCommitment {
+        version: MessageCommitment::VERSION,
+        operator: *operator.key,
+        tx_id: data.tx_id,
+        receiver: data.receiver,
+        hash: data.hash,
+        executable_at,
+        challenged: false,
+        dispute: None,
+    };
+
+    if raw_commitment.data_is_empty() {
+        create_state_account(
+            operator,
+            raw_commitment,
+            system_program,
+            program_id,
+            seeds,
+            &commitment,
+        )?;
+    } else {
+        store_account(raw_commitment, &commitment)?;
+    }
+
+    bond.locked_until = bond.locked_until.max(executable_at);
+
+    store_account(raw_bond, &bond)?;
+
+    GatewayEvent::CommitmentPosted {
+        tx_id: data.tx_id,
+        operator: *operator.key,
+        receiver: data.receiver,
+        executable_at,
+    }
+    .emit();
+
+    Ok(())
+}
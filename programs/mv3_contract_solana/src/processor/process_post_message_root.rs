@@ -0,0 +1,116 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    system_program::ID,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    constants::{CONFIG_SEED, ROOT_SEED},
+    error::MessengerError,
+    events::GatewayEvent,
+    instruction::{PostMessageRoot, RootDigest},
+    state::{
+        account::{load_account, load_chain},
+        batch::MessageRoot,
+        config::MessengerConfig,
+    },
+    utils::{
+        assert_account_signer, check_keys_eq, check_seeds, create_state_account,
+        load_validator_set, verify_validator_signature,
+    },
+};
+
+pub fn process_post_message_root(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: PostMessageRoot,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let payer = next_account_info(accounts)?;
+
+    assert_account_signer(payer)?;
+
+    let raw_config = next_account_info(accounts)?;
+
+    let config = load_account::<MessengerConfig>(raw_config, program_id, &[CONFIG_SEED])?;
+
+    if !config.bridge_enabled() {
+        return Err(MessengerError::BrigdeNotEnabled.into());
+    }
+
+    let raw_source_chain = next_account_info(accounts)?;
+
+    load_chain(raw_source_chain, program_id, data.source_chain_id)?;
+
+    let raw_validators = next_account_info(accounts)?;
+
+    let validator_set = load_validator_set(raw_validators, &config, program_id)?;
+
+    let sysvar_instructions = next_account_info(accounts)?;
+
+    let raw_root = next_account_info(accounts)?;
+
+    let seeds: &[&[u8]] = &[
+        ROOT_SEED,
+        &data.source_chain_id.to_le_bytes(),
+        &data.batch.to_le_bytes(),
+    ];
+
+    check_seeds(raw_root, seeds, program_id)?;
+
+    if !raw_root.data_is_empty() {
+        return Err(MessengerError::RootAlreadyPosted.into());
+    }
+
+    let system_program = next_account_info(accounts)?;
+
+    check_keys_eq(system_program.key, &ID)?;
+
+    let hashed = RootDigest {
+        source_chain_id: data.source_chain_id,
+        dest_chain_id: config.chain_id(),
+        batch: data.batch,
+        root: data.root,
+        gateway: *program_id,
+    }
+    .hash(config.digest_scheme());
+
+    //a root vouches for every message of the batch, so it has to be signed by the validators
+    verify_validator_signature(
+        &config,
+        validator_set.as_ref(),
+        sysvar_instructions,
+        &hashed,
+        &data.signature,
+    )?;
+
+    let message_root = MessageRoot {
+        version: MessageRoot::VERSION,
+        source_chain_id: data.source_chain_id,
+        batch: data.batch,
+        root: data.root,
+        posted_at: Clock::get()?.unix_timestamp,
+    };
+
+    create_state_account(
+        payer,
+        raw_root,
+        system_program,
+        program_id,
+        seeds,
+        &message_root,
+    )?;
+
+    GatewayEvent::MessageRootPosted {
+        source_chain_id: data.source_chain_id,
+        batch: data.batch,
+        root: data.root,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -1,35 +1,49 @@
 use crate::{
+    bls::BlsProof,
     constants::{
-        CONFIG_SEED, MESSAGE_CLIENT_SEED, MESSAGE_CLIENT_TREASURY_SEED, MESSAGE_SEED,
-        SOLANA_CHAIN_ID, TX_FEE,
+        CHAIN_RATE_LIMIT_SEED, CHANNEL_SEED, CLIENT_RATE_LIMIT_SEED, COMMITMENT_SEED, CONFIG_SEED,
+        MESSAGE_CLIENT_SEED, MESSAGE_CLIENT_TREASURY_SEED, MESSAGE_SEED, ROOT_SEED, TX_FEE,
     },
+    ed25519,
     error::MessengerError,
-    instruction::{MessageDigest, ReceiveMessage},
+    events::GatewayEvent,
+    instruction::{BatchProof, MessageDigest, ReceiveMessage},
+    merkle,
     state::{
-        config::{MessageClient, MessengerConfig},
+        account::{
+            load_account, load_account_mut, load_chain, load_message_client, store_account,
+            GatewayAccount, DISCRIMINATOR_LEN,
+        },
+        address::TypedAddress,
+        batch::MessageRoot,
+        channel::{payload_sequence, ChannelState},
+        config::{ForeignAddress, MessengerConfig},
         message::MessagePayload,
+        optimistic::MessageCommitment,
     },
     utils::{
         assert_account_signer, check_client_seeds, check_client_treasury_seeds,
-        check_global_treasury_seeds, check_seeds, create_ecdsa_sig, initialize_account,
-        pubkey_to_address, public_key_to_address,
+        check_global_treasury_seeds, check_seeds, consume_rate_limit, create_state_account,
+        get_next_tx_id, initialize_account, load_validator_set, verify_evm_signature,
     },
 };
 
-use borsh::BorshSerialize;
+use borsh::BorshDeserialize;
 use message_hook::{get_extra_account_metas_address, onchain::invoke_execute};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    borsh0_10::try_from_slice_unchecked,
+    clock::Clock,
     entrypoint::ProgramResult,
     msg,
     program::invoke_signed,
     pubkey::Pubkey,
     system_instruction,
+    sysvar::Sysvar,
 };
 
 pub fn process_receive_message(
     receive_message: ReceiveMessage,
+    batch_proof: Option<BatchProof>,
     program_id: &Pubkey,
     accounts: &[AccountInfo],
 ) -> ProgramResult {
@@ -41,35 +55,90 @@ pub fn process_receive_message(
 
     let raw_config = next_account_info(accounts_iter)?;
 
-    check_seeds(raw_config, &[CONFIG_SEED], program_id)?;
-
     let message_client = next_account_info(accounts_iter)?;
 
-    check_client_seeds(receive_message.receiver, *message_client.key)?;
+    check_client_seeds(receive_message.receiver, *message_client.key, program_id)?;
 
-    let decoded_client = try_from_slice_unchecked::<MessageClient>(&message_client.data.borrow())?;
+    let decoded_client = load_message_client(message_client, program_id)?;
 
     if decoded_client.destination_contract != receive_message.receiver {
         return Err(MessengerError::InvalidClientProgramId.into());
     }
 
-    let mut config: MessengerConfig = try_from_slice_unchecked(&raw_config.data.borrow())?;
+    let mut config = load_account_mut::<MessengerConfig>(raw_config, program_id, &[CONFIG_SEED])?;
 
     let message_data = next_account_info(accounts_iter)?;
 
     let client_treasury = next_account_info(accounts_iter)?;
 
-    let treasury_bump =
-        check_client_treasury_seeds(decoded_client.destination_contract, *client_treasury.key)?;
+    let treasury_bump = check_client_treasury_seeds(
+        decoded_client.destination_contract,
+        *client_treasury.key,
+        program_id,
+    )?;
 
     let global_treasury = next_account_info(accounts_iter)?;
 
-    check_global_treasury_seeds(*global_treasury.key)?;
+    check_global_treasury_seeds(*global_treasury.key, program_id)?;
 
     let system_program = next_account_info(accounts_iter)?;
 
     let sysvar_instructions = next_account_info(accounts_iter)?;
 
+    let chain_rate_limit = next_account_info(accounts_iter)?;
+
+    let client_rate_limit = next_account_info(accounts_iter)?;
+
+    let raw_source_chain = next_account_info(accounts_iter)?;
+
+    let source_chain = load_chain(
+        raw_source_chain,
+        program_id,
+        receive_message.source_chain_id,
+    )?;
+
+    let raw_validators = next_account_info(accounts_iter)?;
+
+    let validator_set = load_validator_set(raw_validators, &config, program_id)?;
+
+    let raw_commitment = next_account_info(accounts_iter)?;
+
+    let channel = next_account_info(accounts_iter)?;
+
+    //messages of a batch are proven against the root the validators signed for the batch
+    let message_root = match &batch_proof {
+        Some(batch_proof) => Some(load_account::<MessageRoot>(
+            next_account_info(accounts_iter)?,
+            program_id,
+            &[
+                ROOT_SEED,
+                &receive_message.source_chain_id.to_le_bytes(),
+                &batch_proof.batch.to_le_bytes(),
+            ],
+        )?),
+        None => None,
+    };
+
+    //messages to optimistic clients are executed on the commitment of a bonded operator instead
+    //of validator signatures
+    let commitment = if decoded_client.optimistic && message_root.is_none() {
+        Some(load_account::<MessageCommitment>(
+            raw_commitment,
+            program_id,
+            &[COMMITMENT_SEED, &receive_message.tx_id.to_le_bytes()],
+        )?)
+    } else {
+        None
+    };
+
+    let signed_per_message = commitment.is_none() && message_root.is_none();
+
+    let validator_set = validator_set.filter(|_| signed_per_message);
+
+    let validators = validator_set.as_ref().filter(|_| config.bls_enabled());
+
+    let chainsig = config.chainsig().filter(|_| signed_per_message);
+
     let transfer_ix =
         system_instruction::transfer(client_treasury.key, global_treasury.key, TX_FEE);
 
@@ -88,125 +157,240 @@ pub fn process_receive_message(
         ]],
     )?;
 
+    GatewayEvent::FeeCollected {
+        destination_contract: decoded_client.destination_contract,
+        amount: TX_FEE,
+    }
+    .emit();
+
     let bump = check_seeds(
         message_data,
         &[MESSAGE_SEED, &receive_message.tx_id.to_le_bytes()],
         program_id,
     )?;
 
-    let data_position = if config.chainsig.is_some() { 1 } else { 0 };
+    let now = Clock::get()?.unix_timestamp;
 
-    let mut encoded_recipient: Vec<u8> = [0; 12].to_vec();
+    //data holds the exsig signature, the BLS proof or chainsig signature and the payload, in that
+    //order. Ed25519 signatures are carried by the preceding instruction, messages of a batch and
+    //messages to optimistic clients carry no validator signature.
+    let validator_signed = validators.is_some() || chainsig.is_some();
 
-    encoded_recipient
-        .extend_from_slice(&hex::decode("0000000000000000000000000000000000000001").unwrap());
+    let message_digest = |exsig: &Option<ForeignAddress>| {
+        let data_position = usize::from(exsig.is_some()) + usize::from(validator_signed);
 
-    let base_message_payload = MessageDigest {
-        data: receive_message.data.get(data_position).unwrap().clone(),
-        tx_id: receive_message.tx_id,
-        sender: receive_message.sender,
-        recipient: encoded_recipient.try_into().unwrap(),
-        dest_chain_id: receive_message.dest_chain_id,
-        source_chain_id: receive_message.source_chain_id,
+        receive_message
+            .data
+            .get(data_position)
+            .map(|payload| {
+                MessageDigest::new(
+                    receive_message.tx_id,
+                    receive_message.source_chain_id,
+                    receive_message.dest_chain_id,
+                    receive_message.sender,
+                    receive_message.receiver,
+                    *program_id,
+                    payload,
+                )
+            })
+            .ok_or(MessengerError::MissingMessagePayload)
     };
 
-    let message_payload = base_message_payload.try_to_vec().unwrap();
+    let exsigs = decoded_client.accepted_exsigs(now);
+
+    //within the grace period of a rotation the message may be signed with the previous exsig, or
+    //carry no exsig signature when the client had none
+    let exsig = match exsigs.as_slice() {
+        [exsig] => *exsig,
+        _ => {
+            let signed_with = |exsig: &ForeignAddress| {
+                let (Ok(digest), Some(signature)) =
+                    (message_digest(&Some(*exsig)), receive_message.data.first())
+                else {
+                    return false;
+                };
+
+                verify_evm_signature(&digest.hash(config.digest_scheme()), signature, exsig).is_ok()
+            };
+
+            exsigs
+                .iter()
+                .flatten()
+                .find(|exsig| signed_with(exsig))
+                .map(|exsig| Some(*exsig))
+                .or_else(|| exsigs.contains(&None).then_some(None))
+                .unwrap_or(exsigs[0])
+        }
+    };
+
+    let digest = message_digest(&exsig)?;
 
     // role_guard(&config, signer, Role::Operator)?;
 
-    msg!("Bridge enabled: {:?}", config.bridge_enabled);
+    msg!("Bridge enabled: {:?}", config.bridge_enabled());
 
-    if !config.bridge_enabled {
+    if !config.bridge_enabled() {
         return Err(MessengerError::BrigdeNotEnabled.into());
     }
 
+    if config.inbound_paused() {
+        return Err(MessengerError::InboundPaused.into());
+    }
+
+    source_chain.check_inbound()?;
+
+    TypedAddress::from_foreign(source_chain.address_format, &receive_message.sender)?;
+
+    if decoded_client.paused {
+        return Err(MessengerError::ClientPaused.into());
+    }
+
     msg!("Dest chain : {:?}", receive_message.dest_chain_id);
 
-    if u64::from(receive_message.dest_chain_id) != SOLANA_CHAIN_ID {
+    if receive_message.dest_chain_id != config.chain_id() {
         return Err(MessengerError::ChainNotSupported.into());
     }
 
-    let mut data_index = 0;
-
-    // let src_chain_exists = config
-    //     .enabled_chains
-    //     .into_iter()
-    //     .any(|c| u64::from(c) == receive_message.source_chain_id);
+    let hashed = digest.hash(config.digest_scheme());
 
-    // if !src_chain_exists {
-    //     return Err(MessengerError::ChainNotSupported.into());
-    // }
+    let mut data_index = 0;
 
-    if let Some(exsig) = decoded_client.exsig {
+    if let Some(exsig) = exsig {
         let exsig_vrs_bytes = receive_message
             .data
             .get(data_index)
-            .expect("Missing Exsig vrs bytes!");
+            .ok_or(MessengerError::InvalidSignature)?;
 
-        if exsig_vrs_bytes.len() != 72 {
-            return Err(MessengerError::InvalidSignature.into());
-        }
+        verify_evm_signature(&hashed, exsig_vrs_bytes, &exsig)?;
 
-        let recovered_signer = solana_program::secp256k1_recover::secp256k1_recover(
-            &exsig_vrs_bytes[65..],
-            exsig_vrs_bytes[0],
-            &exsig_vrs_bytes[1..65],
-        )
-        .expect("Failed to recover secp256k1 sig");
+        data_index += 1;
+    }
 
-        let signer = recovered_signer.0.try_to_vec().unwrap();
+    if let Some(commitment) = &commitment {
+        commitment.check_executable(&hashed, now)?;
+    }
 
-        if signer.as_slice() != exsig {
-            return Err(MessengerError::InvalidSignature.into());
+    if let Some((message_root, batch_proof)) = message_root.as_ref().zip(batch_proof.as_ref()) {
+        if !merkle::verify(&hashed, &batch_proof.proof, &message_root.root) {
+            return Err(MessengerError::InvalidMerkleProof.into());
         }
-        data_index = data_index + 1;
     }
 
-    if let Some(chainsig) = config.chainsig {
-        let chainsig_vrs_bytes = receive_message
+    if let Some(validators) = validator_set.as_ref().filter(|set| set.ed25519_enabled()) {
+        validators.verify_ed25519(&ed25519::previous_instruction_signers(
+            sysvar_instructions,
+            &hashed,
+        )?)?;
+    }
+
+    if let Some(validators) = validators {
+        let proof = receive_message
             .data
             .get(data_index)
-            .expect("Missing Chainsig vrs bytes!");
-
-        let recovery_id: u8 = chainsig_vrs_bytes[64] - 27;
-
-        let hashed = create_ecdsa_sig(&message_payload);
+            .and_then(|bytes| BlsProof::try_from_slice(bytes).ok())
+            .ok_or(MessengerError::InvalidSignature)?;
 
-        let recovered_chainsig = solana_program::secp256k1_recover::secp256k1_recover(
-            &hashed,
-            recovery_id,
-            &chainsig_vrs_bytes[..64],
-        )
-        .expect("Failed to recover secp256k1 sig");
-
-        let chainsig_address = recovered_chainsig.0;
+        validators.verify(&hashed, &proof)?;
 
-        let address = public_key_to_address(&chainsig_address);
+        data_index += 1;
+    } else if let Some(chainsig) = chainsig {
+        let chainsig_vrs_bytes = receive_message
+            .data
+            .get(data_index)
+            .ok_or(MessengerError::InvalidSignature)?;
 
-        data_index = data_index + 1;
+        verify_evm_signature(&hashed, chainsig_vrs_bytes, &chainsig)?;
 
-        if pubkey_to_address(&chainsig[12..]) != pubkey_to_address(&address) {
-            return Err(MessengerError::InvalidSignature.into());
-        }
+        data_index += 1;
     }
 
     if !message_data.data_is_empty() {
         return Err(MessengerError::MessageAlreadyProcessed.into());
     }
 
-    let message_payload = receive_message.data.get(data_index).unwrap();
+    let message_payload = receive_message
+        .data
+        .get(data_index)
+        .ok_or(MessengerError::MissingMessagePayload)?;
+
+    GatewayEvent::MessageVerified {
+        tx_id: receive_message.tx_id,
+        source_chain_id: receive_message.source_chain_id,
+        sender: receive_message.sender,
+        receiver: receive_message.receiver,
+    }
+    .emit();
+
+    consume_rate_limit(
+        signer,
+        chain_rate_limit,
+        system_program,
+        program_id,
+        &[
+            CHAIN_RATE_LIMIT_SEED,
+            &receive_message.source_chain_id.to_le_bytes(),
+        ],
+        source_chain.rate_limit.as_ref(),
+        message_payload.len() as u64,
+    )?;
+
+    consume_rate_limit(
+        signer,
+        client_rate_limit,
+        system_program,
+        program_id,
+        &[CLIENT_RATE_LIMIT_SEED, receive_message.receiver.as_ref()],
+        decoded_client.rate_limit.as_ref(),
+        message_payload.len() as u64,
+    )?;
+
+    //ordered clients execute the messages of each source chain and sender in sequence, a failed
+    //execution reverts the advance so the message stays the next one
+    if decoded_client.ordered {
+        let channel_seeds: &[&[u8]] = &[
+            CHANNEL_SEED,
+            &receive_message.source_chain_id.to_le_bytes(),
+            &receive_message.sender,
+            receive_message.receiver.as_ref(),
+        ];
+
+        let mut channel_state = if channel.data_is_empty() {
+            ChannelState::new(
+                receive_message.source_chain_id,
+                receive_message.sender,
+                receive_message.receiver,
+            )
+        } else {
+            load_account::<ChannelState>(channel, program_id, channel_seeds)?
+        };
+
+        channel_state.advance(payload_sequence(message_payload)?)?;
+
+        if channel.data_is_empty() {
+            create_state_account(
+                signer,
+                channel,
+                system_program,
+                program_id,
+                channel_seeds,
+                &channel_state,
+            )?;
+        } else {
+            store_account(channel, &channel_state)?;
+        }
+    }
 
     let decoded_message = MessagePayload::unpack(
         receive_message.tx_id,
         receive_message.sender,
         message_payload,
-    );
+    )?;
 
     initialize_account(
         signer,
         message_data,
         system_program,
-        MessagePayload::LEN,
+        DISCRIMINATOR_LEN as u64 + MessagePayload::LEN,
         program_id,
         &[MESSAGE_SEED, &receive_message.tx_id.to_le_bytes(), &[bump]],
     )?;
@@ -220,24 +404,43 @@ pub fn process_receive_message(
         return Err(MessengerError::MissingValidationAccountInfo.into());
     }
 
-    config.next_tx_id = config.next_tx_id.checked_add(1).unwrap();
+    let next_tx_id = get_next_tx_id(&config)?;
 
-    raw_config
-        .data
-        .borrow_mut()
-        .copy_from_slice(&config.try_to_vec().unwrap());
+    config.set_next_tx_id(next_tx_id);
+    config.record_received();
+
+    //the receiver may list the config among its extra accounts
+    drop(config);
 
     message_data
         .data
         .borrow_mut()
-        .copy_from_slice(&decoded_message.try_to_vec().unwrap());
+        .copy_from_slice(&decoded_message.pack()?);
 
-    invoke_execute(
+    let execution = invoke_execute(
         &receive_message.receiver,
         message_client,
         sysvar_instructions,
         accounts_iter.as_slice(),
         Vec::from(&message_payload[..]),
-    )?;
+    );
+
+    if let Err(error) = execution {
+        GatewayEvent::MessageFailed {
+            tx_id: receive_message.tx_id,
+            receiver: receive_message.receiver,
+            error: error.to_string(),
+        }
+        .emit();
+
+        return Err(error);
+    }
+
+    GatewayEvent::MessageExecuted {
+        tx_id: receive_message.tx_id,
+        receiver: receive_message.receiver,
+    }
+    .emit();
+
     Ok(())
 }
@@ -0,0 +1,87 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    system_program::ID,
+};
+
+use crate::{
+    constants::{CHAIN_SEED, CONFIG_SEED},
+    error::MessengerError,
+    events::GatewayEvent,
+    instruction::RegisterChain,
+    state::{
+        account::{load_account, load_chain, GatewayAccount},
+        chain::ChainConfig,
+        config::MessengerConfig,
+    },
+    utils::{assert_account_signer, check_keys_eq, create_state_account, write_resized},
+};
+
+pub fn process_register_chain(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: RegisterChain,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let authority = next_account_info(accounts)?;
+
+    assert_account_signer(authority)?;
+
+    let raw_config = next_account_info(accounts)?;
+
+    let config: MessengerConfig = load_account(raw_config, program_id, &[CONFIG_SEED])?;
+
+    if *authority.key != config.owner {
+        return Err(MessengerError::CallerNotOwner.into());
+    }
+
+    let system_program = next_account_info(accounts)?;
+
+    check_keys_eq(system_program.key, &ID)?;
+
+    let raw_chain = next_account_info(accounts)?;
+
+    ChainConfig::check_name(&data.name)?;
+
+    let registered = raw_chain.data_is_empty();
+
+    let mut chain = if registered {
+        ChainConfig::new(data.chain_id, &data.name, data.address_format)
+    } else {
+        load_chain(raw_chain, program_id, data.chain_id)?
+    };
+
+    chain.name = data.name;
+    chain.address_format = data.address_format;
+    chain.min_confirmations = data.min_confirmations;
+    chain.fees = data.fees;
+    chain.enabled = data.enabled;
+
+    if registered {
+        create_state_account(
+            authority,
+            raw_chain,
+            system_program,
+            program_id,
+            &[CHAIN_SEED, &data.chain_id.to_le_bytes()],
+            &chain,
+        )?;
+    } else {
+        write_resized(authority, raw_chain, system_program, &chain.pack()?)?;
+    }
+
+    GatewayEvent::ChainRegistered {
+        chain_id: chain.chain_id,
+        name: chain.name,
+        address_format: chain.address_format,
+        min_confirmations: chain.min_confirmations,
+        fees: chain.fees,
+        enabled: chain.enabled,
+        registered,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
-use borsh::BorshSerialize;
+use std::str::FromStr;
+
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    borsh0_10::try_from_slice_unchecked,
     entrypoint::ProgramResult,
     pubkey::Pubkey,
     rent::Rent,
@@ -12,15 +12,27 @@ use solana_program::{
 };
 
 use crate::{
-    constants::{CALLER_INSTRUCTION_DISCRIMINATOR, CALLER_PROGRAM, CONFIG_SEED, MESSAGE_SEED},
+    constants::{
+        ACCUMULATOR_SEED, CALLER_INSTRUCTION_DISCRIMINATOR, CALLER_PROGRAM, CHAIN_RATE_LIMIT_SEED,
+        CLIENT_RATE_LIMIT_SEED, CONFIG_SEED, MESSAGE_SEED, ROLES_SEED,
+    },
     error::MessengerError,
+    events::GatewayEvent,
     instruction::SendMessage,
     state::{
-        config::{MessengerConfig, Role},
+        account::{
+            load_account, load_account_mut, load_chain, load_message_client, store_account,
+            DISCRIMINATOR_LEN,
+        },
+        accumulator::{OutboundAccumulator, OutboundDigest},
+        address::TypedAddress,
+        config::{MessengerConfig, Role, RoleRegistry},
         message::Message,
     },
     utils::{
-        check_keys_eq, check_seeds, get_next_tx_id, initialize_account, role_guard, transfer_sol,
+        check_client_seeds, check_global_treasury_seeds, check_keys_eq, consume_rate_limit,
+        create_state_account, get_next_tx_id, initialize_account, keccak256, role_guard,
+        transfer_sol,
     },
 };
 
@@ -33,8 +45,6 @@ pub fn process_send_message(
 
     let raw_config = next_account_info(accounts)?;
 
-    check_seeds(raw_config, &[CONFIG_SEED], program_id)?;
-
     let sender = next_account_info(accounts)?;
 
     let raw_message = next_account_info(accounts)?;
@@ -45,26 +55,61 @@ pub fn process_send_message(
 
     check_keys_eq(sysvar_instructions.key, &SysvarInstructions)?;
 
+    let chain_rate_limit = next_account_info(accounts)?;
+
+    let sender_client = next_account_info(accounts)?;
+
+    check_client_seeds(*sender.key, *sender_client.key, program_id)?;
+
+    let sender_rate_limit = next_account_info(accounts)?;
+
+    let raw_destination_chain = next_account_info(accounts)?;
+
+    let raw_roles = next_account_info(accounts)?;
+
+    let global_treasury = next_account_info(accounts)?;
+
+    check_global_treasury_seeds(*global_treasury.key, program_id)?;
+
+    let raw_accumulator = next_account_info(accounts)?;
+
     check_keys_eq(system_program.key, &ID)?;
 
+    //senders registered as message clients are paused and rate limited as clients as well
+    let sender_limit = if sender_client.data_is_empty() {
+        None
+    } else {
+        let decoded_client = load_message_client(sender_client, program_id)?;
+
+        if decoded_client.paused {
+            return Err(MessengerError::ClientPaused.into());
+        }
+
+        decoded_client.rate_limit
+    };
+
     let current_ix_index = load_current_index_checked(sysvar_instructions)?;
 
+    let previous_ix_index = current_ix_index
+        .checked_sub(1)
+        .ok_or(MessengerError::InvalidInstructionIndex)?;
+
     let previous_ix =
-        load_instruction_at_checked(usize::from(current_ix_index - 1), sysvar_instructions)
-            .map_err(|_| MessengerError::InvalidInstructionIndex)
-            .unwrap();
+        load_instruction_at_checked(usize::from(previous_ix_index), sysvar_instructions)
+            .map_err(|_| MessengerError::InvalidInstructionIndex)?;
 
-    if previous_ix.program_id != CALLER_PROGRAM.parse().unwrap() {
+    let caller_program =
+        Pubkey::from_str(CALLER_PROGRAM).map_err(|_| MessengerError::CallerProgramNotConfigured)?;
+
+    if previous_ix.program_id != caller_program {
         return Err(MessengerError::InvalidPreInstruction.into());
     }
 
-    let previous_ix_data = &previous_ix.data[0];
-
-    if previous_ix_data != &CALLER_INSTRUCTION_DISCRIMINATOR {
+    if previous_ix.data.first() != Some(&CALLER_INSTRUCTION_DISCRIMINATOR) {
         return Err(MessengerError::InvalidPreInstruction.into());
     }
 
-    let total_len = Message::LEN + data.data.len();
+    let total_len = DISCRIMINATOR_LEN + Message::LEN + data.data.len();
     if raw_message.data_is_empty() {
         let lamports = Rent::default().minimum_balance(total_len);
 
@@ -78,38 +123,126 @@ pub fn process_send_message(
             &[MESSAGE_SEED],
         )?;
     } else {
-        let additional_length = raw_config.data_len().checked_sub(total_len).unwrap_or(0);
+        let additional_length = total_len.saturating_sub(raw_message.data_len());
 
         if additional_length > 0 {
             let realloc_fee = Rent::default().minimum_balance(additional_length);
 
             transfer_sol(sender, raw_message, realloc_fee, system_program, None)?;
 
-            raw_message.realloc(total_len.checked_add(additional_length).unwrap(), false)?;
+            raw_message.realloc(total_len, false)?;
         }
     }
 
-    let mut config: MessengerConfig = try_from_slice_unchecked(&raw_config.data.borrow())?;
+    let mut config = load_account_mut::<MessengerConfig>(raw_config, program_id, &[CONFIG_SEED])?;
 
-    if !config.bridge_enabled {
+    if !config.bridge_enabled() {
         return Err(MessengerError::BrigdeNotEnabled.into());
     }
 
-    let chain_exist = config.enabled_chains.iter().any(|c| *c == data.chain);
+    if config.outbound_paused() {
+        return Err(MessengerError::OutboundPaused.into());
+    }
+
+    let destination_chain = load_chain(raw_destination_chain, program_id, data.chain)?;
+
+    destination_chain.check_outbound(data.confirmations)?;
+
+    TypedAddress::from_foreign(destination_chain.address_format, &data.recipient)?;
+
+    if config.whitelist_only() {
+        let roles: RoleRegistry = load_account(raw_roles, program_id, &[ROLES_SEED])?;
+
+        role_guard(&roles, sender, Role::Whitelist)?;
+    }
+
+    let message_len = data.data.len() as u64;
+
+    consume_rate_limit(
+        sender,
+        chain_rate_limit,
+        system_program,
+        program_id,
+        &[CHAIN_RATE_LIMIT_SEED, &data.chain.to_le_bytes()],
+        destination_chain.rate_limit.as_ref(),
+        message_len,
+    )?;
 
-    if !chain_exist {
-        return Err(MessengerError::ChainNotSupported.into());
+    consume_rate_limit(
+        sender,
+        sender_rate_limit,
+        system_program,
+        program_id,
+        &[CLIENT_RATE_LIMIT_SEED, sender.key.as_ref()],
+        sender_limit.as_ref(),
+        message_len,
+    )?;
+
+    let fee = destination_chain.fees.message_fee(message_len)?;
+
+    if fee > 0 {
+        transfer_sol(sender, global_treasury, fee, system_program, None)?;
     }
 
-    if config.whitelist_only {
-        role_guard(&config, sender, Role::Whitelist)?;
+    let next_tx_id = get_next_tx_id(&config)?;
+
+    config.set_next_tx_id(next_tx_id);
+    config.record_sent();
+
+    //every sent message is provable to the destination against the root of the accumulator
+    let mut accumulator = if raw_accumulator.data_is_empty() {
+        let accumulator = OutboundAccumulator::new();
+
+        create_state_account(
+            sender,
+            raw_accumulator,
+            system_program,
+            program_id,
+            &[ACCUMULATOR_SEED],
+            &accumulator,
+        )?;
+
+        accumulator
+    } else {
+        load_account::<OutboundAccumulator>(raw_accumulator, program_id, &[ACCUMULATOR_SEED])?
+    };
+
+    let leaf = OutboundDigest {
+        tx_id: next_tx_id,
+        source_chain_id: config.chain_id(),
+        dest_chain_id: data.chain,
+        sender: *sender.key,
+        recipient: data.recipient,
+        confirmations: data.confirmations,
+        gateway: *program_id,
+        payload_hash: keccak256(&data.data),
     }
+    .leaf();
 
-    let next_tx_id = get_next_tx_id(&config);
+    let index = accumulator.append(leaf)?;
 
-    config.next_tx_id = next_tx_id;
+    store_account(raw_accumulator, &accumulator)?;
 
-    raw_message.data.borrow_mut().copy_from_slice(
+    GatewayEvent::MessageSent {
+        tx_id: next_tx_id,
+        sender: *sender.key,
+        recipient: data.recipient,
+        chain: data.chain,
+        confirmations: data.confirmations,
+        data_len: message_len,
+    }
+    .emit();
+
+    GatewayEvent::MessageAccumulated {
+        tx_id: next_tx_id,
+        index,
+        leaf,
+        root: accumulator.root(),
+    }
+    .emit();
+
+    store_account(
+        raw_message,
         &Message {
             chain: data.chain,
             confirmations: data.confirmations,
@@ -117,14 +250,8 @@ pub fn process_send_message(
             recipient: data.recipient,
             sender: *sender.key,
             tx_id: next_tx_id,
-        }
-        .try_to_vec()
-        .unwrap(),
-    );
-    raw_config
-        .data
-        .borrow_mut()
-        .serialize(&mut config.try_to_vec().unwrap())?;
+        },
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,89 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    system_program::ID,
+};
+
+use crate::{
+    constants::{CONFIG_SEED, VALIDATORS_SEED},
+    error::MessengerError,
+    events::GatewayEvent,
+    instruction::SetBlsValidators,
+    state::{
+        account::{load_account, load_account_mut, GatewayAccount},
+        config::MessengerConfig,
+        validators::ValidatorSet,
+    },
+    utils::{assert_account_signer, check_keys_eq, create_state_account, write_resized},
+};
+
+pub fn process_set_bls_validators(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: SetBlsValidators,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let authority = next_account_info(accounts)?;
+
+    assert_account_signer(authority)?;
+
+    let raw_config = next_account_info(accounts)?;
+
+    let mut config = load_account_mut::<MessengerConfig>(raw_config, program_id, &[CONFIG_SEED])?;
+
+    if *authority.key != config.owner {
+        return Err(MessengerError::CallerNotOwner.into());
+    }
+
+    let system_program = next_account_info(accounts)?;
+
+    check_keys_eq(system_program.key, &ID)?;
+
+    let raw_validators = next_account_info(accounts)?;
+
+    let created = raw_validators.data_is_empty();
+
+    let mut validators = if created {
+        ValidatorSet::new()
+    } else {
+        load_account(raw_validators, program_id, &[VALIDATORS_SEED])?
+    };
+
+    validators.write_keys(usize::from(data.offset), &data.keys)?;
+    validators.threshold = data.threshold;
+
+    if data.enabled {
+        validators.check()?;
+    }
+
+    if created {
+        create_state_account(
+            authority,
+            raw_validators,
+            system_program,
+            program_id,
+            &[VALIDATORS_SEED],
+            &validators,
+        )?;
+    } else {
+        write_resized(
+            authority,
+            raw_validators,
+            system_program,
+            &validators.pack()?,
+        )?;
+    }
+
+    config.set_bls_enabled(data.enabled);
+
+    GatewayEvent::BlsValidatorsSet {
+        validators: validators.bls_keys.len() as u16,
+        threshold: validators.threshold,
+        enabled: data.enabled,
+    }
+    .emit();
+
+    Ok(())
+}
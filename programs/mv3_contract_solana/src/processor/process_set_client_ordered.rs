@@ -0,0 +1,47 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::MessengerError,
+    events::GatewayEvent,
+    state::account::{load_message_client, store_account},
+    utils::{assert_account_signer, check_client_seeds},
+};
+
+pub fn process_set_client_ordered(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    destination_contract: Pubkey,
+    enabled: bool,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let authority = next_account_info(accounts)?;
+
+    assert_account_signer(authority)?;
+
+    let message_client = next_account_info(accounts)?;
+
+    check_client_seeds(destination_contract, *message_client.key, program_id)?;
+
+    let mut decoded_client = load_message_client(message_client, program_id)?;
+
+    if decoded_client.authority != *authority.key {
+        return Err(MessengerError::InvalidUpdateAuthority.into());
+    }
+
+    decoded_client.ordered = enabled;
+
+    store_account(message_client, &decoded_client)?;
+
+    GatewayEvent::ClientOrderedSet {
+        destination_contract,
+        enabled,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    system_program::ID,
+};
+
+use crate::{
+    constants::{CONFIG_SEED, VALIDATORS_SEED},
+    error::MessengerError,
+    events::GatewayEvent,
+    instruction::SetEd25519Validators,
+    state::{
+        account::{load_account, GatewayAccount},
+        config::MessengerConfig,
+        validators::ValidatorSet,
+    },
+    utils::{assert_account_signer, check_keys_eq, create_state_account, write_resized},
+};
+
+pub fn process_set_ed25519_validators(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: SetEd25519Validators,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let authority = next_account_info(accounts)?;
+
+    assert_account_signer(authority)?;
+
+    let raw_config = next_account_info(accounts)?;
+
+    let config = load_account::<MessengerConfig>(raw_config, program_id, &[CONFIG_SEED])?;
+
+    if *authority.key != config.owner {
+        return Err(MessengerError::CallerNotOwner.into());
+    }
+
+    let system_program = next_account_info(accounts)?;
+
+    check_keys_eq(system_program.key, &ID)?;
+
+    let raw_validators = next_account_info(accounts)?;
+
+    let created = raw_validators.data_is_empty();
+
+    let mut validators = if created {
+        ValidatorSet::new()
+    } else {
+        load_account(raw_validators, program_id, &[VALIDATORS_SEED])?
+    };
+
+    validators.set_ed25519_keys(data.keys, data.threshold)?;
+
+    if created {
+        create_state_account(
+            authority,
+            raw_validators,
+            system_program,
+            program_id,
+            &[VALIDATORS_SEED],
+            &validators,
+        )?;
+    } else {
+        write_resized(
+            authority,
+            raw_validators,
+            system_program,
+            &validators.pack()?,
+        )?;
+    }
+
+    GatewayEvent::Ed25519ValidatorsSet {
+        validators: validators.ed25519_keys.len() as u16,
+        threshold: validators.ed25519_threshold,
+    }
+    .emit();
+
+    Ok(())
+}
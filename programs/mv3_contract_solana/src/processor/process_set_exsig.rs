@@ -1,18 +1,27 @@
 use std::ops::Sub;
 
-use borsh::BorshSerialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    borsh0_10::try_from_slice_unchecked,
+    clock::Clock,
     entrypoint::ProgramResult,
     pubkey::Pubkey,
     rent::Rent,
+    sysvar::Sysvar,
 };
 
-use crate::{instruction::SetExsig, state::config::MessageClient, utils::transfer_sol};
+use crate::{
+    error::MessengerError,
+    events::GatewayEvent,
+    instruction::SetExsig,
+    state::{
+        account::{load_message_client, GatewayAccount},
+        config::PendingExsig,
+    },
+    utils::{assert_account_signer, transfer_sol},
+};
 
 pub fn process_set_exsig(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     data: SetExsig,
 ) -> ProgramResult {
@@ -22,16 +31,42 @@ pub fn process_set_exsig(
     let message_client = next_account_info(accounts)?;
     let system_program = next_account_info(accounts)?;
 
-    let mut decoded_client =
-        try_from_slice_unchecked::<MessageClient>(&message_client.data.borrow())?;
+    assert_account_signer(authority)?;
 
-    decoded_client.exsig = Some(data.exsig);
+    let mut decoded_client = load_message_client(message_client, program_id)?;
 
     if decoded_client.authority != *authority.key {
-        return ProgramResult::Err(solana_program::program_error::ProgramError::IllegalOwner);
+        return Err(MessengerError::CallerNotOwner.into());
+    }
+
+    if data.activation_delay < 0 {
+        return Err(MessengerError::InvalidInstruction.into());
     }
 
-    let serialized_data = decoded_client.try_to_vec().unwrap();
+    let now = Clock::get()?.unix_timestamp;
+
+    let activates_at = now
+        .checked_add(data.activation_delay)
+        .ok_or(MessengerError::ArithmeticOverflow)?;
+
+    decoded_client.apply_pending_exsig(now);
+
+    //replacing the exsig right away stays possible, e.g. when it was compromised
+    if data.activation_delay > 0 && decoded_client.in_exsig_grace_period(now) {
+        return Err(MessengerError::ExsigRotationInProgress.into());
+    }
+
+    if data.activation_delay == 0 {
+        decoded_client.exsig = data.exsig;
+        decoded_client.pending_exsig = None;
+    } else {
+        decoded_client.pending_exsig = Some(PendingExsig {
+            exsig: data.exsig,
+            activates_at,
+        });
+    }
+
+    let serialized_data = decoded_client.pack()?;
 
     if serialized_data.len() > message_client.data_len() {
         let data_diff = serialized_data.len().sub(message_client.data_len());
@@ -43,5 +78,17 @@ pub fn process_set_exsig(
 
     message_client.realloc(serialized_data.len(), false)?;
 
+    message_client
+        .data
+        .borrow_mut()
+        .copy_from_slice(&serialized_data);
+
+    GatewayEvent::ExsigChanged {
+        destination_contract: decoded_client.destination_contract,
+        exsig: data.exsig,
+        activates_at,
+    }
+    .emit();
+
     Ok(())
 }
@@ -0,0 +1,84 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    system_program::ID,
+};
+
+use crate::{
+    constants::{CONFIG_SEED, OPTIMISTIC_SEED},
+    error::MessengerError,
+    events::GatewayEvent,
+    instruction::SetOptimisticConfig,
+    state::{
+        account::{load_account, GatewayAccount},
+        config::MessengerConfig,
+        optimistic::OptimisticConfig,
+    },
+    utils::{
+        assert_account_signer, check_keys_eq, check_seeds, create_state_account, write_resized,
+    },
+};
+
+pub fn process_set_optimistic_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: SetOptimisticConfig,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let authority = next_account_info(accounts)?;
+
+    assert_account_signer(authority)?;
+
+    let raw_config = next_account_info(accounts)?;
+
+    let config = load_account::<MessengerConfig>(raw_config, program_id, &[CONFIG_SEED])?;
+
+    if *authority.key != config.owner {
+        return Err(MessengerError::CallerNotOwner.into());
+    }
+
+    let system_program = next_account_info(accounts)?;
+
+    check_keys_eq(system_program.key, &ID)?;
+
+    let raw_optimistic_config = next_account_info(accounts)?;
+
+    check_seeds(raw_optimistic_config, &[OPTIMISTIC_SEED], program_id)?;
+
+    let optimistic_config =
+        OptimisticConfig::new(data.challenge_period, data.min_bond, data.watchers);
+
+    optimistic_config.check()?;
+
+    if raw_optimistic_config.data_is_empty() {
+        create_state_account(
+            authority,
+            raw_optimistic_config,
+            system_program,
+            program_id,
+            &[OPTIMISTIC_SEED],
+            &optimistic_config,
+        )?;
+    } else {
+        //the existing account has to be an optimistic config
+        load_account::<OptimisticConfig>(raw_optimistic_config, program_id, &[OPTIMISTIC_SEED])?;
+
+        write_resized(
+            authority,
+            raw_optimistic_config,
+            system_program,
+            &optimistic_config.pack()?,
+        )?;
+    }
+
+    GatewayEvent::OptimisticConfigSet {
+        challenge_period: optimistic_config.challenge_period,
+        min_bond: optimistic_config.min_bond,
+        watchers: optimistic_config.watchers.len() as u16,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program::ID,
+};
+
+use crate::{
+    constants::{CONFIG_SEED, ROLES_SEED},
+    error::MessengerError,
+    events::GatewayEvent,
+    instruction::{PauseTarget, SetPause},
+    state::{
+        account::{load_account, load_chain, load_message_client, store_account},
+        config::{MessengerConfig, Role, RoleRegistry},
+    },
+    utils::{assert_account_signer, check_client_seeds, check_keys_eq, role_guard},
+};
+
+pub fn process_set_pause(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    data: SetPause,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let authority = next_account_info(accounts)?;
+
+    assert_account_signer(authority)?;
+
+    let raw_config = next_account_info(accounts)?;
+
+    let system_program = next_account_info(accounts)?;
+
+    check_keys_eq(system_program.key, &ID)?;
+
+    let mut config: MessengerConfig = load_account(raw_config, program_id, &[CONFIG_SEED])?;
+
+    let raw_roles = next_account_info(accounts)?;
+
+    //the client is loaded ahead of the guard, its authority can drain it before closing it
+    let client = match &data.target {
+        PauseTarget::Client(destination_contract) => {
+            let message_client = next_account_info(accounts)?;
+
+            check_client_seeds(*destination_contract, *message_client.key, program_id)?;
+
+            let decoded_client = load_message_client(message_client, program_id)?;
+
+            Some((message_client, decoded_client))
+        }
+        _ => None,
+    };
+
+    let client_authority = client
+        .as_ref()
+        .is_some_and(|(_, decoded_client)| decoded_client.authority == *authority.key);
+
+    //supers and client authorities can only pull the emergency brake, lifting a pause is
+    //reserved for the owner
+    if *authority.key != config.owner {
+        if !data.paused {
+            return Err(MessengerError::CallerNotOwner.into());
+        }
+
+        if !client_authority {
+            let roles: RoleRegistry = load_account(raw_roles, program_id, &[ROLES_SEED])?;
+
+            role_guard(&roles, authority, Role::Super)?;
+        }
+    }
+
+    GatewayEvent::PauseChanged {
+        authority: *authority.key,
+        target: data.target.clone(),
+        paused: data.paused,
+    }
+    .emit();
+
+    match data.target {
+        PauseTarget::Inbound => {
+            config.set_inbound_paused(data.paused);
+
+            store_account(raw_config, &config)
+        }
+        PauseTarget::Outbound => {
+            config.set_outbound_paused(data.paused);
+
+            store_account(raw_config, &config)
+        }
+        PauseTarget::SourceChain(chain_id) => {
+            let raw_chain = next_account_info(accounts)?;
+
+            let mut chain = load_chain(raw_chain, program_id, chain_id)?;
+
+            chain.inbound_paused = data.paused;
+
+            store_account(raw_chain, &chain)
+        }
+        PauseTarget::DestinationChain(chain_id) => {
+            let raw_chain = next_account_info(accounts)?;
+
+            let mut chain = load_chain(raw_chain, program_id, chain_id)?;
+
+            chain.outbound_paused = data.paused;
+
+            store_account(raw_chain, &chain)
+        }
+        PauseTarget::Client(_) => {
+            let (message_client, mut decoded_client) =
+                client.ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+            decoded_client.paused = data.paused;
+
+            store_account(message_client, &decoded_client)
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    system_program::ID,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    constants::CONFIG_SEED,
+    error::MessengerError,
+    events::GatewayEvent,
+    instruction::{RateLimitTarget, SetRateLimit},
+    state::{
+        account::{
+            load_account, load_chain, load_message_client, load_owned_account, store_account,
+            GatewayAccount,
+        },
+        config::MessengerConfig,
+        rate_limit::RateLimitState,
+    },
+    utils::{assert_account_signer, check_client_seeds, check_keys_eq, check_seeds, write_resized},
+};
+
+pub fn process_set_rate_limit(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    data: SetRateLimit,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let authority = next_account_info(accounts)?;
+
+    assert_account_signer(authority)?;
+
+    let raw_config = next_account_info(accounts)?;
+
+    let system_program = next_account_info(accounts)?;
+
+    check_keys_eq(system_program.key, &ID)?;
+
+    let config: MessengerConfig = load_account(raw_config, program_id, &[CONFIG_SEED])?;
+
+    if *authority.key != config.owner {
+        return Err(MessengerError::CallerNotOwner.into());
+    }
+
+    let (account, serialized_data, previous_limit) = match data.target {
+        RateLimitTarget::Chain(chain_id) => {
+            let raw_chain = next_account_info(accounts)?;
+
+            let mut chain = load_chain(raw_chain, program_id, chain_id)?;
+
+            let previous_limit = std::mem::replace(&mut chain.rate_limit, data.limit);
+
+            (raw_chain, chain.pack()?, previous_limit)
+        }
+        RateLimitTarget::Client(destination_contract) => {
+            let message_client = next_account_info(accounts)?;
+
+            check_client_seeds(destination_contract, *message_client.key, program_id)?;
+
+            let mut decoded_client = load_message_client(message_client, program_id)?;
+
+            let previous_limit = std::mem::replace(&mut decoded_client.rate_limit, data.limit);
+
+            (message_client, decoded_client.pack()?, previous_limit)
+        }
+    };
+
+    write_resized(authority, account, system_program, &serialized_data)?;
+
+    let raw_bucket = next_account_info(accounts)?;
+
+    let (bucket_seed, target_seed) = data.target.bucket_seeds();
+
+    check_seeds(raw_bucket, &[bucket_seed, &target_seed], program_id)?;
+
+    //levels of an existing bucket are kept in units of the old window, a missing one is created
+    //on first use
+    if !raw_bucket.data_is_empty() {
+        if let Some(limit) = &data.limit {
+            let now = Clock::get()?.unix_timestamp;
+
+            let bucket = match &previous_limit {
+                Some(previous_limit) => {
+                    let mut bucket: RateLimitState = load_owned_account(raw_bucket, program_id)?;
+
+                    bucket.rescale(previous_limit, limit, now);
+
+                    bucket
+                }
+                //the bucket was left alone while unlimited
+                None => RateLimitState::new(limit, now),
+            };
+
+            store_account(raw_bucket, &bucket)?;
+        }
+    }
+
+    GatewayEvent::RateLimitChanged {
+        target: data.target,
+        limit: data.limit,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    constants::{BOND_SEED, COMMITMENT_SEED, CONFIG_SEED},
+    error::MessengerError,
+    events::GatewayEvent,
+    state::{
+        account::{load_account, store_account},
+        config::MessengerConfig,
+        optimistic::{MessageCommitment, OperatorBond},
+    },
+    utils::{
+        check_global_treasury_seeds, debit_lamports, load_validator_set, verify_validator_signature,
+    },
+};
+
+pub fn process_settle_dispute(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    tx_id: u128,
+    signature: Option<Vec<u8>>,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let raw_commitment = next_account_info(accounts)?;
+
+    let mut commitment: MessageCommitment = load_account(
+        raw_commitment,
+        program_id,
+        &[COMMITMENT_SEED, &tx_id.to_le_bytes()],
+    )?;
+
+    if commitment.challenged {
+        return Err(MessengerError::CommitmentChallenged.into());
+    }
+
+    let dispute = commitment
+        .dispute
+        .take()
+        .ok_or(MessengerError::CommitmentNotDisputed)?;
+
+    let raw_bond = next_account_info(accounts)?;
+
+    let mut bond: OperatorBond = load_account(
+        raw_bond,
+        program_id,
+        &[BOND_SEED, commitment.operator.as_ref()],
+    )?;
+
+    let global_treasury = next_account_info(accounts)?;
+
+    check_global_treasury_seeds(*global_treasury.key, program_id)?;
+
+    let raw_config = next_account_info(accounts)?;
+
+    let config = load_account::<MessengerConfig>(raw_config, program_id, &[CONFIG_SEED])?;
+
+    let raw_validators = next_account_info(accounts)?;
+
+    let validator_set = load_validator_set(raw_validators, &config, program_id)?;
+
+    let sysvar_instructions = next_account_info(accounts)?;
+
+    //the validators signing the committed digest disprove the claim of the watcher
+    if let Some(signature) = signature {
+        verify_validator_signature(
+            &config,
+            validator_set.as_ref(),
+            sysvar_instructions,
+            &commitment.hash,
+            &signature,
+        )?;
+
+        store_account(raw_commitment, &commitment)?;
+
+        GatewayEvent::DisputeCleared {
+            tx_id,
+            operator: commitment.operator,
+        }
+        .emit();
+
+        return Ok(());
+    }
+
+    if Clock::get()?.unix_timestamp < dispute.ends_at {
+        return Err(MessengerError::DisputePeriodActive.into());
+    }
+
+    //an unanswered claim stands, the whole bond is slashed as for proven fraud
+    let slashed = bond.amount;
+
+    debit_lamports(raw_bond, global_treasury, slashed)?;
+
+    bond.amount = 0;
+    commitment.challenged = true;
+
+    store_account(raw_bond, &bond)?;
+    store_account(raw_commitment, &commitment)?;
+
+    GatewayEvent::CommitmentChallenged {
+        tx_id,
+        watcher: dispute.watcher,
+        operator: commitment.operator,
+        slashed,
+    }
+    .emit();
+
+    Ok(())
+}
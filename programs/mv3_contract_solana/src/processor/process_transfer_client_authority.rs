@@ -0,0 +1,71 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program::ID,
+};
+
+use crate::{
+    error::MessengerError,
+    events::GatewayEvent,
+    instruction::TransferClientAuthority,
+    state::account::{load_message_client, GatewayAccount},
+    utils::{assert_account_signer, check_client_seeds, check_keys_eq, transfer_sol},
+};
+
+pub fn process_transfer_client_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: TransferClientAuthority,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+
+    let authority = next_account_info(accounts)?;
+
+    assert_account_signer(authority)?;
+
+    let message_client = next_account_info(accounts)?;
+
+    check_client_seeds(data.destination_contract, *message_client.key, program_id)?;
+
+    let system_program = next_account_info(accounts)?;
+
+    check_keys_eq(system_program.key, &ID)?;
+
+    let mut decoded_client = load_message_client(message_client, program_id)?;
+
+    if decoded_client.authority != *authority.key {
+        return Err(MessengerError::InvalidUpdateAuthority.into());
+    }
+
+    //new authority has to accept the transfer, passing None cancels a pending transfer
+    decoded_client.pending_authority = data.new_authority;
+
+    let serialized_client = decoded_client.pack()?;
+
+    let data_diff = serialized_client
+        .len()
+        .saturating_sub(message_client.data_len());
+
+    if data_diff > 0 {
+        let rent = Rent::default().minimum_balance(data_diff);
+
+        transfer_sol(authority, message_client, rent, system_program, None)?;
+    }
+
+    message_client.realloc(serialized_client.len(), false)?;
+
+    message_client
+        .data
+        .borrow_mut()
+        .copy_from_slice(&serialized_client);
+
+    GatewayEvent::ClientAuthorityTransferStarted {
+        destination_contract: data.destination_contract,
+        pending_authority: data.new_authority,
+    }
+    .emit();
+
+    Ok(())
+}
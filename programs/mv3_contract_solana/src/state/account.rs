@@ -0,0 +1,275 @@
+//BorshSchema derives helper structs for enum variants which are never read
+#![allow(dead_code)]
+
+use std::{cell::RefMut, mem::size_of};
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use bytemuck::Pod;
+use solana_program::{
+    account_info::AccountInfo, borsh0_10::try_from_slice_unchecked, entrypoint::ProgramResult,
+    program_error::ProgramError, pubkey::Pubkey,
+};
+
+use crate::{
+    constants::{CHAIN_SEED, MESSAGE_CLIENT_SEED},
+    error::MessengerError,
+    instruction::RateLimitTarget,
+    utils::check_seeds,
+};
+
+use super::{
+    accumulator::OutboundAccumulator,
+    batch::MessageRoot,
+    chain::ChainConfig,
+    channel::ChannelState,
+    config::{MessageClient, MessengerConfig, RoleRegistry},
+    message::{Message, MessagePayload},
+    optimistic::{MessageCommitment, OperatorBond, OptimisticConfig},
+    rate_limit::RateLimitState,
+    validators::ValidatorSet,
+};
+
+pub const DISCRIMINATOR_LEN: usize = 8;
+
+/// State owned by the gateway. Accounts start with a discriminator unique to their type, so an
+/// account of one type is never accepted where another is expected.
+pub trait GatewayAccount: BorshSerialize + BorshDeserialize {
+    /// First 8 bytes of `sha256("account:<type name>")`
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN];
+
+    /// Layout version stored right after the discriminator, `None` for types without one
+    const VERSION: Option<u8> = None;
+
+    /// Discriminator followed by the borsh encoding
+    fn pack(&self) -> Result<Vec<u8>, ProgramError> {
+        let mut data = Self::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(
+            &self
+                .try_to_vec()
+                .map_err(|err| ProgramError::BorshIoError(err.to_string()))?,
+        );
+
+        Ok(data)
+    }
+
+    /// Trailing bytes left over from reallocations are ignored
+    fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        check_layout::<Self>(data)?;
+
+        Ok(try_from_slice_unchecked(&data[DISCRIMINATOR_LEN..])?)
+    }
+}
+
+impl GatewayAccount for MessengerConfig {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [0x2f, 0x17, 0x28, 0x4a, 0xf8, 0xdb, 0xab, 0x73];
+    const VERSION: Option<u8> = Some(MessengerConfig::VERSION);
+}
+
+impl GatewayAccount for MessageClient {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [0x25, 0xcc, 0x2c, 0x26, 0x13, 0xed, 0xe2, 0x90];
+    const VERSION: Option<u8> = Some(MessageClient::VERSION);
+}
+
+impl GatewayAccount for RoleRegistry {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [0xad, 0x81, 0x22, 0xfa, 0xdf, 0x5c, 0x00, 0xe8];
+}
+
+impl GatewayAccount for ChainConfig {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [0x0d, 0xb1, 0xe9, 0x8d, 0xd4, 0x1d, 0x94, 0x38];
+    const VERSION: Option<u8> = Some(ChainConfig::VERSION);
+}
+
+impl GatewayAccount for Message {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [0x6e, 0x97, 0x17, 0x6e, 0xc6, 0x06, 0x7d, 0xb5];
+}
+
+impl GatewayAccount for MessagePayload {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [0x4f, 0xa3, 0x74, 0x68, 0xd6, 0xcb, 0x41, 0x5b];
+}
+
+impl GatewayAccount for RateLimitState {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [0x4b, 0xad, 0x56, 0xcf, 0x34, 0xaa, 0x47, 0x61];
+}
+
+impl GatewayAccount for ValidatorSet {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [0x23, 0xce, 0x61, 0xca, 0x2b, 0x0b, 0x0b, 0x7f];
+    const VERSION: Option<u8> = Some(ValidatorSet::VERSION);
+}
+
+impl GatewayAccount for OptimisticConfig {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [0xe2, 0xac, 0x72, 0xf3, 0xe4, 0xcd, 0xb8, 0xdc];
+    const VERSION: Option<u8> = Some(OptimisticConfig::VERSION);
+}
+
+impl GatewayAccount for OperatorBond {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [0xd1, 0x40, 0xfe, 0x57, 0xe4, 0xf4, 0x5e, 0xda];
+    const VERSION: Option<u8> = Some(OperatorBond::VERSION);
+}
+
+impl GatewayAccount for MessageCommitment {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [0xec, 0x29, 0x35, 0x2d, 0x42, 0x92, 0x89, 0xb7];
+    const VERSION: Option<u8> = Some(MessageCommitment::VERSION);
+}
+
+impl GatewayAccount for MessageRoot {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [0xf4, 0x63, 0x56, 0x3e, 0x23, 0x12, 0xab, 0x2f];
+    const VERSION: Option<u8> = Some(MessageRoot::VERSION);
+}
+
+impl GatewayAccount for OutboundAccumulator {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [0x83, 0x7c, 0xdb, 0x3d, 0x21, 0x57, 0x93, 0x86];
+    const VERSION: Option<u8> = Some(OutboundAccumulator::VERSION);
+}
+
+impl GatewayAccount for ChannelState {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [0x4a, 0x84, 0x8d, 0xc4, 0x40, 0x34, 0x53, 0x88];
+    const VERSION: Option<u8> = Some(ChannelState::VERSION);
+}
+
+/// Account types which can be upgraded by `MigrateAccount`
+#[derive(
+    BorshDeserialize, BorshSerialize, BorshSchema, Debug, Clone, Copy, PartialEq, PartialOrd,
+)]
+pub enum AccountKind {
+    Config,
+    MessageClient,
+    Message,
+    ReceivedMessage,
+    /// Bucket of the chain or client, whose address is derived from it
+    RateLimit(RateLimitTarget),
+}
+
+pub fn has_discriminator<T: GatewayAccount>(data: &[u8]) -> bool {
+    data.len() >= DISCRIMINATOR_LEN && data[..DISCRIMINATOR_LEN] == T::DISCRIMINATOR
+}
+
+/// Checks `data` holds a `T` stored in its current layout
+fn check_layout<T: GatewayAccount>(data: &[u8]) -> ProgramResult {
+    if !has_discriminator::<T>(data) {
+        return Err(MessengerError::InvalidAccountDiscriminator.into());
+    }
+
+    if let Some(version) = T::VERSION {
+        if data.get(DISCRIMINATOR_LEN) != Some(&version) {
+            return Err(MessengerError::UnsupportedAccountVersion.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `data` starts with the discriminator of any gateway account type
+pub fn is_gateway_account(data: &[u8]) -> bool {
+    has_discriminator::<MessengerConfig>(data)
+        || has_discriminator::<MessageClient>(data)
+        || has_discriminator::<RoleRegistry>(data)
+        || has_discriminator::<ChainConfig>(data)
+        || has_discriminator::<Message>(data)
+        || has_discriminator::<MessagePayload>(data)
+        || has_discriminator::<RateLimitState>(data)
+        || has_discriminator::<ValidatorSet>(data)
+        || has_discriminator::<OptimisticConfig>(data)
+        || has_discriminator::<OperatorBond>(data)
+        || has_discriminator::<MessageCommitment>(data)
+        || has_discriminator::<MessageRoot>(data)
+        || has_discriminator::<OutboundAccumulator>(data)
+        || has_discriminator::<ChannelState>(data)
+}
+
+/// Decodes the `T` stored in `account` after checking the account is owned by `program_id`
+pub fn load_owned_account<T: GatewayAccount>(
+    account: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<T, ProgramError> {
+    if account.owner != program_id {
+        return Err(MessengerError::InvalidAccountOwner.into());
+    }
+
+    T::unpack(&account.data.borrow())
+}
+
+/// Decodes the `T` stored in `account` after checking its owner and that its address is derived
+/// from `seeds`
+pub fn load_account<T: GatewayAccount>(
+    account: &AccountInfo,
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+) -> Result<T, ProgramError> {
+    check_seeds(account, seeds, program_id)?;
+
+    load_owned_account(account, program_id)
+}
+
+/// Borrows the fixed-size `T` stored in `account` in place, after the same checks as
+/// `load_account`. Changes are written straight to the account data, without re-encoding it.
+pub fn load_account_mut<'a, T: GatewayAccount + Pod>(
+    account: &'a AccountInfo,
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+) -> Result<RefMut<'a, T>, ProgramError> {
+    check_seeds(account, seeds, program_id)?;
+
+    if account.owner != program_id {
+        return Err(MessengerError::InvalidAccountOwner.into());
+    }
+
+    let data = account.try_borrow_mut_data()?;
+
+    check_layout::<T>(&data)?;
+
+    let end = DISCRIMINATOR_LEN + size_of::<T>();
+
+    if data.len() < end {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+
+    Ok(RefMut::map(data, |data| {
+        bytemuck::from_bytes_mut(&mut data[DISCRIMINATOR_LEN..end])
+    }))
+}
+
+/// Loads a message client, its address is derived from the destination contract stored in it
+pub fn load_message_client(
+    account: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<MessageClient, ProgramError> {
+    let client: MessageClient = load_owned_account(account, program_id)?;
+
+    check_seeds(
+        account,
+        &[MESSAGE_CLIENT_SEED, client.destination_contract.as_ref()],
+        program_id,
+    )?;
+
+    Ok(client)
+}
+
+/// Loads the registry entry of `chain_id`, chains without one are not supported
+pub fn load_chain(
+    account: &AccountInfo,
+    program_id: &Pubkey,
+    chain_id: u64,
+) -> Result<ChainConfig, ProgramError> {
+    check_seeds(account, &[CHAIN_SEED, &chain_id.to_le_bytes()], program_id)?;
+
+    if account.data_is_empty() {
+        return Err(MessengerError::ChainNotSupported.into());
+    }
+
+    load_owned_account(account, program_id)
+}
+
+/// Writes `state` to the start of `account`, which has to be large enough to hold it
+pub fn store_account<T: GatewayAccount>(account: &AccountInfo, state: &T) -> ProgramResult {
+    let data = state.pack()?;
+
+    let mut account_data = account.try_borrow_mut_data()?;
+
+    if account_data.len() < data.len() {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+
+    account_data[..data.len()].copy_from_slice(&data);
+
+    Ok(())
+}
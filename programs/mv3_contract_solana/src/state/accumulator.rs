@@ -0,0 +1,153 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::{eip712::encode_uint, error::MessengerError, utils::keccak256};
+
+use super::config::ForeignAddress;
+
+/// Levels of the outbound tree, which holds up to `2^TREE_DEPTH - 1` messages
+pub const TREE_DEPTH: usize = 32;
+
+/// Parent of `left` and `right`, `keccak256(abi.encodePacked(left, right))`
+pub fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut encoded = [0; 64];
+    encoded[..32].copy_from_slice(left);
+    encoded[32..].copy_from_slice(right);
+
+    keccak256(&encoded)
+}
+
+/// Roots of the empty subtrees of every level, the empty leaf being zero
+pub fn zero_hashes() -> [[u8; 32]; TREE_DEPTH] {
+    let mut zero_hashes = [[0; 32]; TREE_DEPTH];
+
+    for level in 1..TREE_DEPTH {
+        zero_hashes[level] = hash_node(&zero_hashes[level - 1], &zero_hashes[level - 1]);
+    }
+
+    zero_hashes
+}
+
+/// Root reached from the leaf at `index` through the siblings in `proof`, the check an EVM
+/// verifier of outbound messages runs against a root it trusts
+pub fn process_inclusion_proof(
+    leaf: &[u8; 32],
+    index: u64,
+    proof: &[[u8; 32]; TREE_DEPTH],
+) -> [u8; 32] {
+    proof
+        .iter()
+        .enumerate()
+        .fold(*leaf, |node, (level, sibling)| {
+            if (index >> level) & 1 == 1 {
+                hash_node(sibling, &node)
+            } else {
+                hash_node(&node, sibling)
+            }
+        })
+}
+
+/// Incremental Merkle tree over the leaves of every message sent through the gateway, stored at
+/// `[ACCUMULATOR_SEED]`. Only the left siblings of the path of the next leaf are kept, like the
+/// deposit contract of Ethereum does.
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, Clone, Debug, Default, PartialEq)]
+pub struct OutboundAccumulator {
+    /// Layout the account is stored in
+    pub version: u8,
+    /// Leaves appended so far, the index of the next one
+    pub count: u64,
+    pub branch: [[u8; 32]; TREE_DEPTH],
+}
+
+impl OutboundAccumulator {
+    pub const VERSION: u8 = 1;
+
+    pub fn new() -> Self {
+        OutboundAccumulator {
+            version: Self::VERSION,
+            ..Default::default()
+        }
+    }
+
+    /// Appends `leaf` and returns its index
+    pub fn append(&mut self, leaf: [u8; 32]) -> Result<u64, MessengerError> {
+        let index = self.count;
+
+        if index >= (1 << TREE_DEPTH) - 1 {
+            return Err(MessengerError::AccumulatorFull);
+        }
+
+        self.count += 1;
+
+        let mut node = leaf;
+        let mut size = self.count;
+
+        for level in 0..TREE_DEPTH {
+            if size & 1 == 1 {
+                self.branch[level] = node;
+
+                break;
+            }
+
+            node = hash_node(&self.branch[level], &node);
+            size >>= 1;
+        }
+
+        Ok(index)
+    }
+
+    /// Root of the tree over the appended leaves, padded with empty leaves
+    pub fn root(&self) -> [u8; 32] {
+        let zero_hashes = zero_hashes();
+
+        let mut node = [0; 32];
+        let mut size = self.count;
+
+        for (left, zero) in self.branch.iter().zip(&zero_hashes) {
+            node = if size & 1 == 1 {
+                hash_node(left, &node)
+            } else {
+                hash_node(&node, zero)
+            };
+
+            size >>= 1;
+        }
+
+        node
+    }
+}
+
+/// Message sent from Solana as a leaf of the outbound tree
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, PartialEq)]
+pub struct OutboundDigest {
+    pub tx_id: u128,
+    pub source_chain_id: u64,
+    pub dest_chain_id: u64,
+    pub sender: Pubkey,
+    pub recipient: ForeignAddress,
+    pub confirmations: u16,
+    /// Gateway program the message was sent through
+    pub gateway: Pubkey,
+    /// keccak256 of the payload
+    pub payload_hash: [u8; 32],
+}
+
+impl OutboundDigest {
+    /// Leaf of the message, `keccak256(abi.encode(txId, sourceChainId, destChainId, sender,
+    /// recipient, confirmations, gateway, payloadHash))` with the IDs and confirmations as
+    /// `uint256` and the rest as `bytes32`
+    pub fn leaf(&self) -> [u8; 32] {
+        let words = [
+            encode_uint(self.tx_id),
+            encode_uint(u128::from(self.source_chain_id)),
+            encode_uint(u128::from(self.dest_chain_id)),
+            self.sender.to_bytes(),
+            self.recipient,
+            encode_uint(u128::from(self.confirmations)),
+            self.gateway.to_bytes(),
+            self.payload_hash,
+        ];
+
+        keccak256(&words.concat())
+    }
+}
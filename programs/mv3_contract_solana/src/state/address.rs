@@ -0,0 +1,128 @@
+use std::{fmt, str::FromStr};
+
+use solana_program::pubkey::Pubkey;
+
+use crate::{error::MessengerError, utils::keccak256};
+
+use super::{chain::AddressFormat, config::ForeignAddress};
+
+/// Address on another chain in the format of that chain. Accounts and instructions store it as a
+/// `ForeignAddress`, the 32 byte form relayers and the other gateways exchange.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypedAddress {
+    Evm([u8; 20]),
+    Bytes32([u8; 32]),
+    Solana(Pubkey),
+}
+
+impl TypedAddress {
+    pub fn format(&self) -> AddressFormat {
+        match self {
+            TypedAddress::Evm(_) => AddressFormat::Evm,
+            TypedAddress::Bytes32(_) => AddressFormat::Bytes32,
+            TypedAddress::Solana(_) => AddressFormat::Solana,
+        }
+    }
+
+    /// Reads `address` as an address of `format`. EVM addresses have to be left padded with
+    /// zeros.
+    pub fn from_foreign(
+        format: AddressFormat,
+        address: &ForeignAddress,
+    ) -> Result<Self, MessengerError> {
+        match format {
+            AddressFormat::Evm => {
+                if address[..12] != [0; 12] {
+                    return Err(MessengerError::InvalidForeignAddress);
+                }
+
+                let mut evm = [0; 20];
+                evm.copy_from_slice(&address[12..]);
+
+                Ok(TypedAddress::Evm(evm))
+            }
+            AddressFormat::Bytes32 => Ok(TypedAddress::Bytes32(*address)),
+            AddressFormat::Solana => Ok(TypedAddress::Solana(Pubkey::new_from_array(*address))),
+        }
+    }
+
+    pub fn to_foreign(&self) -> ForeignAddress {
+        match self {
+            TypedAddress::Evm(evm) => {
+                let mut address = [0; 32];
+                address[12..].copy_from_slice(evm);
+
+                address
+            }
+            TypedAddress::Bytes32(address) => *address,
+            TypedAddress::Solana(pubkey) => pubkey.to_bytes(),
+        }
+    }
+
+    /// Parses the text form of an address of `format`: 0x prefixed hex for EVM and 32 byte
+    /// addresses, base58 for Solana. Mixed case EVM addresses have to carry a valid EIP-55
+    /// checksum.
+    pub fn parse(format: AddressFormat, value: &str) -> Result<Self, MessengerError> {
+        match format {
+            AddressFormat::Evm => {
+                let hex_address = value.strip_prefix("0x").unwrap_or(value);
+
+                let mut evm = [0; 20];
+                hex::decode_to_slice(hex_address, &mut evm)
+                    .map_err(|_| MessengerError::InvalidForeignAddress)?;
+
+                let mixed_case = hex_address.chars().any(|c| c.is_ascii_lowercase())
+                    && hex_address.chars().any(|c| c.is_ascii_uppercase());
+
+                if mixed_case && hex_address != &evm_checksum(&evm)[2..] {
+                    return Err(MessengerError::InvalidForeignAddress);
+                }
+
+                Ok(TypedAddress::Evm(evm))
+            }
+            AddressFormat::Bytes32 => {
+                let mut address = [0; 32];
+                hex::decode_to_slice(value.strip_prefix("0x").unwrap_or(value), &mut address)
+                    .map_err(|_| MessengerError::InvalidForeignAddress)?;
+
+                Ok(TypedAddress::Bytes32(address))
+            }
+            AddressFormat::Solana => Pubkey::from_str(value)
+                .map(TypedAddress::Solana)
+                .map_err(|_| MessengerError::InvalidForeignAddress),
+        }
+    }
+}
+
+/// EVM addresses are printed with their EIP-55 checksum
+impl fmt::Display for TypedAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypedAddress::Evm(evm) => f.write_str(&evm_checksum(evm)),
+            TypedAddress::Bytes32(address) => write!(f, "0x{}", hex::encode(address)),
+            TypedAddress::Solana(pubkey) => write!(f, "{}", pubkey),
+        }
+    }
+}
+
+/// EIP-55 form of `address`, 0x prefixed
+pub fn evm_checksum(address: &[u8; 20]) -> String {
+    let lower = hex::encode(address);
+    let hash = keccak256(lower.as_bytes());
+
+    let checksummed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0x0f;
+
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    format!("0x{}", checksummed)
+}
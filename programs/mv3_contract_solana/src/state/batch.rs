@@ -0,0 +1,18 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+
+/// Merkle root over the digest hashes of a batch of messages from one source chain, signed by the
+/// validators. Stored at `[ROOT_SEED, source_chain_id, batch]`, messages of the batch are
+/// delivered with a proof against it.
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, Clone, Debug, Default, PartialEq)]
+pub struct MessageRoot {
+    /// Layout the account is stored in
+    pub version: u8,
+    pub source_chain_id: u64,
+    pub batch: u64,
+    pub root: [u8; 32],
+    pub posted_at: i64,
+}
+
+impl MessageRoot {
+    pub const VERSION: u8 = 1;
+}
@@ -0,0 +1,126 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+
+use crate::error::MessengerError;
+
+use super::rate_limit::RateLimitConfig;
+
+/// Format of the addresses of a chain
+#[derive(
+    BorshDeserialize, BorshSerialize, BorshSchema, Clone, Copy, Debug, PartialEq, Eq, PartialOrd,
+)]
+pub enum AddressFormat {
+    /// 20 byte address, left padded with zeros to 32 bytes
+    Evm,
+    /// 32 byte address, e.g. of Move or Cosmos chains
+    Bytes32,
+    Solana,
+}
+
+/// Fees of delivering a message to a chain, charged to the sender in lamports
+#[derive(
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+)]
+pub struct ChainFees {
+    /// Gas the relayer executes messages with on the chain
+    pub gas_limit: u64,
+    pub base_fee: u64,
+    pub fee_per_byte: u64,
+}
+
+impl ChainFees {
+    pub fn message_fee(&self, bytes: u64) -> Result<u64, MessengerError> {
+        self.fee_per_byte
+            .checked_mul(bytes)
+            .and_then(|fee| fee.checked_add(self.base_fee))
+            .ok_or(MessengerError::ArithmeticOverflow)
+    }
+}
+
+/// Registry entry of a chain messages are exchanged with, stored at `[CHAIN_SEED, chain_id]`.
+/// Messages from or to chains without an enabled entry are rejected.
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, Clone, Debug, PartialEq)]
+pub struct ChainConfig {
+    /// Layout the account is stored in
+    pub version: u8,
+    pub chain_id: u64,
+    pub name: String,
+    pub address_format: AddressFormat,
+    /// Confirmations messages sent to the chain have to request at least
+    pub min_confirmations: u16,
+    pub fees: ChainFees,
+    pub enabled: bool,
+    /// Messages from the chain are rejected
+    pub inbound_paused: bool,
+    /// Messages to the chain are rejected
+    pub outbound_paused: bool,
+    pub rate_limit: Option<RateLimitConfig>,
+}
+
+impl ChainConfig {
+    pub const VERSION: u8 = 1;
+
+    pub const MAX_NAME_LEN: usize = 32;
+
+    /// Enabled chain without fees, pauses or rate limit
+    pub fn new(chain_id: u64, name: &str, address_format: AddressFormat) -> Self {
+        ChainConfig {
+            version: Self::VERSION,
+            chain_id,
+            name: name.to_string(),
+            address_format,
+            min_confirmations: 0,
+            fees: ChainFees::default(),
+            enabled: true,
+            inbound_paused: false,
+            outbound_paused: false,
+            rate_limit: None,
+        }
+    }
+
+    pub fn check_name(name: &str) -> Result<(), MessengerError> {
+        if name.is_empty() || name.len() > Self::MAX_NAME_LEN {
+            return Err(MessengerError::InvalidChainName);
+        }
+
+        Ok(())
+    }
+
+    /// Checks messages from the chain are accepted
+    pub fn check_inbound(&self) -> Result<(), MessengerError> {
+        if !self.enabled {
+            return Err(MessengerError::ChainNotSupported);
+        }
+
+        if self.inbound_paused {
+            return Err(MessengerError::SourceChainPaused);
+        }
+
+        Ok(())
+    }
+
+    /// Checks messages requesting `confirmations` are accepted for the chain
+    pub fn check_outbound(&self, confirmations: u16) -> Result<(), MessengerError> {
+        if !self.enabled {
+            return Err(MessengerError::ChainNotSupported);
+        }
+
+        if self.outbound_paused {
+            return Err(MessengerError::DestinationChainPaused);
+        }
+
+        if confirmations < self.min_confirmations {
+            return Err(MessengerError::InsufficientConfirmations);
+        }
+
+        Ok(())
+    }
+}
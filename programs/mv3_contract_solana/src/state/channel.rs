@@ -0,0 +1,58 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::error::MessengerError;
+
+use super::config::ForeignAddress;
+
+/// Sequence number of a message on an ordered channel, the big endian `uint64` following the
+/// destination program at the start of the payload
+pub fn payload_sequence(payload: &[u8]) -> Result<u64, MessengerError> {
+    payload
+        .get(32..40)
+        .and_then(|sequence| <[u8; 8]>::try_from(sequence).ok())
+        .map(u64::from_be_bytes)
+        .ok_or(MessengerError::InvalidMessagePayload)
+}
+
+/// Delivery order of the messages from `remote_sender` on `source_chain_id` to a client which
+/// opted in to ordered channels. Stored at `[CHANNEL_SEED, source_chain_id, remote_sender,
+/// local_program]` and created by the delivery of the first message of the channel.
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, Clone, Debug, Default, PartialEq)]
+pub struct ChannelState {
+    /// Layout the account is stored in
+    pub version: u8,
+    pub source_chain_id: u64,
+    pub remote_sender: ForeignAddress,
+    pub local_program: Pubkey,
+    /// Sequence number the next delivered message must carry
+    pub next_sequence: u64,
+}
+
+impl ChannelState {
+    pub const VERSION: u8 = 1;
+
+    pub fn new(source_chain_id: u64, remote_sender: ForeignAddress, local_program: Pubkey) -> Self {
+        ChannelState {
+            version: Self::VERSION,
+            source_chain_id,
+            remote_sender,
+            local_program,
+            next_sequence: 0,
+        }
+    }
+
+    /// Accepts the message with `sequence` if it is the next one of the channel
+    pub fn advance(&mut self, sequence: u64) -> Result<(), MessengerError> {
+        if sequence != self.next_sequence {
+            return Err(MessengerError::OutOfOrderMessage);
+        }
+
+        self.next_sequence = self
+            .next_sequence
+            .checked_add(1)
+            .ok_or(MessengerError::OutOfOrderMessage)?;
+
+        Ok(())
+    }
+}
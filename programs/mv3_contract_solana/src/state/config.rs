@@ -1,43 +1,210 @@
+use std::mem::size_of;
+
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use bytemuck::{Pod, Zeroable};
 use solana_program::pubkey::Pubkey;
 
-#[derive(BorshDeserialize, BorshSerialize, BorshSchema, Debug)]
+use crate::{constants::EXSIG_GRACE_PERIOD, error::MessengerError};
+
+use super::{address::TypedAddress, chain::AddressFormat, rate_limit::RateLimitConfig};
+
+/// Fixed-size part of the config which `Send` and `ReceiveMessage` read and update in place.
+/// Roles live in `RoleRegistry` and every chain has its own `ChainConfig`.
+///
+/// Integers are stored as little endian byte arrays, so the struct has no padding, can be
+/// borrowed from unaligned account data and its borsh encoding is its memory layout. Fields are
+/// only ever appended, so every older header is a prefix of the current one.
+#[repr(C)]
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, Clone, Copy, Debug, PartialEq)]
 pub struct MessengerConfig {
+    /// Layout the account is stored in, older layouts are upgraded by `MigrateConfig`
+    pub version: u8,
+    /// `MessengerConfig::*_FLAG` bits
+    pub flags: u8,
     pub owner: Pubkey,
-    pub next_tx_id: u128,
-    pub enabled_chains: Vec<u32>,
-    pub whitelists: Vec<UserPermission>,
-    pub bridge_enabled: bool,
-    //TODO: implemented later
-    pub fee_currency: Option<Pubkey>,
-    pub bridge_operators: Vec<UserPermission>,
-    pub bridge_supers: Vec<UserPermission>,
-    pub bridge_a_team: Vec<UserPermission>,
     pub accountant: Pubkey,
-    pub whitelist_only: bool,
-    pub chainsig: Option<ForeignAddress>,
+    /// Only meaningful with `FEE_CURRENCY_FLAG` set
+    pub fee_currency: Pubkey,
+    /// Only meaningful with `CHAINSIG_FLAG` set
+    pub chainsig: ForeignAddress,
+    next_tx_id: [u8; 16],
+    messages_sent: [u8; 8],
+    messages_received: [u8; 8],
+    chain_id: [u8; 8],
 }
 
+//every field is a byte array, so the header has neither padding nor invalid bit patterns. The
+//derives of the pinned bytemuck leave unused helpers behind, hence the manual impls.
+const _: () = assert!(size_of::<MessengerConfig>() == 2 + 4 * 32 + 16 + 3 * 8);
+
+unsafe impl Zeroable for MessengerConfig {}
+
+unsafe impl Pod for MessengerConfig {}
+
 impl MessengerConfig {
-    pub fn new(owner: &Pubkey, accountant: &Pubkey) -> Self {
+    pub const VERSION: u8 = 3;
+
+    pub const LEN: usize = size_of::<Self>();
+
+    pub const BRIDGE_ENABLED_FLAG: u8 = 1 << 0;
+    pub const WHITELIST_ONLY_FLAG: u8 = 1 << 1;
+    pub const INBOUND_PAUSED_FLAG: u8 = 1 << 2;
+    pub const OUTBOUND_PAUSED_FLAG: u8 = 1 << 3;
+    pub const CHAINSIG_FLAG: u8 = 1 << 4;
+    pub const FEE_CURRENCY_FLAG: u8 = 1 << 5;
+    pub const EIP712_FLAG: u8 = 1 << 6;
+    pub const BLS_FLAG: u8 = 1 << 7;
+
+    pub fn new(owner: &Pubkey, accountant: &Pubkey, chain_id: u64) -> Self {
         MessengerConfig {
-            owner: owner.clone(),
-            next_tx_id: 0_u128,
-            enabled_chains: vec![],
-            whitelists: vec![],
-            bridge_enabled: true,
-            fee_currency: None,
-            bridge_operators: vec![],
-            bridge_supers: vec![],
-            bridge_a_team: vec![],
-            accountant: accountant.clone(),
-            whitelist_only: false,
-            chainsig: None,
+            version: Self::VERSION,
+            flags: Self::BRIDGE_ENABLED_FLAG,
+            owner: *owner,
+            accountant: *accountant,
+            chain_id: chain_id.to_le_bytes(),
+            ..Zeroable::zeroed()
+        }
+    }
+
+    /// ID of the chain the gateway is deployed on, messages are only accepted for it
+    pub fn chain_id(&self) -> u64 {
+        u64::from_le_bytes(self.chain_id)
+    }
+
+    fn flag(&self, flag: u8) -> bool {
+        self.flags & flag != 0
+    }
+
+    fn set_flag(&mut self, flag: u8, value: bool) {
+        if value {
+            self.flags |= flag;
+        } else {
+            self.flags &= !flag;
         }
     }
+
+    pub fn bridge_enabled(&self) -> bool {
+        self.flag(Self::BRIDGE_ENABLED_FLAG)
+    }
+
+    pub fn set_bridge_enabled(&mut self, enabled: bool) {
+        self.set_flag(Self::BRIDGE_ENABLED_FLAG, enabled)
+    }
+
+    pub fn whitelist_only(&self) -> bool {
+        self.flag(Self::WHITELIST_ONLY_FLAG)
+    }
+
+    pub fn set_whitelist_only(&mut self, whitelist_only: bool) {
+        self.set_flag(Self::WHITELIST_ONLY_FLAG, whitelist_only)
+    }
+
+    pub fn inbound_paused(&self) -> bool {
+        self.flag(Self::INBOUND_PAUSED_FLAG)
+    }
+
+    pub fn set_inbound_paused(&mut self, paused: bool) {
+        self.set_flag(Self::INBOUND_PAUSED_FLAG, paused)
+    }
+
+    pub fn outbound_paused(&self) -> bool {
+        self.flag(Self::OUTBOUND_PAUSED_FLAG)
+    }
+
+    pub fn set_outbound_paused(&mut self, paused: bool) {
+        self.set_flag(Self::OUTBOUND_PAUSED_FLAG, paused)
+    }
+
+    pub fn chainsig(&self) -> Option<ForeignAddress> {
+        self.flag(Self::CHAINSIG_FLAG).then_some(self.chainsig)
+    }
+
+    pub fn set_chainsig(&mut self, chainsig: Option<ForeignAddress>) {
+        self.set_flag(Self::CHAINSIG_FLAG, chainsig.is_some());
+        self.chainsig = chainsig.unwrap_or_default();
+    }
+
+    pub fn digest_scheme(&self) -> DigestScheme {
+        if self.flag(Self::EIP712_FLAG) {
+            DigestScheme::Eip712
+        } else {
+            DigestScheme::PersonalSign
+        }
+    }
+
+    pub fn set_digest_scheme(&mut self, scheme: DigestScheme) {
+        self.set_flag(Self::EIP712_FLAG, scheme == DigestScheme::Eip712)
+    }
+
+    /// Inbound messages carry an aggregated signature of the `ValidatorSet` instead of the
+    /// chainsig signature
+    pub fn bls_enabled(&self) -> bool {
+        self.flag(Self::BLS_FLAG)
+    }
+
+    pub fn set_bls_enabled(&mut self, enabled: bool) {
+        self.set_flag(Self::BLS_FLAG, enabled)
+    }
+
+    pub fn fee_currency(&self) -> Option<Pubkey> {
+        self.flag(Self::FEE_CURRENCY_FLAG)
+            .then_some(self.fee_currency)
+    }
+
+    pub fn set_fee_currency(&mut self, fee_currency: Option<Pubkey>) {
+        self.set_flag(Self::FEE_CURRENCY_FLAG, fee_currency.is_some());
+        self.fee_currency = fee_currency.unwrap_or_default();
+    }
+
+    pub fn next_tx_id(&self) -> u128 {
+        u128::from_le_bytes(self.next_tx_id)
+    }
+
+    pub fn set_next_tx_id(&mut self, next_tx_id: u128) {
+        self.next_tx_id = next_tx_id.to_le_bytes();
+    }
+
+    pub fn messages_sent(&self) -> u64 {
+        u64::from_le_bytes(self.messages_sent)
+    }
+
+    pub fn messages_received(&self) -> u64 {
+        u64::from_le_bytes(self.messages_received)
+    }
+
+    //counters only feed monitoring, so they wrap instead of failing delivery
+    pub fn record_sent(&mut self) {
+        self.messages_sent = self.messages_sent().wrapping_add(1).to_le_bytes();
+    }
+
+    pub fn record_received(&mut self) {
+        self.messages_received = self.messages_received().wrapping_add(1).to_le_bytes();
+    }
+}
+
+/// How the chainsig and exsig of inbound messages hash the `MessageDigest` they sign
+#[derive(
+    BorshDeserialize, BorshSerialize, BorshSchema, Clone, Copy, Debug, PartialEq, Eq, PartialOrd,
+)]
+pub enum DigestScheme {
+    /// EIP-191 personal sign hash of the borsh encoded digest
+    PersonalSign,
+    /// EIP-712 hash of the digest as a typed `Message` in the domain of the gateway
+    Eip712,
+}
+
+/// Wallets granted a `Role` by the owner
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, Debug, Default)]
+pub struct RoleRegistry {
+    pub whitelists: Vec<UserPermission>,
+    pub bridge_operators: Vec<UserPermission>,
+    pub bridge_supers: Vec<UserPermission>,
+    pub bridge_a_team: Vec<UserPermission>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[derive(
+    BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Eq, PartialOrd, Ord, Clone, Debug,
+)]
 pub enum Role {
     Operator,
     ATeam,
@@ -68,17 +235,96 @@ impl Exsig {
     pub const LEN: usize = 8 + 32 + 32;
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Debug)]
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, Debug)]
 pub struct MessageClient {
+    /// Layout the account is stored in, older layouts are upgraded by `MigrateConfig`
+    pub version: u8,
     pub authority: Pubkey,
     pub destination_contract: Pubkey,
     pub notify_on_failure: bool,
     pub supported_chains: Vec<u64>,
-    //add chain id in allowed_contracts
+    /// Valid in the address format of at least one of `supported_chains`
     pub allowed_contracts: Vec<ForeignAddress>,
     pub exsig: Option<ForeignAddress>,
+    pub paused: bool,
+    pub pending_authority: Option<Pubkey>,
+    pub pending_exsig: Option<PendingExsig>,
+    pub rate_limit: Option<RateLimitConfig>,
+    /// Messages are executed on commitments of bonded operators after the challenge period
+    /// instead of on validator signatures
+    pub optimistic: bool,
+    /// Messages carry a sequence number and are executed in order per source chain and sender
+    pub ordered: bool,
 }
 
 impl MessageClient {
-    pub const LEN: u64 = 32 + 32 + 1 + 4 + 4 + 1;
+    pub const LEN: u64 = 1 + 32 + 32 + 1 + 4 + 4 + 1 + 1 + 1 + 1 + 1 + 1 + 1;
+
+    pub const VERSION: u8 = 3;
+
+    /// Exsig which messages have to be signed with at `now`. The previous exsig stays in effect
+    /// until a pending rotation activates, so messages signed before the rotation still verify.
+    pub fn active_exsig(&self, now: i64) -> Option<ForeignAddress> {
+        match &self.pending_exsig {
+            Some(pending) if pending.activates_at <= now => pending.exsig,
+            _ => self.exsig,
+        }
+    }
+
+    /// Exsigs messages are accepted with at `now`, the active one first. The previous exsig is
+    /// accepted for `EXSIG_GRACE_PERIOD` seconds after a rotation activates.
+    pub fn accepted_exsigs(&self, now: i64) -> Vec<Option<ForeignAddress>> {
+        let active = self.active_exsig(now);
+
+        if self.in_exsig_grace_period(now) && self.exsig != active {
+            vec![active, self.exsig]
+        } else {
+            vec![active]
+        }
+    }
+
+    /// Whether a rotation activated at `now` and the previous exsig is still accepted
+    pub fn in_exsig_grace_period(&self, now: i64) -> bool {
+        matches!(
+            &self.pending_exsig,
+            Some(pending) if pending.activates_at <= now
+                && now < pending.activates_at.saturating_add(EXSIG_GRACE_PERIOD)
+        )
+    }
+
+    /// Checks every allowed contract is an address in one of `formats`, the formats of the
+    /// supported chains
+    pub fn check_allowed_contracts(&self, formats: &[AddressFormat]) -> Result<(), MessengerError> {
+        if formats.is_empty() {
+            return Ok(());
+        }
+
+        for contract in &self.allowed_contracts {
+            if !formats
+                .iter()
+                .any(|format| TypedAddress::from_foreign(*format, contract).is_ok())
+            {
+                return Err(MessengerError::InvalidForeignAddress);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the exsig with the pending one once the grace period of the rotation ended
+    pub fn apply_pending_exsig(&mut self, now: i64) {
+        if matches!(
+            &self.pending_exsig,
+            Some(pending) if pending.activates_at.saturating_add(EXSIG_GRACE_PERIOD) <= now
+        ) {
+            self.exsig = self.active_exsig(now);
+            self.pending_exsig = None;
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, Debug, Clone, PartialEq)]
+pub struct PendingExsig {
+    pub exsig: Option<ForeignAddress>,
+    pub activates_at: i64,
 }
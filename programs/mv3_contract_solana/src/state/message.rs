@@ -1,23 +1,25 @@
-use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::{clock::Clock, pubkey::Pubkey, sysvar::Sysvar};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{clock::Clock, program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar};
+
+use crate::error::MessengerError;
 
 use super::config::ForeignAddress;
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct Message {
     pub tx_id: u128,
     pub sender: Pubkey,
     pub recipient: ForeignAddress,
-    pub chain: u32,
+    pub chain: u64,
     pub data: Vec<u8>,
     pub confirmations: u16,
 }
 
 impl Message {
-    pub const LEN: usize = 4 + 16 + 32 + 32 + 4 + 4 + 2;
+    pub const LEN: usize = 4 + 16 + 32 + 32 + 8 + 4 + 2;
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct MessagePayload {
     pub tx_id: u128,
     pub destination: Pubkey,
@@ -28,16 +30,20 @@ pub struct MessagePayload {
 impl MessagePayload {
     pub const LEN: u64 = 16 + 32 + 8 + 32;
 
-    pub fn unpack(tx_id: u128, sender: ForeignAddress, data: &Vec<u8>) -> Self {
-        let program_id = Pubkey::new_from_array(data[..32].try_into().unwrap());
+    /// Record of a delivered payload, which starts with the program it is destined to
+    pub fn unpack(tx_id: u128, sender: ForeignAddress, data: &[u8]) -> Result<Self, ProgramError> {
+        let program_id = data
+            .get(..32)
+            .and_then(|program_id| <[u8; 32]>::try_from(program_id).ok())
+            .ok_or(MessengerError::InvalidMessagePayload)?;
 
-        let current_unix = Clock::get().unwrap().unix_timestamp;
+        let current_unix = Clock::get()?.unix_timestamp;
 
-        Self {
+        Ok(Self {
             tx_id,
-            destination: program_id,
+            destination: Pubkey::new_from_array(program_id),
             received_at: current_unix,
             sender,
-        }
+        })
     }
 }
@@ -1,2 +1,12 @@
+pub mod account;
+pub mod accumulator;
+pub mod address;
+pub mod batch;
+pub mod chain;
+pub mod channel;
 pub mod config;
 pub mod message;
+pub mod optimistic;
+pub mod rate_limit;
+pub mod validators;
+pub mod version;
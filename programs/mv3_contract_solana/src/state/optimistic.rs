@@ -0,0 +1,150 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::error::MessengerError;
+
+/// Parameters of optimistic delivery, stored at `[OPTIMISTIC_SEED]`. Messages to clients which
+/// opted in with `MessageClient::optimistic` are committed by a bonded operator and executed
+/// once the challenge period passed without a watcher claiming fraud.
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, Clone, Debug, Default, PartialEq)]
+pub struct OptimisticConfig {
+    /// Layout the account is stored in
+    pub version: u8,
+    /// Seconds a commitment can be challenged for before it is executable
+    pub challenge_period: i64,
+    /// Lamports an operator has to keep bonded to post commitments
+    pub min_bond: u64,
+    /// Accounts allowed to challenge commitments
+    pub watchers: Vec<Pubkey>,
+}
+
+impl OptimisticConfig {
+    pub const VERSION: u8 = 1;
+
+    pub const MAX_WATCHERS: usize = 64;
+
+    pub fn new(challenge_period: i64, min_bond: u64, watchers: Vec<Pubkey>) -> Self {
+        OptimisticConfig {
+            version: Self::VERSION,
+            challenge_period,
+            min_bond,
+            watchers,
+        }
+    }
+
+    /// Checks commitments can be challenged: the period is not empty, a slashed operator loses
+    /// something and every watcher is listed once
+    pub fn check(&self) -> Result<(), MessengerError> {
+        if self.challenge_period <= 0
+            || self.min_bond == 0
+            || self.watchers.is_empty()
+            || self.watchers.len() > Self::MAX_WATCHERS
+        {
+            return Err(MessengerError::InvalidOptimisticConfig);
+        }
+
+        for (index, watcher) in self.watchers.iter().enumerate() {
+            if self.watchers[..index].contains(watcher) {
+                return Err(MessengerError::InvalidOptimisticConfig);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn is_watcher(&self, account: &Pubkey) -> bool {
+        self.watchers.contains(account)
+    }
+}
+
+/// Lamports `operator` has at stake, stored at `[BOND_SEED, operator]` on top of the rent of the
+/// account
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, Clone, Debug, Default, PartialEq)]
+pub struct OperatorBond {
+    /// Layout the account is stored in
+    pub version: u8,
+    pub operator: Pubkey,
+    /// Lamports slashed when a commitment of the operator is challenged
+    pub amount: u64,
+    /// End of the challenge period of the latest commitment, the bond can't be withdrawn before
+    pub locked_until: i64,
+}
+
+impl OperatorBond {
+    pub const VERSION: u8 = 1;
+
+    pub fn new(operator: Pubkey) -> Self {
+        OperatorBond {
+            version: Self::VERSION,
+            operator,
+            ..Default::default()
+        }
+    }
+}
+
+/// Claim of a watcher that a commitment is fraudulent, settled once `ends_at` passed unless the
+/// validators vouched for the committed message before
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, Clone, Debug, Default, PartialEq)]
+pub struct CommitmentDispute {
+    pub watcher: Pubkey,
+    /// Start of the settlement, the operator loses the bond if the claim stands until then
+    pub ends_at: i64,
+}
+
+/// Message an operator vouches for, stored at `[COMMITMENT_SEED, tx_id]`. A challenged
+/// commitment blocks the message until another operator commits to it in its place, a
+/// disputed one until the dispute is settled.
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, Clone, Debug, Default, PartialEq)]
+pub struct MessageCommitment {
+    /// Layout the account is stored in
+    pub version: u8,
+    pub operator: Pubkey,
+    pub tx_id: u128,
+    pub receiver: Pubkey,
+    /// Digest hash of the message, in the digest scheme of the config
+    pub hash: [u8; 32],
+    /// Start of the execution window, the end of the challenge period
+    pub executable_at: i64,
+    pub challenged: bool,
+    /// Pending claim of a watcher against the commitment
+    pub dispute: Option<CommitmentDispute>,
+}
+
+impl MessageCommitment {
+    pub const VERSION: u8 = 1;
+
+    /// Checks the message with digest hash `hash` can be executed at `now` on the strength of
+    /// the commitment
+    pub fn check_executable(&self, hash: &[u8; 32], now: i64) -> Result<(), MessengerError> {
+        if self.challenged {
+            return Err(MessengerError::CommitmentChallenged);
+        }
+
+        if self.dispute.is_some() {
+            return Err(MessengerError::CommitmentDisputed);
+        }
+
+        if self.hash != *hash {
+            return Err(MessengerError::InvalidSignature);
+        }
+
+        if now < self.executable_at {
+            return Err(MessengerError::ChallengePeriodActive);
+        }
+
+        Ok(())
+    }
+
+    /// Checks a watcher can still challenge the commitment at `now`
+    pub fn check_challengeable(&self, now: i64) -> Result<(), MessengerError> {
+        if self.challenged {
+            return Err(MessengerError::CommitmentChallenged);
+        }
+
+        if now >= self.executable_at {
+            return Err(MessengerError::ChallengePeriodEnded);
+        }
+
+        Ok(())
+    }
+}
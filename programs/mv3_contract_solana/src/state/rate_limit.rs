@@ -0,0 +1,114 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+
+use crate::error::MessengerError;
+
+/// Token bucket refilling `max_messages` and `max_bytes` linearly over `window` seconds.
+/// A zero capacity leaves that dimension unlimited.
+#[derive(
+    BorshDeserialize, BorshSerialize, BorshSchema, Debug, Clone, Copy, PartialEq, PartialOrd,
+)]
+pub struct RateLimitConfig {
+    pub max_messages: u64,
+    pub max_bytes: u64,
+    pub window: i64,
+}
+
+/// Bucket levels are kept in units of 1/window tokens so partial refills are never rounded away.
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, Debug, Default)]
+pub struct RateLimitState {
+    pub message_tokens: u128,
+    pub byte_tokens: u128,
+    pub last_updated: i64,
+}
+
+impl RateLimitState {
+    pub const LEN: u64 = 16 + 16 + 8;
+
+    pub fn new(config: &RateLimitConfig, now: i64) -> Self {
+        let window = config.window.max(1) as u128;
+
+        RateLimitState {
+            message_tokens: u128::from(config.max_messages) * window,
+            byte_tokens: u128::from(config.max_bytes) * window,
+            last_updated: now,
+        }
+    }
+
+    pub fn consume(
+        &mut self,
+        config: &RateLimitConfig,
+        now: i64,
+        bytes: u64,
+    ) -> Result<(), MessengerError> {
+        //no amount of waiting refills the bucket beyond its capacity
+        if config.max_bytes > 0 && bytes > config.max_bytes {
+            return Err(MessengerError::MessageTooLarge);
+        }
+
+        let window = config.window.max(1) as u128;
+        let elapsed = now.saturating_sub(self.last_updated).max(0) as u128;
+
+        let message_tokens = refill(self.message_tokens, config.max_messages, window, elapsed);
+        let byte_tokens = refill(self.byte_tokens, config.max_bytes, window, elapsed);
+
+        let message_cost = if config.max_messages > 0 { window } else { 0 };
+        let byte_cost = if config.max_bytes > 0 {
+            u128::from(bytes) * window
+        } else {
+            0
+        };
+
+        if message_tokens < message_cost || byte_tokens < byte_cost {
+            return Err(MessengerError::RateLimitExceeded);
+        }
+
+        self.message_tokens = message_tokens - message_cost;
+        self.byte_tokens = byte_tokens - byte_cost;
+        self.last_updated = now;
+
+        Ok(())
+    }
+
+    /// Carries the bucket over from `old` to `new` at `now`. The levels refilled under `old` are
+    /// converted to the window of `new`, a dimension `old` left unlimited starts out full.
+    pub fn rescale(&mut self, old: &RateLimitConfig, new: &RateLimitConfig, now: i64) {
+        let old_window = old.window.max(1) as u128;
+        let new_window = new.window.max(1) as u128;
+        let elapsed = now.saturating_sub(self.last_updated).max(0) as u128;
+
+        let message_tokens = refill(self.message_tokens, old.max_messages, old_window, elapsed);
+        let byte_tokens = refill(self.byte_tokens, old.max_bytes, old_window, elapsed);
+
+        self.message_tokens = carry(
+            message_tokens,
+            old.max_messages,
+            old_window,
+            new.max_messages,
+            new_window,
+        );
+        self.byte_tokens = carry(
+            byte_tokens,
+            old.max_bytes,
+            old_window,
+            new.max_bytes,
+            new_window,
+        );
+        self.last_updated = now;
+    }
+}
+
+fn carry(tokens: u128, old_capacity: u64, old_window: u128, capacity: u64, window: u128) -> u128 {
+    let full = u128::from(capacity) * window;
+
+    if old_capacity == 0 {
+        return full;
+    }
+
+    (tokens.saturating_mul(window) / old_window).min(full)
+}
+
+fn refill(tokens: u128, capacity: u64, window: u128, elapsed: u128) -> u128 {
+    tokens
+        .saturating_add(elapsed.saturating_mul(u128::from(capacity)))
+        .min(u128::from(capacity) * window)
+}
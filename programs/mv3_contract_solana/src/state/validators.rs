@@ -0,0 +1,121 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    bls::{self, BlsKey, BlsProof, G1Point},
+    error::MessengerError,
+};
+
+/// Validators whose aggregated BLS signature inbound messages carry while
+/// `MessengerConfig::BLS_FLAG` is set, and validators signing inbound messages with Ed25519 keys
+/// while their threshold is not zero. Stored at `[VALIDATORS_SEED]`.
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema, Clone, Debug, Default, PartialEq)]
+pub struct ValidatorSet {
+    /// Layout the account is stored in
+    pub version: u8,
+    /// Signers a message needs at least
+    pub threshold: u16,
+    /// BN254 G1 keys, validator `i` is bit `i` of the signer bitmap
+    pub bls_keys: Vec<G1Point>,
+    /// Ed25519 signers a message needs at least, zero when they are not checked
+    pub ed25519_threshold: u16,
+    /// Ed25519 keys, checked by an Ed25519 program instruction preceding `ReceiveMessage`
+    pub ed25519_keys: Vec<Pubkey>,
+}
+
+impl ValidatorSet {
+    pub const VERSION: u8 = 1;
+
+    pub const MAX_VALIDATORS: usize = 256;
+
+    pub fn new() -> Self {
+        ValidatorSet {
+            version: Self::VERSION,
+            ..Default::default()
+        }
+    }
+
+    /// Replaces the keys from `offset` on with `keys` after checking their proofs of possession,
+    /// so a set too large for one transaction is written in several
+    pub fn write_keys(&mut self, offset: usize, keys: &[BlsKey]) -> Result<(), MessengerError> {
+        if offset > self.bls_keys.len() || offset + keys.len() > Self::MAX_VALIDATORS {
+            return Err(MessengerError::InvalidValidatorSet);
+        }
+
+        for key in keys {
+            bls::check_possession(key)?;
+        }
+
+        self.bls_keys.truncate(offset);
+        self.bls_keys.extend(keys.iter().map(|key| key.key));
+
+        Ok(())
+    }
+
+    /// Checks the set can verify messages: the threshold is reachable and every validator is
+    /// counted once
+    pub fn check(&self) -> Result<(), MessengerError> {
+        if self.threshold == 0 || usize::from(self.threshold) > self.bls_keys.len() {
+            return Err(MessengerError::InvalidValidatorSet);
+        }
+
+        for (index, key) in self.bls_keys.iter().enumerate() {
+            if self.bls_keys[..index].contains(key) {
+                return Err(MessengerError::InvalidValidatorSet);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the Ed25519 keys, no keys and a zero threshold stop checking Ed25519 signatures
+    pub fn set_ed25519_keys(
+        &mut self,
+        keys: Vec<Pubkey>,
+        threshold: u16,
+    ) -> Result<(), MessengerError> {
+        if keys.len() > Self::MAX_VALIDATORS
+            || usize::from(threshold) > keys.len()
+            || (threshold == 0) != keys.is_empty()
+        {
+            return Err(MessengerError::InvalidValidatorSet);
+        }
+
+        for (index, key) in keys.iter().enumerate() {
+            if keys[..index].contains(key) {
+                return Err(MessengerError::InvalidValidatorSet);
+            }
+        }
+
+        self.ed25519_keys = keys;
+        self.ed25519_threshold = threshold;
+
+        Ok(())
+    }
+
+    /// Whether inbound messages need Ed25519 signatures
+    pub fn ed25519_enabled(&self) -> bool {
+        self.ed25519_threshold > 0
+    }
+
+    /// Checks at least `ed25519_threshold` validators are among `signers`, the keys whose
+    /// signatures the Ed25519 program checked
+    pub fn verify_ed25519(&self, signers: &[Pubkey]) -> Result<(), MessengerError> {
+        let signed = self
+            .ed25519_keys
+            .iter()
+            .filter(|key| signers.contains(key))
+            .count();
+
+        if signed < usize::from(self.ed25519_threshold) {
+            return Err(MessengerError::Ed25519ThresholdNotMet);
+        }
+
+        Ok(())
+    }
+
+    /// Checks `proof` holds signatures of at least `threshold` validators over `message`
+    pub fn verify(&self, message: &[u8; 32], proof: &BlsProof) -> Result<(), MessengerError> {
+        bls::verify(message, proof, &self.bls_keys, self.threshold)
+    }
+}
@@ -0,0 +1,192 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    borsh0_10::try_from_slice_unchecked, program_error::ProgramError, pubkey::Pubkey,
+};
+
+use crate::{constants::SOLANA_CHAIN_ID, error::MessengerError};
+
+use super::{
+    account::{has_discriminator, is_gateway_account, GatewayAccount, DISCRIMINATOR_LEN},
+    chain::{AddressFormat, ChainConfig},
+    config::{ForeignAddress, MessageClient, MessengerConfig, RoleRegistry, UserPermission},
+    message::Message,
+};
+
+/// Config layout of the first deployment, stored without a discriminator or version
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct MessengerConfigV0 {
+    pub owner: Pubkey,
+    pub next_tx_id: u128,
+    pub enabled_chains: Vec<u32>,
+    pub whitelists: Vec<UserPermission>,
+    pub bridge_enabled: bool,
+    pub fee_currency: Option<Pubkey>,
+    pub bridge_operators: Vec<UserPermission>,
+    pub bridge_supers: Vec<UserPermission>,
+    pub bridge_a_team: Vec<UserPermission>,
+    pub accountant: Pubkey,
+    pub whitelist_only: bool,
+    pub chainsig: Option<ForeignAddress>,
+}
+
+impl MessengerConfigV0 {
+    /// Enabled chains, in the order `MigrateConfig` expects their registry accounts
+    pub fn chain_ids(&self) -> Vec<u64> {
+        let mut chain_ids: Vec<u64> = self
+            .enabled_chains
+            .iter()
+            .map(|chain_id| u64::from(*chain_id))
+            .collect();
+
+        chain_ids.sort_unstable();
+        chain_ids.dedup();
+
+        chain_ids
+    }
+
+    /// Splits the config into the current header, the role registry and the registry entries of
+    /// `chain_ids()`. Until the registry, every chain was an EVM chain.
+    pub fn split(self) -> (MessengerConfig, RoleRegistry, Vec<ChainConfig>) {
+        let chains = self
+            .chain_ids()
+            .into_iter()
+            .map(|chain_id| ChainConfig::new(chain_id, &chain_id.to_string(), AddressFormat::Evm))
+            .collect();
+
+        let mut config = MessengerConfig::new(&self.owner, &self.accountant, SOLANA_CHAIN_ID);
+
+        config.set_next_tx_id(self.next_tx_id);
+        config.set_bridge_enabled(self.bridge_enabled);
+        config.set_whitelist_only(self.whitelist_only);
+        config.set_chainsig(self.chainsig);
+        config.set_fee_currency(self.fee_currency);
+
+        let roles = RoleRegistry {
+            whitelists: self.whitelists,
+            bridge_operators: self.bridge_operators,
+            bridge_supers: self.bridge_supers,
+            bridge_a_team: self.bridge_a_team,
+        };
+
+        (config, roles, chains)
+    }
+}
+
+/// Message client layout of the first deployment, stored without a discriminator or version
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct MessageClientV0 {
+    pub authority: Pubkey,
+    pub destination_contract: Pubkey,
+    pub notify_on_failure: bool,
+    pub supported_chains: Vec<u64>,
+    pub allowed_contracts: Vec<ForeignAddress>,
+    pub exsig: Option<ForeignAddress>,
+}
+
+impl From<MessageClientV0> for MessageClient {
+    fn from(value: MessageClientV0) -> Self {
+        MessageClient {
+            version: MessageClient::VERSION,
+            authority: value.authority,
+            destination_contract: value.destination_contract,
+            notify_on_failure: value.notify_on_failure,
+            supported_chains: value.supported_chains,
+            allowed_contracts: value.allowed_contracts,
+            exsig: value.exsig,
+            paused: false,
+            pending_authority: None,
+            pending_exsig: None,
+            rate_limit: None,
+            optimistic: false,
+            ordered: false,
+        }
+    }
+}
+
+/// Outbound message layout of the first deployment, stored without a discriminator and with a
+/// 32 bit chain ID
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct MessageV0 {
+    pub tx_id: u128,
+    pub sender: Pubkey,
+    pub recipient: ForeignAddress,
+    pub chain: u32,
+    pub data: Vec<u8>,
+    pub confirmations: u16,
+}
+
+impl From<MessageV0> for Message {
+    fn from(value: MessageV0) -> Self {
+        Message {
+            tx_id: value.tx_id,
+            sender: value.sender,
+            recipient: value.recipient,
+            chain: u64::from(value.chain),
+            data: value.data,
+            confirmations: value.confirmations,
+        }
+    }
+}
+
+/// Version `data` of a `T` was stored with, layouts of the first deployment carry neither a
+/// discriminator nor a version
+pub fn stored_version<T: GatewayAccount>(data: &[u8]) -> Result<u8, ProgramError> {
+    if has_discriminator::<T>(data) {
+        return data
+            .get(DISCRIMINATOR_LEN)
+            .copied()
+            .ok_or(MessengerError::UnsupportedAccountVersion.into());
+    }
+
+    //anything else stored behind a discriminator was written after the first deployment
+    if is_gateway_account(data) {
+        return Err(MessengerError::InvalidAccountDiscriminator.into());
+    }
+
+    Ok(0)
+}
+
+/// Decodes a config stored before the header split
+pub fn unpack_legacy_config(data: &[u8]) -> Result<MessengerConfigV0, ProgramError> {
+    match stored_version::<MessengerConfig>(data)? {
+        0 => Ok(try_from_slice_unchecked(data)?),
+        MessengerConfig::VERSION => Err(MessengerError::AccountAlreadyMigrated.into()),
+        _ => Err(MessengerError::UnsupportedAccountVersion.into()),
+    }
+}
+
+/// State whose layout changed between deployments. Every historical layout stays decodable, so
+/// accounts can be upgraded in place instead of being recreated.
+pub trait VersionedAccount: GatewayAccount {
+    /// Decodes `data` stored with a `version` older than the current one, upgraded to the
+    /// current layout
+    fn unpack_outdated(version: u8, data: &[u8]) -> Result<Self, ProgramError>;
+
+    /// Decodes `data` stored in any layout, upgraded to the current one, along with the version it
+    /// was stored in
+    fn unpack_versioned(data: &[u8]) -> Result<(u8, Self), ProgramError> {
+        let version = stored_version::<Self>(data)?;
+
+        if Some(version) == Self::VERSION {
+            return Ok((version, Self::unpack(data)?));
+        }
+
+        Ok((version, Self::unpack_outdated(version, data)?))
+    }
+}
+
+impl VersionedAccount for MessengerConfig {
+    /// Only the header is returned, `unpack_legacy_config` keeps the lists moved out of it
+    fn unpack_outdated(_version: u8, data: &[u8]) -> Result<Self, ProgramError> {
+        Ok(unpack_legacy_config(data)?.split().0)
+    }
+}
+
+impl VersionedAccount for MessageClient {
+    fn unpack_outdated(version: u8, data: &[u8]) -> Result<Self, ProgramError> {
+        match version {
+            0 => Ok(try_from_slice_unchecked::<MessageClientV0>(data)?.into()),
+            _ => Err(MessengerError::UnsupportedAccountVersion.into()),
+        }
+    }
+}
@@ -1,23 +1,44 @@
+use borsh::BorshDeserialize;
 use sha3::{Digest, Keccak256};
 use solana_program::{
     account_info::AccountInfo,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    clock::Clock,
     entrypoint::ProgramResult,
     program::{invoke, invoke_signed},
     program_error::ProgramError,
     pubkey::Pubkey,
     rent::Rent,
+    secp256k1_recover::secp256k1_recover,
     system_instruction::{self, create_account},
+    system_program,
+    sysvar::Sysvar,
 };
 use tiny_keccak::{Hasher, Keccak};
 
 use crate::{
+    bls::BlsProof,
     constants::{
-        GLOBAL_TREASURY, MESSAGE_CLIENT_SEED, MESSAGE_CLIENT_TREASURY_SEED, MESSAGE_SEED, PREFIX,
+        ACCUMULATOR_SEED, BOND_SEED, CHAIN_RATE_LIMIT_SEED, CHAIN_SEED, CHANNEL_SEED,
+        CLIENT_RATE_LIMIT_SEED, COMMITMENT_SEED, GLOBAL_TREASURY, MESSAGE_CLIENT_SEED,
+        MESSAGE_CLIENT_TREASURY_SEED, MESSAGE_SEED, OPTIMISTIC_SEED, PREFIX, ROOT_SEED,
+        VALIDATORS_SEED,
     },
+    ed25519,
     error::MessengerError,
-    state::config::{MessengerConfig, Role},
+    state::{
+        account::{
+            load_account, load_owned_account, store_account, GatewayAccount, DISCRIMINATOR_LEN,
+        },
+        config::{ForeignAddress, MessengerConfig, Role, RoleRegistry},
+        rate_limit::{RateLimitConfig, RateLimitState},
+        validators::ValidatorSet,
+    },
 };
 
+/// Creates the account `account` at the address derived from `seeds`, owned by `owner_program`.
+/// Anyone can send lamports to the address ahead of time, so a funded address is topped up to
+/// rent exemption, allocated and assigned instead of created.
 pub fn initialize_account<'a, 'b>(
     from: &'a AccountInfo<'b>,
     account: &'a AccountInfo<'b>,
@@ -26,17 +47,38 @@ pub fn initialize_account<'a, 'b>(
     owner_program: &Pubkey,
     seeds: &[&[u8]],
 ) -> ProgramResult {
-    let rent = Rent::default().minimum_balance(space.try_into().unwrap());
+    let rent = Rent::default()
+        .minimum_balance(usize::try_from(space).map_err(|_| MessengerError::ArithmeticOverflow)?);
+
+    let accounts = &[
+        account.to_owned(),
+        from.to_owned(),
+        system_program.to_owned(),
+    ];
+
+    if account.lamports() == 0 {
+        let create_account_ix = create_account(from.key, account.key, rent, space, owner_program);
+
+        invoke_signed(&create_account_ix, accounts, &[seeds])?;
+
+        return Ok(());
+    }
+
+    let missing = rent.saturating_sub(account.lamports());
 
-    let create_account_ix = create_account(from.key, account.key, rent, space, owner_program);
+    if missing > 0 {
+        transfer_sol(from, account, missing, system_program, None)?;
+    }
 
     invoke_signed(
-        &create_account_ix,
-        &[
-            account.to_owned(),
-            from.to_owned(),
-            system_program.to_owned(),
-        ],
+        &system_instruction::allocate(account.key, space),
+        accounts,
+        &[seeds],
+    )?;
+
+    invoke_signed(
+        &system_instruction::assign(account.key, owner_program),
+        accounts,
         &[seeds],
     )?;
 
@@ -52,13 +94,13 @@ pub fn check_keys_eq(account_1: &Pubkey, account_2: &Pubkey) -> Result<(), Progr
 }
 
 pub fn role_guard(
-    config: &MessengerConfig,
+    roles: &RoleRegistry,
     checked_account: &AccountInfo,
     role: Role,
 ) -> Result<(), ProgramError> {
     match role {
         Role::ATeam => {
-            if config
+            if roles
                 .bridge_a_team
                 .iter()
                 .any(|a_team| a_team.wallet == *checked_account.key && a_team.is_active)
@@ -68,7 +110,7 @@ pub fn role_guard(
             return Err(MessengerError::CallerNotATeam.into());
         }
         Role::Super => {
-            if config.bridge_supers.iter().any(|super_account| {
+            if roles.bridge_supers.iter().any(|super_account| {
                 super_account.wallet == *checked_account.key && super_account.is_active
             }) {
                 return Ok(());
@@ -76,7 +118,7 @@ pub fn role_guard(
             return Err(MessengerError::CallerNotSuper.into());
         }
         Role::Operator => {
-            if config
+            if roles
                 .bridge_operators
                 .iter()
                 .any(|operator| operator.wallet == *checked_account.key && operator.is_active)
@@ -91,22 +133,13 @@ pub fn role_guard(
     }
 }
 
-pub fn check_target_chain(config: MessengerConfig, target_chain: &u32) -> Result<(), ProgramError> {
-    if !config
-        .enabled_chains
-        .iter()
-        .any(|chain| chain == target_chain)
-    {
-        return Err(MessengerError::ChainNotSupported.into());
-    }
-
-    Ok(())
-}
-
-pub fn get_next_tx_id(config: &MessengerConfig) -> u128 {
-    let next_tx_id = config.next_tx_id.checked_add(1).expect("Oveflow");
+pub fn get_next_tx_id(config: &MessengerConfig) -> Result<u128, ProgramError> {
+    let next_tx_id = config
+        .next_tx_id()
+        .checked_add(1)
+        .ok_or(MessengerError::ArithmeticOverflow)?;
 
-    next_tx_id
+    Ok(next_tx_id)
 }
 
 pub fn check_seeds(
@@ -126,11 +159,12 @@ pub fn check_seeds(
 pub fn check_client_seeds(
     destination_contract: Pubkey,
     address: Pubkey,
+    program_id: &Pubkey,
 ) -> Result<u8, ProgramError> {
-    let (pda, bump) = get_message_client_pda(destination_contract);
+    let (pda, bump) = get_message_client_pda(destination_contract, program_id);
 
     if address != pda {
-        return Err(ProgramError::InvalidSeeds.into());
+        return Err(MessengerError::InvalidAccountSeeds.into());
     }
 
     Ok(bump)
@@ -139,6 +173,7 @@ pub fn check_client_seeds(
 pub fn check_client_treasury_seeds(
     destination_contract: Pubkey,
     address: Pubkey,
+    program_id: &Pubkey,
 ) -> Result<u8, ProgramError> {
     let (pda, bump) = Pubkey::find_program_address(
         &[
@@ -146,39 +181,42 @@ pub fn check_client_treasury_seeds(
             destination_contract.as_ref(),
             MESSAGE_CLIENT_TREASURY_SEED,
         ],
-        &crate::id(),
+        program_id,
     );
 
     if pda != address {
-        return Err(ProgramError::InvalidSeeds.into());
+        return Err(MessengerError::InvalidAccountSeeds.into());
     }
 
     Ok(bump)
 }
 
-pub fn get_client_treasury_pda(destination_contract: Pubkey) -> Pubkey {
+pub fn get_client_treasury_pda(destination_contract: Pubkey, program_id: &Pubkey) -> Pubkey {
     let (pda, _) = Pubkey::find_program_address(
         &[
             MESSAGE_CLIENT_SEED,
             destination_contract.as_ref(),
             MESSAGE_CLIENT_TREASURY_SEED,
         ],
-        &crate::id(),
+        program_id,
     );
 
     pda
 }
 
-pub fn get_global_treasury_pda() -> Pubkey {
-    let (pda, _) = Pubkey::find_program_address(&[MESSAGE_SEED, GLOBAL_TREASURY], &crate::id());
+pub fn get_global_treasury_pda(program_id: &Pubkey) -> Pubkey {
+    let (pda, _) = Pubkey::find_program_address(&[MESSAGE_SEED, GLOBAL_TREASURY], program_id);
     pda
 }
 
-pub fn check_global_treasury_seeds(address: Pubkey) -> Result<u8, ProgramError> {
-    let (pda, bump) = Pubkey::find_program_address(&[MESSAGE_SEED, GLOBAL_TREASURY], &crate::id());
+pub fn check_global_treasury_seeds(
+    address: Pubkey,
+    program_id: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (pda, bump) = Pubkey::find_program_address(&[MESSAGE_SEED, GLOBAL_TREASURY], program_id);
 
     if pda != address {
-        return Err(ProgramError::InvalidSeeds.into());
+        return Err(MessengerError::InvalidAccountSeeds.into());
     }
 
     Ok(bump)
@@ -212,9 +250,91 @@ pub fn transfer_sol<'a, 'b>(
     Ok(())
 }
 
-pub fn get_message_pda(program_id: &Pubkey) -> Pubkey {
+/// Moves `lamports` out of `from`, which the gateway owns, so it can't go through the system
+/// program
+pub fn debit_lamports(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    **from.lamports.borrow_mut() = from
+        .lamports()
+        .checked_sub(lamports)
+        .ok_or(MessengerError::ArithmeticOverflow)?;
+    **to.lamports.borrow_mut() = to
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(MessengerError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+/// Closes `account`, which the gateway owns, moving its lamports to `to`. Returns the lamports
+/// moved.
+pub fn close_account(account: &AccountInfo, to: &AccountInfo) -> Result<u64, ProgramError> {
+    let lamports = account.lamports();
+
+    debit_lamports(account, to, lamports)?;
+
+    account.realloc(0, false)?;
+    account.assign(&system_program::ID);
+
+    Ok(lamports)
+}
+
+/// Creates the account at the address derived from `seeds` holding `state`
+pub fn create_state_account<'a, 'b, T: GatewayAccount>(
+    payer: &'a AccountInfo<'b>,
+    account: &'a AccountInfo<'b>,
+    system_program: &'a AccountInfo<'b>,
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+    state: &T,
+) -> ProgramResult {
+    let bump = check_seeds(account, seeds, program_id)?;
+
+    let data = state.pack()?;
+
+    let mut signer_seeds = seeds.to_vec();
+    let bump_seed = [bump];
+    signer_seeds.push(&bump_seed);
+
+    initialize_account(
+        payer,
+        account,
+        system_program,
+        data.len() as u64,
+        program_id,
+        &signer_seeds,
+    )?;
+
+    account.data.borrow_mut().copy_from_slice(&data);
+
+    Ok(())
+}
+
+/// Replaces the data of `account` with `data`, with `payer` funding the rent of any growth
+pub fn write_resized<'a, 'b>(
+    payer: &'a AccountInfo<'b>,
+    account: &'a AccountInfo<'b>,
+    system_program: &'a AccountInfo<'b>,
+    data: &[u8],
+) -> ProgramResult {
+    let data_diff = data.len().saturating_sub(account.data_len());
+
+    if data_diff > 0 {
+        let rent = Rent::default().minimum_balance(data_diff);
+
+        transfer_sol(payer, account, rent, system_program, None)?;
+    }
+
+    account.realloc(data.len(), false)?;
+
+    account.data.borrow_mut().copy_from_slice(data);
+
+    Ok(())
+}
+
+/// Message sent by `sender_program` through the gateway deployed at `program_id`
+pub fn get_message_pda(sender_program: &Pubkey, program_id: &Pubkey) -> Pubkey {
     let (message_key, _) =
-        Pubkey::find_program_address(&[MESSAGE_SEED, program_id.as_ref()], &crate::id());
+        Pubkey::find_program_address(&[MESSAGE_SEED, sender_program.as_ref()], program_id);
 
     message_key
 }
@@ -222,15 +342,15 @@ pub fn get_message_pda(program_id: &Pubkey) -> Pubkey {
 pub fn public_key_to_address(pub_key: &[u8]) -> [u8; 20] {
     let mut hasher = Keccak256::new();
 
-    if pub_key[0] == 4 {
-        hasher.update(&pub_key[1..]);
-    } else {
-        hasher.update(pub_key);
+    match pub_key.split_first() {
+        Some((4, key)) => hasher.update(key),
+        _ => hasher.update(pub_key),
     }
 
-    let result = hasher.finalize();
+    let mut address = [0; 20];
+    address.copy_from_slice(&hasher.finalize()[12..]);
 
-    result[12..32].try_into().unwrap()
+    address
 }
 
 pub fn keccak256(bytes: &[u8]) -> [u8; 32] {
@@ -257,9 +377,243 @@ pub fn create_ecdsa_sig(message: &Vec<u8>) -> [u8; 32] {
     hashed
 }
 
-pub fn get_message_client_pda(destination_contract: Pubkey) -> (Pubkey, u8) {
+/// Checks a 65 byte `r || s || v` signature over `hash` was made by the EVM address `expected`,
+/// stored left padded to 32 bytes. `v` may be either 0/1 or 27/28.
+pub fn verify_evm_signature(
+    hash: &[u8; 32],
+    signature: &[u8],
+    expected: &ForeignAddress,
+) -> Result<(), ProgramError> {
+    if signature.len() != 65 {
+        return Err(MessengerError::InvalidSignature.into());
+    }
+
+    let recovery_id = match signature[64] {
+        0 | 1 => signature[64],
+        27 | 28 => signature[64] - 27,
+        _ => return Err(MessengerError::InvalidSignature.into()),
+    };
+
+    let recovered = secp256k1_recover(hash, recovery_id, &signature[..64])
+        .map_err(|_| MessengerError::InvalidSignature)?;
+
+    if public_key_to_address(&recovered.0) != expected[12..] {
+        return Err(MessengerError::InvalidSignature.into());
+    }
+
+    Ok(())
+}
+
+/// Validator set stored at `raw_validators`, `None` while neither BLS nor Ed25519 verification is
+/// enabled
+pub fn load_validator_set(
+    raw_validators: &AccountInfo,
+    config: &MessengerConfig,
+    program_id: &Pubkey,
+) -> Result<Option<ValidatorSet>, ProgramError> {
+    if config.bls_enabled() || !raw_validators.data_is_empty() {
+        Ok(Some(load_account::<ValidatorSet>(
+            raw_validators,
+            program_id,
+            &[VALIDATORS_SEED],
+        )?))
+    } else {
+        check_seeds(raw_validators, &[VALIDATORS_SEED], program_id)?;
+
+        Ok(None)
+    }
+}
+
+/// Checks the validators signed `hash`: the Ed25519 validators in the instruction preceding the
+/// current one, and the BLS validators or the chainsig with `signature`. Fails when the gateway
+/// verifies none of them.
+pub fn verify_validator_signature(
+    config: &MessengerConfig,
+    validator_set: Option<&ValidatorSet>,
+    sysvar_instructions: &AccountInfo,
+    hash: &[u8; 32],
+    signature: &[u8],
+) -> ProgramResult {
+    let ed25519_validators = validator_set.filter(|set| set.ed25519_enabled());
+
+    if let Some(validators) = ed25519_validators {
+        validators.verify_ed25519(&ed25519::previous_instruction_signers(
+            sysvar_instructions,
+            hash,
+        )?)?;
+    }
+
+    if let Some(validators) = validator_set.filter(|_| config.bls_enabled()) {
+        let proof =
+            BlsProof::try_from_slice(signature).map_err(|_| MessengerError::InvalidSignature)?;
+
+        validators.verify(hash, &proof)?;
+    } else if let Some(chainsig) = config.chainsig() {
+        verify_evm_signature(hash, signature, &chainsig)?;
+    } else if ed25519_validators.is_none() {
+        return Err(MessengerError::InvalidSignature.into());
+    }
+
+    Ok(())
+}
+
+pub fn get_message_client_pda(destination_contract: Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[MESSAGE_CLIENT_SEED, destination_contract.as_ref()],
-        &crate::id(),
+        program_id,
     )
 }
+
+/// Reads the upgrade authority of `program` from its BPF upgradeable loader ProgramData account.
+/// Returns `None` for programs which were made immutable.
+pub fn get_program_upgrade_authority(
+    program: &AccountInfo,
+    program_data: &AccountInfo,
+) -> Result<Option<Pubkey>, ProgramError> {
+    if *program.owner != bpf_loader_upgradeable::id() {
+        return Err(MessengerError::InvalidClientProgramId.into());
+    }
+
+    //bincode layout: u32 enum tag followed by the variant fields
+    let program_account = program.data.borrow();
+
+    if program_account.len() < UpgradeableLoaderState::size_of_program()
+        || program_account[..4] != 2_u32.to_le_bytes()
+        || program_account[4..36] != program_data.key.to_bytes()
+    {
+        return Err(MessengerError::InvalidClientProgramId.into());
+    }
+
+    let program_data_account = program_data.data.borrow();
+
+    if program_data_account.len() < UpgradeableLoaderState::size_of_programdata_metadata()
+        || program_data_account[..4] != 3_u32.to_le_bytes()
+    {
+        return Err(MessengerError::InvalidClientProgramId.into());
+    }
+
+    //skip the tag and the last deployment slot
+    let upgrade_authority = match program_data_account[12] {
+        0 => None,
+        _ => Some(
+            Pubkey::try_from(&program_data_account[13..45])
+                .map_err(|_| MessengerError::InvalidClientProgramId)?,
+        ),
+    };
+
+    Ok(upgrade_authority)
+}
+
+/// Registry entry of `chain_id`
+pub fn get_chain_pda(chain_id: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CHAIN_SEED, &chain_id.to_le_bytes()], program_id)
+}
+
+pub fn get_validators_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VALIDATORS_SEED], program_id)
+}
+
+pub fn get_optimistic_config_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[OPTIMISTIC_SEED], program_id)
+}
+
+pub fn get_bond_pda(operator: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BOND_SEED, operator.as_ref()], program_id)
+}
+
+pub fn get_commitment_pda(tx_id: u128, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COMMITMENT_SEED, &tx_id.to_le_bytes()], program_id)
+}
+
+pub fn get_root_pda(source_chain_id: u64, batch: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ROOT_SEED,
+            &source_chain_id.to_le_bytes(),
+            &batch.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+pub fn get_accumulator_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ACCUMULATOR_SEED], program_id)
+}
+
+pub fn get_channel_pda(
+    source_chain_id: u64,
+    remote_sender: &ForeignAddress,
+    local_program: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            CHANNEL_SEED,
+            &source_chain_id.to_le_bytes(),
+            remote_sender,
+            local_program.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+pub fn get_chain_rate_limit_pda(chain_id: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CHAIN_RATE_LIMIT_SEED, &chain_id.to_le_bytes()],
+        program_id,
+    )
+}
+
+pub fn get_client_rate_limit_pda(
+    destination_contract: Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CLIENT_RATE_LIMIT_SEED, destination_contract.as_ref()],
+        program_id,
+    )
+}
+
+/// Takes one message of `bytes` length out of the bucket stored in `rate_limit_account`,
+/// creating the bucket on first use. Does nothing when no limit is configured.
+pub fn consume_rate_limit<'a, 'b>(
+    payer: &'a AccountInfo<'b>,
+    rate_limit_account: &'a AccountInfo<'b>,
+    system_program: &'a AccountInfo<'b>,
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+    limit: Option<&RateLimitConfig>,
+    bytes: u64,
+) -> ProgramResult {
+    let bump = check_seeds(rate_limit_account, seeds, program_id)?;
+
+    let limit = match limit {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let mut state = if rate_limit_account.data_is_empty() {
+        let mut signer_seeds = seeds.to_vec();
+        let bump_seed = [bump];
+        signer_seeds.push(&bump_seed);
+
+        initialize_account(
+            payer,
+            rate_limit_account,
+            system_program,
+            DISCRIMINATOR_LEN as u64 + RateLimitState::LEN,
+            program_id,
+            &signer_seeds,
+        )?;
+
+        RateLimitState::new(limit, now)
+    } else {
+        load_owned_account::<RateLimitState>(rate_limit_account, program_id)?
+    };
+
+    state.consume(limit, now, bytes)?;
+
+    store_account(rate_limit_account, &state)
+}
@@ -0,0 +1,90 @@
+use borsh::BorshSerialize;
+use mv3_contract_solana::{
+    constants::CONFIG_SEED,
+    error::MessengerError,
+    state::{
+        account::{load_account, GatewayAccount},
+        config::{MessageClient, MessengerConfig},
+    },
+};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+#[test]
+pub fn test_unpack_checks_discriminator() {
+    let owner = Pubkey::new_unique();
+
+    let mut data = MessengerConfig::new(&owner, &owner, 1).pack().unwrap();
+
+    //reallocated accounts keep trailing bytes
+    data.extend_from_slice(&[0; 16]);
+
+    assert_eq!(MessengerConfig::unpack(&data).unwrap().owner, owner);
+
+    assert_eq!(
+        MessageClient::unpack(&data).err(),
+        Some(MessengerError::InvalidAccountDiscriminator.into())
+    );
+
+    //accounts written before discriminators were introduced have to be migrated first
+    let legacy = MessengerConfig::new(&owner, &owner, 1)
+        .try_to_vec()
+        .unwrap();
+
+    assert!(MessengerConfig::unpack(&legacy).is_err());
+}
+
+#[test]
+pub fn test_load_account_checks_owner_and_seeds() {
+    let program_id = mv3_contract_solana::id();
+    let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
+
+    let owner = Pubkey::new_unique();
+    let mut data = MessengerConfig::new(&owner, &owner, 1).pack().unwrap();
+    let mut lamports = 0;
+
+    let account = AccountInfo::new(
+        &config,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        &program_id,
+        false,
+        0,
+    );
+
+    let loaded: MessengerConfig = load_account(&account, &program_id, &[CONFIG_SEED]).unwrap();
+
+    assert_eq!(loaded.owner, owner);
+
+    assert_eq!(
+        load_account::<MessengerConfig>(&account, &program_id, &[b"other"]).err(),
+        Some(MessengerError::InvalidAccountSeeds.into())
+    );
+
+    let impostor = Pubkey::new_unique();
+    let mut impostor_data = MessengerConfig::new(&impostor, &impostor, 1)
+        .pack()
+        .unwrap();
+    let mut impostor_lamports = 0;
+
+    //same address and layout, but owned by another program
+    let substituted = AccountInfo::new(
+        &config,
+        false,
+        false,
+        &mut impostor_lamports,
+        &mut impostor_data,
+        &impostor,
+        false,
+        0,
+    );
+
+    let result: Result<MessengerConfig, ProgramError> =
+        load_account(&substituted, &program_id, &[CONFIG_SEED]);
+
+    assert_eq!(
+        result.err(),
+        Some(MessengerError::InvalidAccountOwner.into())
+    );
+}
@@ -0,0 +1,87 @@
+use mv3_contract_solana::{
+    eip712::encode_uint,
+    error::MessengerError,
+    state::accumulator::{
+        hash_node, process_inclusion_proof, zero_hashes, OutboundAccumulator, OutboundDigest,
+        TREE_DEPTH,
+    },
+    utils::keccak256,
+};
+use solana_program::pubkey::Pubkey;
+
+fn leaf(i: u8) -> [u8; 32] {
+    keccak256(&[i])
+}
+
+#[test]
+pub fn test_append_and_root() {
+    let zero_hashes = zero_hashes();
+
+    let mut accumulator = OutboundAccumulator::new();
+
+    //the empty tree is the root of empty leaves
+    assert_eq!(
+        accumulator.root(),
+        hash_node(&zero_hashes[TREE_DEPTH - 1], &zero_hashes[TREE_DEPTH - 1])
+    );
+
+    for i in 0..3 {
+        assert_eq!(accumulator.append(leaf(i)), Ok(u64::from(i)));
+    }
+
+    let mut node = hash_node(
+        &hash_node(&leaf(0), &leaf(1)),
+        &hash_node(&leaf(2), &zero_hashes[0]),
+    );
+
+    for zero in &zero_hashes[2..] {
+        node = hash_node(&node, zero);
+    }
+
+    assert_eq!(accumulator.root(), node);
+
+    //the proof of the last leaf holds its left sibling and the empty subtrees
+    let mut proof = zero_hashes;
+    proof[1] = hash_node(&leaf(0), &leaf(1));
+
+    assert_eq!(process_inclusion_proof(&leaf(2), 2, &proof), node);
+    assert_ne!(process_inclusion_proof(&leaf(2), 3, &proof), node);
+}
+
+#[test]
+pub fn test_accumulator_full() {
+    let mut accumulator = OutboundAccumulator::new();
+    accumulator.count = (1 << TREE_DEPTH) - 2;
+
+    assert!(accumulator.append(leaf(0)).is_ok());
+    assert_eq!(
+        accumulator.append(leaf(1)),
+        Err(MessengerError::AccumulatorFull)
+    );
+}
+
+#[test]
+pub fn test_outbound_leaf_is_abi_encoded() {
+    let digest = OutboundDigest {
+        tx_id: 7,
+        source_chain_id: 19999999991,
+        dest_chain_id: 5,
+        sender: Pubkey::new_from_array([1; 32]),
+        recipient: [2; 32],
+        confirmations: 12,
+        gateway: mv3_contract_solana::id(),
+        payload_hash: keccak256(&[1, 2, 3]),
+    };
+
+    let mut encoded = vec![];
+    encoded.extend_from_slice(&encode_uint(7));
+    encoded.extend_from_slice(&encode_uint(19999999991));
+    encoded.extend_from_slice(&encode_uint(5));
+    encoded.extend_from_slice(&[1; 32]);
+    encoded.extend_from_slice(&[2; 32]);
+    encoded.extend_from_slice(&encode_uint(12));
+    encoded.extend_from_slice(&mv3_contract_solana::id().to_bytes());
+    encoded.extend_from_slice(&keccak256(&[1, 2, 3]));
+
+    assert_eq!(digest.leaf(), keccak256(&encoded));
+}
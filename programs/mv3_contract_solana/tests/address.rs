@@ -0,0 +1,124 @@
+use mv3_contract_solana::{
+    error::MessengerError,
+    state::{
+        address::{evm_checksum, TypedAddress},
+        chain::AddressFormat,
+        config::MessageClient,
+    },
+};
+use solana_program::pubkey::Pubkey;
+
+//test vectors of EIP-55
+const CHECKSUMMED: [&str; 4] = [
+    "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+    "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+    "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+    "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+];
+
+#[test]
+pub fn test_evm_checksum() {
+    for checksummed in CHECKSUMMED {
+        let address = TypedAddress::parse(AddressFormat::Evm, &checksummed.to_lowercase()).unwrap();
+
+        assert_eq!(address.to_string(), checksummed);
+
+        let TypedAddress::Evm(evm) = address else {
+            panic!("Not an EVM address!");
+        };
+
+        assert_eq!(evm_checksum(&evm), checksummed);
+        assert_eq!(
+            TypedAddress::parse(AddressFormat::Evm, checksummed),
+            Ok(address)
+        );
+    }
+
+    //a single flipped letter breaks the checksum
+    assert_eq!(
+        TypedAddress::parse(
+            AddressFormat::Evm,
+            "0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        ),
+        Err(MessengerError::InvalidForeignAddress)
+    );
+    assert_eq!(
+        TypedAddress::parse(
+            AddressFormat::Evm,
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea"
+        ),
+        Err(MessengerError::InvalidForeignAddress)
+    );
+}
+
+#[test]
+pub fn test_foreign_address_conversions() {
+    let evm = TypedAddress::Evm([0xab; 20]);
+    let foreign = evm.to_foreign();
+
+    assert_eq!(foreign[..12], [0; 12]);
+    assert_eq!(
+        TypedAddress::from_foreign(AddressFormat::Evm, &foreign),
+        Ok(evm)
+    );
+
+    //32 byte addresses do not fit EVM chains
+    assert_eq!(
+        TypedAddress::from_foreign(AddressFormat::Evm, &[1; 32]),
+        Err(MessengerError::InvalidForeignAddress)
+    );
+
+    let bytes32 = TypedAddress::from_foreign(AddressFormat::Bytes32, &[1; 32]).unwrap();
+
+    assert_eq!(bytes32.format(), AddressFormat::Bytes32);
+    assert_eq!(bytes32.to_string(), format!("0x{}", "01".repeat(32)));
+    assert_eq!(
+        TypedAddress::parse(AddressFormat::Bytes32, &bytes32.to_string()),
+        Ok(bytes32)
+    );
+
+    let pubkey = Pubkey::new_unique();
+    let solana = TypedAddress::from_foreign(AddressFormat::Solana, &pubkey.to_bytes()).unwrap();
+
+    assert_eq!(solana.to_string(), pubkey.to_string());
+    assert_eq!(
+        TypedAddress::parse(AddressFormat::Solana, &pubkey.to_string()),
+        Ok(solana)
+    );
+}
+
+#[test]
+pub fn test_check_allowed_contracts() {
+    let evm = TypedAddress::Evm([7; 20]).to_foreign();
+
+    let mut client = MessageClient {
+        version: MessageClient::VERSION,
+        authority: Pubkey::new_unique(),
+        destination_contract: Pubkey::new_unique(),
+        notify_on_failure: false,
+        supported_chains: vec![1, 2],
+        allowed_contracts: vec![evm, [9; 32]],
+        exsig: None,
+        paused: false,
+        pending_authority: None,
+        pending_exsig: None,
+        rate_limit: None,
+        optimistic: false,
+        ordered: false,
+    };
+
+    client
+        .check_allowed_contracts(&[AddressFormat::Evm, AddressFormat::Bytes32])
+        .unwrap();
+
+    assert_eq!(
+        client.check_allowed_contracts(&[AddressFormat::Evm]),
+        Err(MessengerError::InvalidForeignAddress)
+    );
+
+    client.allowed_contracts = vec![evm];
+
+    client
+        .check_allowed_contracts(&[AddressFormat::Evm])
+        .unwrap();
+}
@@ -0,0 +1,75 @@
+use mv3_contract_solana::{
+    error::MessengerError,
+    state::chain::{AddressFormat, ChainConfig, ChainFees},
+};
+
+#[test]
+pub fn test_chain_checks() {
+    let mut chain = ChainConfig::new(5, "evm", AddressFormat::Evm);
+    chain.min_confirmations = 3;
+
+    chain.check_inbound().unwrap();
+    chain.check_outbound(3).unwrap();
+
+    assert_eq!(
+        chain.check_outbound(2),
+        Err(MessengerError::InsufficientConfirmations)
+    );
+
+    chain.outbound_paused = true;
+
+    assert_eq!(
+        chain.check_outbound(3),
+        Err(MessengerError::DestinationChainPaused)
+    );
+    chain.check_inbound().unwrap();
+
+    chain.inbound_paused = true;
+
+    assert_eq!(
+        chain.check_inbound(),
+        Err(MessengerError::SourceChainPaused)
+    );
+
+    //disabling takes precedence over pauses
+    chain.enabled = false;
+
+    assert_eq!(
+        chain.check_inbound(),
+        Err(MessengerError::ChainNotSupported)
+    );
+    assert_eq!(
+        chain.check_outbound(3),
+        Err(MessengerError::ChainNotSupported)
+    );
+}
+
+#[test]
+pub fn test_chain_fees() {
+    let fees = ChainFees {
+        gas_limit: 200_000,
+        base_fee: 1_000,
+        fee_per_byte: 10,
+    };
+
+    assert_eq!(fees.message_fee(0), Ok(1_000));
+    assert_eq!(fees.message_fee(64), Ok(1_640));
+    assert_eq!(
+        fees.message_fee(u64::MAX),
+        Err(MessengerError::ArithmeticOverflow)
+    );
+}
+
+#[test]
+pub fn test_chain_name() {
+    ChainConfig::check_name("ethereum").unwrap();
+
+    assert_eq!(
+        ChainConfig::check_name(""),
+        Err(MessengerError::InvalidChainName)
+    );
+    assert_eq!(
+        ChainConfig::check_name(&"a".repeat(ChainConfig::MAX_NAME_LEN + 1)),
+        Err(MessengerError::InvalidChainName)
+    );
+}
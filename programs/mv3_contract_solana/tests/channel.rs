@@ -0,0 +1,45 @@
+use mv3_contract_solana::{
+    error::MessengerError,
+    state::channel::{payload_sequence, ChannelState},
+};
+use solana_program::pubkey::Pubkey;
+
+fn payload(program: &Pubkey, sequence: u64) -> Vec<u8> {
+    [program.as_ref(), &sequence.to_be_bytes(), b"transfer"].concat()
+}
+
+#[test]
+pub fn test_payload_sequence() {
+    let program = Pubkey::new_unique();
+
+    assert_eq!(payload_sequence(&payload(&program, 7)), Ok(7));
+    assert_eq!(
+        payload_sequence(&payload(&program, u64::MAX)[..40]),
+        Ok(u64::MAX)
+    );
+
+    //the sequence number has to follow the destination program in full
+    assert_eq!(
+        payload_sequence(&payload(&program, 7)[..39]),
+        Err(MessengerError::InvalidMessagePayload)
+    );
+}
+
+#[test]
+pub fn test_channel_advances_in_order() {
+    let mut channel = ChannelState::new(5, [1; 32], Pubkey::new_unique());
+
+    assert_eq!(channel.version, ChannelState::VERSION);
+    assert_eq!(channel.next_sequence, 0);
+
+    assert_eq!(channel.advance(1), Err(MessengerError::OutOfOrderMessage));
+
+    for sequence in 0..3 {
+        assert_eq!(channel.advance(sequence), Ok(()));
+    }
+
+    //replays and gaps leave the channel where it is
+    assert_eq!(channel.advance(2), Err(MessengerError::OutOfOrderMessage));
+    assert_eq!(channel.advance(4), Err(MessengerError::OutOfOrderMessage));
+    assert_eq!(channel.next_sequence, 3);
+}
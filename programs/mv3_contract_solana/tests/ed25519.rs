@@ -0,0 +1,131 @@
+use mv3_contract_solana::{
+    ed25519::{signers, verify_instruction, CURRENT_INSTRUCTION, OFFSETS_START},
+    error::MessengerError,
+    state::validators::ValidatorSet,
+    utils::keccak256,
+};
+use solana_program::{ed25519_program, pubkey::Pubkey};
+
+fn message() -> [u8; 32] {
+    keccak256(b"mv3 ed25519")
+}
+
+fn signatures(keys: &[Pubkey]) -> Vec<(Pubkey, [u8; 64])> {
+    keys.iter()
+        .enumerate()
+        .map(|(index, key)| (*key, [index as u8; 64]))
+        .collect()
+}
+
+fn validator_set(keys: &[Pubkey], threshold: u16) -> ValidatorSet {
+    let mut set = ValidatorSet::new();
+    set.set_ed25519_keys(keys.to_vec(), threshold).unwrap();
+
+    set
+}
+
+#[test]
+pub fn test_signers_of_verify_instruction() {
+    let keys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+
+    let instruction = verify_instruction(&signatures(&keys), &message());
+
+    assert_eq!(instruction.program_id, ed25519_program::ID);
+    assert!(instruction.accounts.is_empty());
+    assert_eq!(instruction.data[0], 3);
+
+    assert_eq!(signers(&instruction.data, &message()), Ok(keys));
+    assert_eq!(
+        signers(&instruction.data, &keccak256(b"other")),
+        Err(MessengerError::InvalidPreInstruction)
+    );
+
+    let empty = verify_instruction(&[], &message());
+
+    assert_eq!(signers(&empty.data, &message()), Ok(vec![]));
+    assert_eq!(
+        signers(&[], &message()),
+        Err(MessengerError::InvalidPreInstruction)
+    );
+}
+
+#[test]
+pub fn test_signers_rejects_foreign_data() {
+    let keys = [Pubkey::new_unique()];
+    let data = verify_instruction(&signatures(&keys), &message()).data;
+
+    //each of the signature, public key and message read from another instruction
+    for field in [1, 3, 6] {
+        let mut foreign = data.clone();
+        let offset = OFFSETS_START + 2 * field;
+        foreign[offset..offset + 2].copy_from_slice(&0u16.to_le_bytes());
+
+        assert_eq!(data[offset..offset + 2], CURRENT_INSTRUCTION.to_le_bytes());
+        assert_eq!(
+            signers(&foreign, &message()),
+            Err(MessengerError::InvalidPreInstruction)
+        );
+    }
+
+    //a count beyond the offsets
+    let mut truncated = data.clone();
+    truncated[0] = 12;
+
+    assert_eq!(
+        signers(&truncated, &message()),
+        Err(MessengerError::InvalidPreInstruction)
+    );
+
+    //a message shorter than the hash
+    let mut short = data;
+    short[OFFSETS_START + 10] = 31;
+
+    assert_eq!(
+        signers(&short, &message()),
+        Err(MessengerError::InvalidPreInstruction)
+    );
+}
+
+#[test]
+pub fn test_ed25519_threshold() {
+    let keys: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    let set = validator_set(&keys, 3);
+
+    assert!(set.ed25519_enabled());
+    assert_eq!(set.verify_ed25519(&keys[1..]), Ok(()));
+
+    //signers outside the set and repeated signers are not counted
+    assert_eq!(
+        set.verify_ed25519(&[keys[0], keys[1], keys[1], Pubkey::new_unique()]),
+        Err(MessengerError::Ed25519ThresholdNotMet)
+    );
+}
+
+#[test]
+pub fn test_ed25519_validator_set_checks() {
+    let keys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let mut set = ValidatorSet::new();
+
+    assert!(!set.ed25519_enabled());
+
+    for (keys, threshold) in [
+        (keys.clone(), 4),
+        (keys.clone(), 0),
+        (vec![], 1),
+        (vec![keys[0], keys[1], keys[0]], 2),
+        (vec![keys[0]; ValidatorSet::MAX_VALIDATORS + 1], 1),
+    ] {
+        assert_eq!(
+            set.set_ed25519_keys(keys, threshold),
+            Err(MessengerError::InvalidValidatorSet)
+        );
+    }
+
+    set.set_ed25519_keys(keys.clone(), 3).unwrap();
+
+    assert_eq!(set.ed25519_keys, keys);
+
+    set.set_ed25519_keys(vec![], 0).unwrap();
+
+    assert!(!set.ed25519_enabled());
+}
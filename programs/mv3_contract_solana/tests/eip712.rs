@@ -0,0 +1,145 @@
+use mv3_contract_solana::{
+    eip712::{domain_separator, encode_bytes, encode_uint, hash_struct, typed_data_hash},
+    instruction::MessageDigest,
+    state::{address::TypedAddress, chain::AddressFormat, config::DigestScheme},
+    utils::keccak256,
+};
+use solana_program::pubkey::Pubkey;
+
+fn evm(address: &str) -> [u8; 32] {
+    TypedAddress::parse(AddressFormat::Evm, address)
+        .unwrap()
+        .to_foreign()
+}
+
+fn hash(hex_hash: &str) -> [u8; 32] {
+    let mut hash = [0; 32];
+    hex::decode_to_slice(hex_hash, &mut hash).unwrap();
+
+    hash
+}
+
+//the Mail example of the EIP-712 specification
+#[test]
+pub fn test_eip712_specification_example() {
+    const PERSON_TYPE: &str = "Person(string name,address wallet)";
+
+    let person = |name: &str, wallet: &str| {
+        hash_struct(PERSON_TYPE, &[encode_bytes(name.as_bytes()), evm(wallet)])
+    };
+
+    let domain_separator = hash_struct(
+        "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)",
+        &[
+            encode_bytes(b"Ether Mail"),
+            encode_bytes(b"1"),
+            encode_uint(1),
+            evm("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"),
+        ],
+    );
+
+    assert_eq!(
+        domain_separator,
+        hash("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f")
+    );
+
+    let mail = hash_struct(
+        "Mail(Person from,Person to,string contents)Person(string name,address wallet)",
+        &[
+            person("Cow", "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"),
+            person("Bob", "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"),
+            encode_bytes(b"Hello, Bob!"),
+        ],
+    );
+
+    assert_eq!(
+        mail,
+        hash("c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e")
+    );
+
+    assert_eq!(
+        typed_data_hash(&domain_separator, &mail),
+        hash("be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2")
+    );
+}
+
+#[test]
+pub fn test_eip712_message_digest() {
+    let gateway = mv3_contract_solana::id();
+    let receiver = Pubkey::new_from_array([9; 32]);
+
+    let digest = MessageDigest::new(
+        1,
+        5,
+        19999999991,
+        evm("0x52908400098527886E0F7030069857D2E4169EE7"),
+        receiver,
+        gateway,
+        &[1, 2, 3],
+    );
+
+    let hashed = digest.hash(DigestScheme::Eip712);
+
+    assert_eq!(
+        hashed,
+        typed_data_hash(
+            &domain_separator(19999999991, &gateway),
+            &digest.struct_hash()
+        )
+    );
+
+    //vectors for the EVM signers
+    assert_eq!(
+        domain_separator(19999999991, &gateway),
+        hash("8e95cc85dab738956aa18f21ac5330f3cade85b1e4aae03b2fe4d7e6b9c437ab")
+    );
+    assert_eq!(
+        digest.struct_hash(),
+        hash("f12b869498971ffb59e0c52e75a79a513e4db870c898733232fbb261ac274770")
+    );
+    assert_eq!(
+        hashed,
+        hash("18307a75e3fa76b655ba796ff1301161b1a260a687a88e2c7d2e893cd048f11f")
+    );
+}
+
+#[test]
+pub fn test_digest_binds_delivery() {
+    let digest = MessageDigest::new(
+        1,
+        5,
+        19999999991,
+        [7; 32],
+        Pubkey::new_unique(),
+        mv3_contract_solana::id(),
+        &[1, 2, 3],
+    );
+
+    assert_eq!(digest.payload_hash, keccak256(&[1, 2, 3]));
+
+    let other_receiver = MessageDigest {
+        receiver: Pubkey::new_unique(),
+        ..digest.clone()
+    };
+
+    let other_gateway = MessageDigest {
+        gateway: Pubkey::new_unique(),
+        ..digest.clone()
+    };
+
+    let other_payload = MessageDigest {
+        payload_hash: keccak256(&[1, 2, 4]),
+        ..digest.clone()
+    };
+
+    for scheme in [DigestScheme::PersonalSign, DigestScheme::Eip712] {
+        for other in [&other_receiver, &other_gateway, &other_payload] {
+            assert_ne!(digest.hash(scheme), other.hash(scheme));
+        }
+    }
+
+    assert_ne!(
+        digest.hash(DigestScheme::PersonalSign),
+        digest.hash(DigestScheme::Eip712)
+    );
+}
@@ -0,0 +1,50 @@
+use mv3_contract_solana::{error::MessengerError, state::message::MessagePayload};
+use solana_program::program_error::ProgramError;
+
+#[test]
+pub fn test_error_codes_are_stable() {
+    //codes already returned by deployed programs
+    assert_eq!(MessengerError::PublicKeyMissmatch.code(), 0);
+    assert_eq!(MessengerError::InvalidSignature.code(), 14);
+    assert_eq!(MessengerError::MessageAlreadyProcessed.code(), 19);
+    assert_eq!(MessengerError::RateLimitExceeded.code(), 29);
+    assert_eq!(MessengerError::UnsupportedAccountVersion.code(), 33);
+
+    assert_eq!(
+        ProgramError::from(MessengerError::ArithmeticOverflow),
+        ProgramError::Custom(36)
+    );
+}
+
+#[test]
+pub fn test_error_codes_round_trip() {
+    let mut code = 0;
+
+    while let Some(error) = MessengerError::from_code(code) {
+        assert_eq!(error.code(), code);
+        assert_eq!(ProgramError::from(error), ProgramError::Custom(code));
+        assert!(error.to_string().starts_with("MessageV3: "));
+        code += 1;
+    }
+
+    assert!(code > 0, "No error codes!");
+
+    //codes are assigned without gaps, so none follows the first unknown one
+    for code in code..code + 256 {
+        assert_eq!(
+            MessengerError::from_code(code),
+            None,
+            "Gap in the error codes!"
+        );
+    }
+
+    assert_eq!(MessengerError::from_code(u32::MAX), None);
+}
+
+#[test]
+pub fn test_malformed_input_is_an_error() {
+    assert_eq!(
+        MessagePayload::unpack(1, [0; 32], &[7; 31]).err(),
+        Some(MessengerError::InvalidMessagePayload.into())
+    );
+}
@@ -0,0 +1,64 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use borsh::BorshSerialize;
+use mv3_contract_solana::events::{parse_events_from_logs, GatewayEvent, EVENT_TAG};
+use solana_program::pubkey::Pubkey;
+
+fn data_log(event: &GatewayEvent) -> String {
+    format!(
+        "Program data: {} {}",
+        STANDARD.encode(EVENT_TAG),
+        STANDARD.encode(event.try_to_vec().unwrap())
+    )
+}
+
+#[test]
+pub fn test_parse_gateway_events() {
+    let gateway = mv3_contract_solana::id();
+    let receiver = Pubkey::new_unique();
+
+    let verified = GatewayEvent::MessageVerified {
+        tx_id: 1,
+        source_chain_id: 5,
+        sender: [7; 32],
+        receiver,
+    };
+    let executed = GatewayEvent::MessageExecuted { tx_id: 1, receiver };
+
+    //events logged by the receiver with the same layout must be ignored
+    let spoofed = GatewayEvent::FeeCollected {
+        destination_contract: receiver,
+        amount: 1,
+    };
+
+    let logs = vec![
+        format!("Program {} invoke [1]", gateway),
+        data_log(&verified),
+        format!("Program {} invoke [2]", receiver),
+        data_log(&spoofed),
+        format!("Program {} success", receiver),
+        data_log(&executed),
+        format!("Program {} success", gateway),
+    ];
+
+    let events = parse_events_from_logs(&gateway, &logs);
+
+    assert_eq!(events, vec![verified, executed]);
+}
+
+#[test]
+pub fn test_parse_skips_foreign_data() {
+    let gateway = mv3_contract_solana::id();
+
+    let logs = vec![
+        format!("Program {} invoke [1]", gateway),
+        "Program log: Instruction: ReceiveMessage".to_string(),
+        format!(
+            "Program data: {} {}",
+            STANDARD.encode(b"other-tag"),
+            STANDARD.encode([1, 2, 3])
+        ),
+        format!("Program {} failed: custom program error: 0x1", gateway),
+    ];
+
+    assert!(parse_events_from_logs(&gateway, &logs).is_empty());
+}
@@ -0,0 +1,297 @@
+use message_hook::idl::{Idl, IdlInstruction};
+use mv3_contract_solana::{
+    constants::SOLANA_CHAIN_ID,
+    instruction::{
+        accept_client_authority, add_user_permission, challenge_commitment, change_config,
+        close_client, configure_client, deposit_withdraw_bond, deposit_withdraw_sol,
+        dispute_commitment, initialize_config, migrate_account, migrate_config, post_commitment,
+        post_message_root, receive_batched_message, receive_message, register_chain,
+        set_client_optimistic, set_client_ordered, set_exsig, set_optimistic_config, set_pause,
+        set_rate_limit, settle_dispute, transfer_client_authority, AddUserPermission, BatchProof,
+        ChangeConfig, DepositWithdraw, FraudEvidence, PauseTarget, PostCommitment, PostMessageRoot,
+        RateLimitTarget, ReceiveMessage, RegisterChain, SetExsig, SetOptimisticConfig, SetPause,
+        SetRateLimit, TransferClientAuthority,
+    },
+    state::{
+        account::AccountKind,
+        chain::{AddressFormat, ChainFees},
+        config::{MessageClient, Role},
+    },
+};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+
+/// Compares `idl` with its exported JSON, `UPDATE_IDL=1 cargo test` regenerates the files
+fn check_exported(idl: &Idl) {
+    let path = format!("{}/../../idl/{}.json", env!("CARGO_MANIFEST_DIR"), idl.name);
+
+    if std::env::var("UPDATE_IDL").is_ok() {
+        std::fs::write(&path, idl.to_json()).unwrap();
+    }
+
+    let exported = std::fs::read_to_string(&path).unwrap_or_default();
+
+    assert!(
+        exported == idl.to_json(),
+        "{} is outdated, run tests with UPDATE_IDL=1",
+        path
+    );
+}
+
+fn check_instruction(idl: &Idl, ix: Instruction) {
+    let described: &IdlInstruction = idl
+        .instructions
+        .iter()
+        .find(|described| ix.data.starts_with(&described.discriminator))
+        .unwrap();
+
+    let required = described
+        .accounts
+        .iter()
+        .filter(|account| !account.optional)
+        .count();
+
+    assert!(
+        ix.accounts.len() >= required && ix.accounts.len() <= described.accounts.len(),
+        "Account count of {} does not match",
+        described.name
+    );
+
+    for (account, meta) in described.accounts.iter().zip(ix.accounts.iter()) {
+        assert!(
+            account.matches(meta),
+            "{}: {} does not match",
+            described.name,
+            account.name
+        );
+    }
+}
+
+#[test]
+pub fn test_idl_exported() {
+    check_exported(&mv3_contract_solana::idl::idl());
+    check_exported(&mv3_solana_sender::idl::idl());
+    check_exported(&message_hook::idl::idl());
+}
+
+#[test]
+pub fn test_idl_matches_builders() {
+    let idl = mv3_contract_solana::idl::idl();
+
+    let program_id = mv3_contract_solana::id();
+    let payer = Pubkey::new_unique();
+    let destination_contract = Pubkey::new_unique();
+
+    let client = MessageClient {
+        version: MessageClient::VERSION,
+        authority: payer,
+        destination_contract,
+        notify_on_failure: false,
+        supported_chains: vec![],
+        allowed_contracts: vec![],
+        exsig: None,
+        paused: false,
+        pending_authority: None,
+        pending_exsig: None,
+        rate_limit: None,
+        optimistic: false,
+        ordered: false,
+    };
+
+    let instructions = vec![
+        initialize_config(payer, SOLANA_CHAIN_ID, payer, &program_id),
+        add_user_permission(
+            program_id,
+            payer,
+            AddUserPermission {
+                user: payer,
+                is_active: true,
+                role: Role::Super,
+            },
+        ),
+        change_config(
+            program_id,
+            payer,
+            ChangeConfig {
+                bridge_enabled: None,
+                accountant: None,
+                whitelist_only: None,
+                chainsig: None,
+                digest_scheme: None,
+            },
+        ),
+        set_exsig(
+            program_id,
+            payer,
+            destination_contract,
+            SetExsig {
+                exsig: None,
+                activation_delay: 0,
+            },
+        ),
+        configure_client(program_id, payer, client),
+        receive_message(
+            &program_id,
+            ReceiveMessage {
+                tx_id: 1,
+                dest_chain_id: 1,
+                receiver: destination_contract,
+                data: vec![],
+                source_chain_id: 5,
+                sender: [0; 32],
+            },
+            payer,
+        ),
+        deposit_withdraw_sol(
+            program_id,
+            payer,
+            destination_contract,
+            1,
+            DepositWithdraw::Deposit,
+        ),
+        set_pause(
+            program_id,
+            payer,
+            SetPause {
+                target: PauseTarget::Inbound,
+                paused: true,
+            },
+        ),
+        set_pause(
+            program_id,
+            payer,
+            SetPause {
+                target: PauseTarget::SourceChain(5),
+                paused: true,
+            },
+        ),
+        set_pause(
+            program_id,
+            payer,
+            SetPause {
+                target: PauseTarget::Client(destination_contract),
+                paused: true,
+            },
+        ),
+        transfer_client_authority(
+            program_id,
+            payer,
+            TransferClientAuthority {
+                destination_contract,
+                new_authority: None,
+            },
+        ),
+        accept_client_authority(program_id, payer, destination_contract),
+        close_client(program_id, payer, destination_contract),
+        set_rate_limit(
+            program_id,
+            payer,
+            SetRateLimit {
+                target: RateLimitTarget::Client(destination_contract),
+                limit: None,
+            },
+        ),
+        migrate_account(program_id, payer, payer, AccountKind::Config),
+        migrate_account(
+            program_id,
+            payer,
+            destination_contract,
+            AccountKind::MessageClient,
+        ),
+        migrate_config(program_id, payer, &[5], &[destination_contract]),
+        register_chain(
+            program_id,
+            payer,
+            RegisterChain {
+                chain_id: 5,
+                name: "evm".to_string(),
+                address_format: AddressFormat::Evm,
+                min_confirmations: 1,
+                fees: ChainFees::default(),
+                enabled: true,
+            },
+        ),
+        set_optimistic_config(
+            program_id,
+            payer,
+            SetOptimisticConfig {
+                challenge_period: 60,
+                min_bond: 1,
+                watchers: vec![payer],
+            },
+        ),
+        set_client_optimistic(program_id, payer, destination_contract, true),
+        deposit_withdraw_bond(program_id, payer, 1, DepositWithdraw::Deposit),
+        post_commitment(
+            program_id,
+            payer,
+            PostCommitment {
+                tx_id: 1,
+                receiver: destination_contract,
+                hash: [0; 32],
+            },
+        ),
+        challenge_commitment(
+            program_id,
+            payer,
+            payer,
+            1,
+            FraudEvidence {
+                source_chain_id: 5,
+                sender: [0; 32],
+                receiver: destination_contract,
+                payload_hash: [0; 32],
+                signature: vec![],
+            },
+        ),
+        post_message_root(
+            program_id,
+            payer,
+            PostMessageRoot {
+                source_chain_id: 5,
+                batch: 1,
+                root: [0; 32],
+                signature: vec![],
+            },
+        ),
+        receive_batched_message(
+            &program_id,
+            ReceiveMessage {
+                tx_id: 1,
+                dest_chain_id: 1,
+                receiver: destination_contract,
+                data: vec![],
+                source_chain_id: 5,
+                sender: [0; 32],
+            },
+            BatchProof {
+                batch: 1,
+                proof: vec![],
+            },
+            payer,
+        ),
+        set_client_ordered(program_id, payer, destination_contract, true),
+        dispute_commitment(program_id, payer, payer, 1),
+        settle_dispute(program_id, payer, 1, None),
+    ];
+
+    for ix in instructions {
+        check_instruction(&idl, ix);
+    }
+
+    check_instruction(
+        &mv3_solana_sender::idl::idl(),
+        mv3_solana_sender::instructions::send_message(
+            mv3_solana_sender::id(),
+            &payer,
+            &destination_contract,
+            5,
+            [0; 32],
+            vec![1, 2, 3],
+        ),
+    );
+
+    check_instruction(
+        &message_hook::idl::idl(),
+        message_hook::instruction::execute(&destination_contract, &payer, &payer, vec![]),
+    );
+}
@@ -0,0 +1,80 @@
+use mv3_contract_solana::{
+    instruction::RootDigest,
+    merkle::{hash_pair, proof, root, verify},
+    state::config::DigestScheme,
+    utils::keccak256,
+};
+
+fn leaves(count: u8) -> Vec<[u8; 32]> {
+    (0..count).map(|i| keccak256(&[i])).collect()
+}
+
+#[test]
+pub fn test_proofs_of_every_leaf() {
+    assert_eq!(root(&[]), None);
+
+    for count in 1..=9 {
+        let leaves = leaves(count);
+        let root = root(&leaves).unwrap();
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = proof(&leaves, index).unwrap();
+
+            assert!(verify(leaf, &proof, &root), "leaf {} of {}", index, count);
+        }
+
+        assert_eq!(proof(&leaves, leaves.len()), None);
+    }
+
+    //a single leaf is its own root
+    assert_eq!(root(&leaves(1)), Some(leaves(1)[0]));
+}
+
+#[test]
+pub fn test_rejected_proofs() {
+    let leaves = leaves(5);
+    let root = root(&leaves).unwrap();
+
+    let proof = proof(&leaves, 2).unwrap();
+
+    assert!(!verify(&keccak256(b"other"), &proof, &root));
+    assert!(!verify(&leaves[2], &proof[1..], &root));
+    assert!(!verify(&leaves[3], &proof, &root));
+
+    //pairs are hashed in sorted order, as OpenZeppelin's MerkleProof does
+    assert_eq!(
+        hash_pair(&leaves[0], &leaves[1]),
+        hash_pair(&leaves[1], &leaves[0])
+    );
+}
+
+#[test]
+pub fn test_root_digest_binds_batch() {
+    let digest = RootDigest {
+        source_chain_id: 5,
+        dest_chain_id: 19999999991,
+        batch: 1,
+        root: [4; 32],
+        gateway: mv3_contract_solana::id(),
+    };
+
+    for scheme in [DigestScheme::PersonalSign, DigestScheme::Eip712] {
+        let other_batch = RootDigest {
+            batch: 2,
+            ..digest.clone()
+        };
+
+        let other_chain = RootDigest {
+            source_chain_id: 6,
+            ..digest.clone()
+        };
+
+        assert_ne!(digest.hash(scheme), other_batch.hash(scheme));
+        assert_ne!(digest.hash(scheme), other_chain.hash(scheme));
+    }
+
+    assert_ne!(
+        digest.hash(DigestScheme::PersonalSign),
+        digest.hash(DigestScheme::Eip712)
+    );
+}
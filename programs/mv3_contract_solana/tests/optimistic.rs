@@ -0,0 +1,105 @@
+use mv3_contract_solana::{
+    error::MessengerError,
+    state::optimistic::{CommitmentDispute, MessageCommitment, OptimisticConfig},
+};
+use solana_program::pubkey::Pubkey;
+
+fn commitment(executable_at: i64) -> MessageCommitment {
+    MessageCommitment {
+        version: MessageCommitment::VERSION,
+        operator: Pubkey::new_unique(),
+        tx_id: 3,
+        receiver: Pubkey::new_unique(),
+        hash: [5; 32],
+        executable_at,
+        challenged: false,
+        dispute: None,
+    }
+}
+
+#[test]
+pub fn test_check_optimistic_config() {
+    let watcher = Pubkey::new_unique();
+
+    let config = OptimisticConfig::new(600, 1_000, vec![watcher]);
+
+    assert_eq!(config.check(), Ok(()));
+    assert!(config.is_watcher(&watcher));
+    assert!(!config.is_watcher(&Pubkey::new_unique()));
+
+    for invalid in [
+        OptimisticConfig::new(0, 1_000, vec![watcher]),
+        OptimisticConfig::new(600, 0, vec![watcher]),
+        OptimisticConfig::new(600, 1_000, vec![]),
+        OptimisticConfig::new(600, 1_000, vec![watcher, watcher]),
+        OptimisticConfig::new(
+            600,
+            1_000,
+            (0..=OptimisticConfig::MAX_WATCHERS)
+                .map(|_| Pubkey::new_unique())
+                .collect(),
+        ),
+    ] {
+        assert_eq!(
+            invalid.check(),
+            Err(MessengerError::InvalidOptimisticConfig)
+        );
+    }
+}
+
+#[test]
+pub fn test_commitment_challenge_window() {
+    let mut commitment = commitment(100);
+
+    //watchers can challenge until the commitment is executable
+    assert_eq!(commitment.check_challengeable(99), Ok(()));
+    assert_eq!(
+        commitment.check_challengeable(100),
+        Err(MessengerError::ChallengePeriodEnded)
+    );
+
+    assert_eq!(
+        commitment.check_executable(&[5; 32], 99),
+        Err(MessengerError::ChallengePeriodActive)
+    );
+    assert_eq!(commitment.check_executable(&[5; 32], 100), Ok(()));
+    assert_eq!(
+        commitment.check_executable(&[6; 32], 100),
+        Err(MessengerError::InvalidSignature)
+    );
+
+    //a challenged commitment blocks the message until another operator replaces it
+    commitment.challenged = true;
+
+    for hash in [[5; 32], [6; 32]] {
+        assert_eq!(
+            commitment.check_executable(&hash, 100),
+            Err(MessengerError::CommitmentChallenged)
+        );
+    }
+    assert_eq!(
+        commitment.check_challengeable(99),
+        Err(MessengerError::CommitmentChallenged)
+    );
+}
+
+#[test]
+pub fn test_disputed_commitment_blocks_execution() {
+    let mut commitment = commitment(100);
+
+    commitment.dispute = Some(CommitmentDispute {
+        watcher: Pubkey::new_unique(),
+        ends_at: 150,
+    });
+
+    //the claim of the watcher holds the message past the challenge period until it is settled
+    assert_eq!(
+        commitment.check_executable(&[5; 32], 200),
+        Err(MessengerError::CommitmentDisputed)
+    );
+    assert_eq!(commitment.check_challengeable(99), Ok(()));
+
+    commitment.dispute = None;
+
+    assert_eq!(commitment.check_executable(&[5; 32], 200), Ok(()));
+}
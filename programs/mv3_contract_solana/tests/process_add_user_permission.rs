@@ -4,10 +4,14 @@ mod utils;
 use std::{assert, println};
 
 use mv3_contract_solana::{
+    constants::SOLANA_CHAIN_ID,
     instruction::{add_user_permission, initialize_config, AddUserPermission},
-    state::config::{MessengerConfig, Role},
+    state::{
+        account::GatewayAccount,
+        config::{Role, RoleRegistry},
+    },
 };
-use solana_program::{borsh0_10::try_from_slice_unchecked, pubkey::Pubkey};
+use solana_program::pubkey::Pubkey;
 
 use crate::utils::ProgramTestBench;
 use solana_program_test::{tokio, ProgramTestContext};
@@ -15,7 +19,12 @@ use solana_program_test::{tokio, ProgramTestContext};
 pub async fn test_add_user_permissions() {
     let mut test = ProgramTestBench::start_impl().await;
 
-    let ix = initialize_config(test.payer_pk, test.payer_pk, &test.program_id);
+    let ix = initialize_config(
+        test.payer_pk,
+        SOLANA_CHAIN_ID,
+        test.payer_pk,
+        &test.program_id,
+    );
 
     let whitelist = Pubkey::new_unique();
     let whitelist2 = Pubkey::new_unique();
@@ -109,37 +118,37 @@ pub async fn test_add_user_permissions() {
 
     let raw_account = test
         .client
-        .get_account(ix.accounts[1].clone().pubkey)
+        .get_account(ix.accounts[3].clone().pubkey)
         .await
         .unwrap()
         .unwrap();
 
-    let config = try_from_slice_unchecked::<MessengerConfig>(&raw_account.data).unwrap();
+    let roles = RoleRegistry::unpack(&raw_account.data).unwrap();
 
     assert!(
-        config.whitelists.get(0).unwrap().wallet == whitelist,
+        roles.whitelists.get(0).unwrap().wallet == whitelist,
         "Invalid first whitelist!"
     );
 
     assert!(
-        config.whitelists.get(1).unwrap().wallet == whitelist2,
+        roles.whitelists.get(1).unwrap().wallet == whitelist2,
         "Invalid first whitelist!"
     );
 
     assert!(
-        config.bridge_a_team.get(0).unwrap().wallet == a_team
-            && !config.bridge_a_team.get(0).unwrap().is_active,
+        roles.bridge_a_team.get(0).unwrap().wallet == a_team
+            && !roles.bridge_a_team.get(0).unwrap().is_active,
         "Invalid first whitelist!"
     );
 
     assert!(
-        config.bridge_supers.get(0).unwrap().wallet == super_1,
+        roles.bridge_supers.get(0).unwrap().wallet == super_1,
         "Invalid super1"
     );
 
     assert!(
-        config.bridge_supers.get(1).unwrap().wallet == super_2
-            && !config.bridge_supers.get(1).unwrap().is_active,
+        roles.bridge_supers.get(1).unwrap().wallet == super_2
+            && !roles.bridge_supers.get(1).unwrap().is_active,
         "Invalid super2"
     );
 }
@@ -1,12 +1,16 @@
 #![cfg(feature = "test-sbf")]
 
 mod utils;
+use std::assert;
 use std::option::Option::Some;
-use std::{assert, println};
 
+use mv3_contract_solana::constants::SOLANA_CHAIN_ID;
 use mv3_contract_solana::instruction::{change_config, initialize_config, ChangeConfig};
-use mv3_contract_solana::state::config::MessengerConfig;
-use solana_program::{borsh0_10::try_from_slice_unchecked, pubkey::Pubkey};
+use mv3_contract_solana::state::{
+    account::GatewayAccount,
+    config::{DigestScheme, MessengerConfig},
+};
+use solana_program::pubkey::Pubkey;
 
 use crate::utils::ProgramTestBench;
 use solana_program_test::tokio;
@@ -14,7 +18,12 @@ use solana_program_test::tokio;
 pub async fn test_change_config() {
     let mut test = ProgramTestBench::start_impl().await;
 
-    let init_ix = initialize_config(test.payer_pk, test.payer_pk, &test.program_id);
+    let init_ix = initialize_config(
+        test.payer_pk,
+        SOLANA_CHAIN_ID,
+        test.payer_pk,
+        &test.program_id,
+    );
 
     let new_accountant = Pubkey::new_unique();
 
@@ -22,11 +31,11 @@ pub async fn test_change_config() {
         test.program_id,
         test.payer_pk,
         ChangeConfig {
-            enabled_chains: Some(vec![2, 3, 4]),
             bridge_enabled: None,
             accountant: Some(new_accountant),
             whitelist_only: Some(true),
             chainsig: None,
+            digest_scheme: Some(DigestScheme::Eip712),
         },
     );
 
@@ -41,13 +50,16 @@ pub async fn test_change_config() {
         .unwrap()
         .unwrap();
 
-    let config = try_from_slice_unchecked::<MessengerConfig>(&raw_account.data).unwrap();
+    let config = MessengerConfig::unpack(&raw_account.data).unwrap();
 
     assert!(config.accountant == new_accountant, "Invalid accountant!");
 
-    assert!(config.enabled_chains == [2, 3, 4], "Invalid chains!");
+    assert!(config.whitelist_only(), "Whitelist not enabled!");
 
-    assert!(config.whitelist_only, "Whitelist not enabled!");
+    assert!(config.bridge_enabled(), "Bridge in invalid state!");
 
-    assert!(config.bridge_enabled, "Bridge in invalid state!");
+    assert!(
+        config.digest_scheme() == DigestScheme::Eip712,
+        "Digest scheme not changed!"
+    );
 }
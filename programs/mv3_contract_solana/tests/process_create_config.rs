@@ -3,8 +3,11 @@
 mod utils;
 use std::assert;
 
-use mv3_contract_solana::state::config::MessengerConfig;
-use solana_program::{borsh0_10::try_from_slice_unchecked, pubkey::Pubkey};
+use mv3_contract_solana::state::{
+    account::GatewayAccount,
+    config::{MessengerConfig, RoleRegistry},
+};
+use solana_program::pubkey::Pubkey;
 
 use crate::utils::ProgramTestBench;
 use solana_program_test::tokio;
@@ -17,6 +20,7 @@ pub async fn test_create_config() {
 
     let ix = mv3_contract_solana::instruction::initialize_config(
         accountant,
+        7,
         test.payer_pk,
         &test.program_id,
     );
@@ -26,7 +30,7 @@ pub async fn test_create_config() {
 
     let raw_config_info = test.client.get_account(acc.pubkey).await.unwrap().unwrap();
 
-    let config = try_from_slice_unchecked::<MessengerConfig>(&raw_config_info.data).unwrap();
+    let config = MessengerConfig::unpack(&raw_config_info.data).unwrap();
 
     assert!(
         config.accountant == accountant,
@@ -35,7 +39,24 @@ pub async fn test_create_config() {
 
     assert!(config.owner == test.payer_pk, "Invalid owner PK!");
 
-    assert!(config.bridge_enabled, "Bridge not enabled!");
+    assert!(config.chain_id() == 7, "Invalid chain id!");
 
-    assert!(!config.whitelist_only, "Whitelist enabled!");
+    assert!(config.bridge_enabled(), "Bridge not enabled!");
+
+    assert!(!config.whitelist_only(), "Whitelist enabled!");
+
+    let raw_roles = test
+        .client
+        .get_account(ix.accounts[3].pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(
+        RoleRegistry::unpack(&raw_roles.data)
+            .unwrap()
+            .whitelists
+            .is_empty(),
+        "Roles not created!"
+    );
 }
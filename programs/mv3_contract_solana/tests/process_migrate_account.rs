@@ -0,0 +1,209 @@
+#![cfg(feature = "test-sbf")]
+
+mod utils;
+use std::assert;
+
+use borsh::BorshSerialize;
+use mv3_contract_solana::{
+    constants::{CONFIG_SEED, MESSAGE_CLIENT_SEED},
+    instruction::{migrate_account, RateLimitTarget},
+    state::{
+        account::{AccountKind, GatewayAccount},
+        config::{MessageClient, MessengerConfig},
+        rate_limit::{RateLimitConfig, RateLimitState},
+        version::MessageClientV0,
+    },
+    utils::get_client_rate_limit_pda,
+};
+use solana_program::pubkey::Pubkey;
+use solana_sdk::{account::Account, signature::Keypair, signer::Signer};
+
+use crate::utils::ProgramTestBench;
+use solana_program_test::tokio;
+
+fn program_account(data: Vec<u8>) -> Account {
+    Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: mv3_contract_solana::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+#[tokio::test]
+pub async fn test_migrate_legacy_client() {
+    let owner = Keypair::new();
+    let program_id = mv3_contract_solana::id();
+    let destination_contract = Pubkey::new_unique();
+
+    let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
+    let (client, _) = Pubkey::find_program_address(
+        &[MESSAGE_CLIENT_SEED, destination_contract.as_ref()],
+        &program_id,
+    );
+
+    let legacy = MessageClientV0 {
+        authority: owner.pubkey(),
+        destination_contract,
+        notify_on_failure: true,
+        supported_chains: vec![1],
+        allowed_contracts: vec![],
+        exsig: None,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let mut test = ProgramTestBench::start_with_accounts(vec![
+        (
+            owner.pubkey(),
+            Account {
+                lamports: 1_000_000_000,
+                ..Account::default()
+            },
+        ),
+        (
+            config,
+            program_account(
+                MessengerConfig::new(&owner.pubkey(), &owner.pubkey(), 1)
+                    .pack()
+                    .unwrap(),
+            ),
+        ),
+        (client, program_account(legacy)),
+    ])
+    .await;
+
+    let ix = migrate_account(
+        program_id,
+        owner.pubkey(),
+        client,
+        AccountKind::MessageClient,
+    );
+
+    //only the config owner can migrate
+    let intruder = Keypair::new();
+    let intruder_ix = migrate_account(
+        program_id,
+        intruder.pubkey(),
+        client,
+        AccountKind::MessageClient,
+    );
+
+    assert!(test
+        .process_transaction_with_signers(&[intruder_ix], &[&intruder])
+        .await
+        .is_err());
+
+    //configs are split into several accounts by `MigrateConfig` instead
+    assert!(test
+        .process_transaction_with_signers(
+            &[migrate_account(
+                program_id,
+                owner.pubkey(),
+                config,
+                AccountKind::Config
+            )],
+            &[&owner]
+        )
+        .await
+        .is_err());
+
+    test.process_transaction_with_signers(&[ix.clone()], &[&owner])
+        .await
+        .unwrap();
+
+    let raw_account = test.client.get_account(client).await.unwrap().unwrap();
+
+    let migrated = MessageClient::unpack(&raw_account.data).unwrap();
+
+    assert!(
+        migrated.authority == owner.pubkey() && migrated.notify_on_failure,
+        "Client lost in migration!"
+    );
+
+    //a second migration would corrupt the account
+    test.context.warp_to_slot(2).unwrap();
+
+    assert!(test
+        .process_transaction_with_signers(&[ix], &[&owner])
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+pub async fn test_migrate_legacy_rate_limit() {
+    let owner = Keypair::new();
+    let program_id = mv3_contract_solana::id();
+    let destination_contract = Pubkey::new_unique();
+
+    let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
+    let (bucket, _) = get_client_rate_limit_pda(destination_contract, &program_id);
+
+    let state = RateLimitState::new(
+        &RateLimitConfig {
+            max_messages: 10,
+            max_bytes: 1_000,
+            window: 60,
+        },
+        0,
+    );
+
+    let mut test = ProgramTestBench::start_with_accounts(vec![
+        (
+            owner.pubkey(),
+            Account {
+                lamports: 1_000_000_000,
+                ..Account::default()
+            },
+        ),
+        (
+            config,
+            program_account(
+                MessengerConfig::new(&owner.pubkey(), &owner.pubkey(), 1)
+                    .pack()
+                    .unwrap(),
+            ),
+        ),
+        (bucket, program_account(state.try_to_vec().unwrap())),
+    ])
+    .await;
+
+    //the target names the address of the bucket, any other account is turned away
+    for target in [
+        RateLimitTarget::Chain(1),
+        RateLimitTarget::Client(Pubkey::new_unique()),
+    ] {
+        assert!(test
+            .process_transaction_with_signers(
+                &[migrate_account(
+                    program_id,
+                    owner.pubkey(),
+                    bucket,
+                    AccountKind::RateLimit(target)
+                )],
+                &[&owner]
+            )
+            .await
+            .is_err());
+    }
+
+    test.process_transaction_with_signers(
+        &[migrate_account(
+            program_id,
+            owner.pubkey(),
+            bucket,
+            AccountKind::RateLimit(RateLimitTarget::Client(destination_contract)),
+        )],
+        &[&owner],
+    )
+    .await
+    .unwrap();
+
+    let raw_account = test.client.get_account(bucket).await.unwrap().unwrap();
+
+    let migrated = RateLimitState::unpack(&raw_account.data).unwrap();
+
+    assert_eq!(migrated.message_tokens, state.message_tokens);
+    assert_eq!(migrated.byte_tokens, state.byte_tokens);
+}
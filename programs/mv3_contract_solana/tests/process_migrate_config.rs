@@ -0,0 +1,164 @@
+#![cfg(feature = "test-sbf")]
+
+mod utils;
+use std::assert;
+
+use borsh::BorshSerialize;
+use mv3_contract_solana::{
+    constants::{CONFIG_SEED, MESSAGE_CLIENT_SEED, SOLANA_CHAIN_ID},
+    instruction::migrate_config,
+    state::{
+        account::GatewayAccount,
+        chain::ChainConfig,
+        config::{MessageClient, MessengerConfig, RoleRegistry, UserPermission},
+        version::{MessageClientV0, MessengerConfigV0},
+    },
+};
+use solana_program::pubkey::Pubkey;
+use solana_sdk::{account::Account, signature::Keypair, signer::Signer};
+
+use crate::utils::ProgramTestBench;
+use solana_program_test::tokio;
+
+fn program_account(data: Vec<u8>) -> Account {
+    Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: mv3_contract_solana::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+#[tokio::test]
+pub async fn test_migrate_first_deployment_config() {
+    let owner = Keypair::new();
+    let program_id = mv3_contract_solana::id();
+    let destination_contract = Pubkey::new_unique();
+
+    let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
+    let (client, _) = Pubkey::find_program_address(
+        &[MESSAGE_CLIENT_SEED, destination_contract.as_ref()],
+        &program_id,
+    );
+
+    let legacy_config = MessengerConfigV0 {
+        owner: owner.pubkey(),
+        next_tx_id: 12,
+        enabled_chains: vec![4],
+        whitelists: vec![UserPermission {
+            wallet: destination_contract,
+            is_active: true,
+        }],
+        bridge_enabled: true,
+        fee_currency: None,
+        bridge_operators: vec![],
+        bridge_supers: vec![],
+        bridge_a_team: vec![],
+        accountant: owner.pubkey(),
+        whitelist_only: false,
+        chainsig: Some([5; 32]),
+    };
+
+    let legacy_client = MessageClientV0 {
+        authority: owner.pubkey(),
+        destination_contract,
+        notify_on_failure: false,
+        supported_chains: vec![1],
+        allowed_contracts: vec![[2; 32]],
+        exsig: Some([3; 32]),
+    };
+
+    let mut test = ProgramTestBench::start_with_accounts(vec![
+        (
+            owner.pubkey(),
+            Account {
+                lamports: 1_000_000_000,
+                ..Account::default()
+            },
+        ),
+        (config, program_account(legacy_config.try_to_vec().unwrap())),
+        (client, program_account(legacy_client.try_to_vec().unwrap())),
+    ])
+    .await;
+
+    //only the config owner can migrate
+    let intruder = Keypair::new();
+
+    assert!(test
+        .process_transaction_with_signers(
+            &[migrate_config(program_id, intruder.pubkey(), &[], &[])],
+            &[&intruder]
+        )
+        .await
+        .is_err());
+
+    let ix = migrate_config(program_id, owner.pubkey(), &[4], &[destination_contract]);
+
+    test.process_transaction_with_signers(&[ix.clone()], &[&owner])
+        .await
+        .unwrap();
+
+    let raw_config = test.client.get_account(config).await.unwrap().unwrap();
+    let migrated = MessengerConfig::unpack(&raw_config.data).unwrap();
+
+    assert!(migrated.version == MessengerConfig::VERSION);
+    assert!(migrated.next_tx_id() == 12, "Tx id lost in migration!");
+    assert!(migrated.chain_id() == SOLANA_CHAIN_ID);
+    assert!(
+        migrated.chainsig() == Some([5; 32]),
+        "Chainsig lost in migration!"
+    );
+
+    //the lists of the config moved to their own accounts
+    let raw_roles = test
+        .client
+        .get_account(ix.accounts[3].pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(
+        RoleRegistry::unpack(&raw_roles.data).unwrap().whitelists[0].wallet == destination_contract,
+        "Whitelist lost in migration!"
+    );
+
+    let raw_chain = test
+        .client
+        .get_account(ix.accounts[4].pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let chain = ChainConfig::unpack(&raw_chain.data).unwrap();
+
+    assert!(
+        chain.chain_id == 4 && chain.enabled && !chain.outbound_paused,
+        "Chains lost in migration!"
+    );
+
+    let raw_client = test.client.get_account(client).await.unwrap().unwrap();
+    let migrated = MessageClient::unpack(&raw_client.data).unwrap();
+
+    assert!(migrated.version == MessageClient::VERSION);
+    assert!(migrated.exsig == Some([3; 32]), "Exsig lost in migration!");
+    assert!(migrated.allowed_contracts == vec![[2; 32]]);
+
+    //accounts already up to date are left untouched, the chains are imported by now
+    test.context.warp_to_slot(2).unwrap();
+
+    let ix = migrate_config(program_id, owner.pubkey(), &[], &[destination_contract]);
+
+    test.process_transaction_with_signers(&[ix], &[&owner])
+        .await
+        .unwrap();
+
+    let raw_config = test.client.get_account(config).await.unwrap().unwrap();
+
+    assert!(
+        MessengerConfig::unpack(&raw_config.data)
+            .unwrap()
+            .next_tx_id()
+            == 12
+    );
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "test-sbf")]
+
+mod utils;
+use std::assert;
+
+use mv3_contract_solana::constants::SOLANA_CHAIN_ID;
+use mv3_contract_solana::instruction::{
+    deposit_withdraw_bond, initialize_config, set_optimistic_config, DepositWithdraw,
+    SetOptimisticConfig,
+};
+use mv3_contract_solana::state::{
+    account::GatewayAccount,
+    optimistic::{OperatorBond, OptimisticConfig},
+};
+use solana_program::pubkey::Pubkey;
+
+use crate::utils::ProgramTestBench;
+use solana_program_test::tokio;
+
+#[tokio::test]
+pub async fn test_optimistic_config_and_bond() {
+    let mut test = ProgramTestBench::start_impl().await;
+
+    let init_ix = initialize_config(
+        test.payer_pk,
+        SOLANA_CHAIN_ID,
+        test.payer_pk,
+        &test.program_id,
+    );
+
+    let watcher = Pubkey::new_unique();
+
+    let config_ix = set_optimistic_config(
+        test.program_id,
+        test.payer_pk,
+        SetOptimisticConfig {
+            challenge_period: 600,
+            min_bond: 1_000_000,
+            watchers: vec![watcher],
+        },
+    );
+
+    let deposit_ix = deposit_withdraw_bond(
+        test.program_id,
+        test.payer_pk,
+        2_000_000,
+        DepositWithdraw::Deposit,
+    );
+
+    test.process_transaction(&[init_ix, config_ix.clone(), deposit_ix.clone()])
+        .await
+        .unwrap();
+
+    let raw_config = test
+        .client
+        .get_account(config_ix.accounts[3].pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let config = OptimisticConfig::unpack(&raw_config.data).unwrap();
+
+    assert!(config.watchers == vec![watcher], "Watchers not written!");
+
+    assert!(config.challenge_period == 600, "Invalid challenge period!");
+
+    let invalid_ix = set_optimistic_config(
+        test.program_id,
+        test.payer_pk,
+        SetOptimisticConfig {
+            challenge_period: 0,
+            min_bond: 1_000_000,
+            watchers: vec![watcher],
+        },
+    );
+
+    assert!(
+        test.process_transaction(&[invalid_ix]).await.is_err(),
+        "Empty challenge period accepted!"
+    );
+
+    let withdraw_ix = deposit_withdraw_bond(
+        test.program_id,
+        test.payer_pk,
+        500_000,
+        DepositWithdraw::Withdraw,
+    );
+
+    test.process_transaction(&[withdraw_ix]).await.unwrap();
+
+    let raw_bond = test
+        .client
+        .get_account(deposit_ix.accounts[1].pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let bond = OperatorBond::unpack(&raw_bond.data).unwrap();
+
+    assert!(bond.operator == test.payer_pk, "Invalid operator!");
+
+    assert!(bond.amount == 1_500_000, "Invalid bond amount!");
+
+    let overdraw_ix = deposit_withdraw_bond(
+        test.program_id,
+        test.payer_pk,
+        2_000_000,
+        DepositWithdraw::Withdraw,
+    );
+
+    assert!(
+        test.process_transaction(&[overdraw_ix]).await.is_err(),
+        "Withdrew more than the bond!"
+    );
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "test-sbf")]
+
+mod utils;
+use std::assert;
+
+use mv3_contract_solana::constants::SOLANA_CHAIN_ID;
+use mv3_contract_solana::instruction::{
+    initialize_config, register_chain, set_pause, PauseTarget, RegisterChain, SetPause,
+};
+use mv3_contract_solana::state::{
+    account::GatewayAccount,
+    chain::{AddressFormat, ChainConfig, ChainFees},
+};
+
+use crate::utils::ProgramTestBench;
+use solana_program_test::tokio;
+
+fn evm_chain(name: &str, base_fee: u64) -> RegisterChain {
+    RegisterChain {
+        chain_id: 5,
+        name: name.to_string(),
+        address_format: AddressFormat::Evm,
+        min_confirmations: 12,
+        fees: ChainFees {
+            gas_limit: 200_000,
+            base_fee,
+            fee_per_byte: 10,
+        },
+        enabled: true,
+    }
+}
+
+#[tokio::test]
+pub async fn test_register_chain() {
+    let mut test = ProgramTestBench::start_impl().await;
+
+    let init_ix = initialize_config(
+        test.payer_pk,
+        SOLANA_CHAIN_ID,
+        test.payer_pk,
+        &test.program_id,
+    );
+
+    let register_ix = register_chain(test.program_id, test.payer_pk, evm_chain("goerli", 1_000));
+
+    let pause_ix = set_pause(
+        test.program_id,
+        test.payer_pk,
+        SetPause {
+            target: PauseTarget::DestinationChain(5),
+            paused: true,
+        },
+    );
+
+    //updating the entry keeps its pauses
+    let update_ix = register_chain(test.program_id, test.payer_pk, evm_chain("sepolia", 2_000));
+
+    test.process_transaction(&[init_ix, register_ix.clone(), pause_ix, update_ix])
+        .await
+        .unwrap();
+
+    let raw_chain = test
+        .client
+        .get_account(register_ix.accounts[3].pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let chain = ChainConfig::unpack(&raw_chain.data).unwrap();
+
+    assert!(chain.chain_id == 5, "Invalid chain id!");
+
+    assert!(chain.name == "sepolia", "Entry not updated!");
+
+    assert!(chain.fees.base_fee == 2_000, "Fees not updated!");
+
+    assert!(chain.min_confirmations == 12, "Invalid confirmations!");
+
+    assert!(chain.outbound_paused, "Pause not kept!");
+
+    let invalid_ix = register_chain(test.program_id, test.payer_pk, evm_chain("", 0));
+
+    assert!(
+        test.process_transaction(&[invalid_ix]).await.is_err(),
+        "Empty chain name accepted!"
+    );
+}
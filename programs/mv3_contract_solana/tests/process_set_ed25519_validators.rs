@@ -0,0 +1,110 @@
+#![cfg(feature = "test-sbf")]
+
+mod utils;
+use std::assert;
+
+use mv3_contract_solana::constants::SOLANA_CHAIN_ID;
+use mv3_contract_solana::instruction::{
+    initialize_config, set_ed25519_validators, SetEd25519Validators,
+};
+use mv3_contract_solana::state::{account::GatewayAccount, validators::ValidatorSet};
+use solana_program::pubkey::Pubkey;
+
+use crate::utils::ProgramTestBench;
+use solana_program_test::tokio;
+
+#[tokio::test]
+pub async fn test_set_ed25519_validators() {
+    let mut test = ProgramTestBench::start_impl().await;
+
+    let init_ix = initialize_config(
+        test.payer_pk,
+        SOLANA_CHAIN_ID,
+        test.payer_pk,
+        &test.program_id,
+    );
+
+    let keys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+
+    let set_ix = set_ed25519_validators(
+        test.program_id,
+        test.payer_pk,
+        SetEd25519Validators {
+            keys: keys.clone(),
+            threshold: 2,
+        },
+    );
+
+    test.process_transaction(&[init_ix, set_ix.clone()])
+        .await
+        .unwrap();
+
+    let raw_validators = test
+        .client
+        .get_account(set_ix.accounts[3].pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let validators = ValidatorSet::unpack(&raw_validators.data).unwrap();
+
+    assert!(validators.ed25519_keys == keys, "Keys not written!");
+
+    assert!(validators.ed25519_threshold == 2, "Invalid threshold!");
+
+    assert!(validators.bls_keys.is_empty(), "BLS keys written!");
+
+    let unreachable_ix = set_ed25519_validators(
+        test.program_id,
+        test.payer_pk,
+        SetEd25519Validators {
+            keys: keys.clone(),
+            threshold: 4,
+        },
+    );
+
+    assert!(
+        test.process_transaction(&[unreachable_ix]).await.is_err(),
+        "Unreachable threshold accepted!"
+    );
+
+    let duplicate_ix = set_ed25519_validators(
+        test.program_id,
+        test.payer_pk,
+        SetEd25519Validators {
+            keys: vec![keys[0], keys[0]],
+            threshold: 1,
+        },
+    );
+
+    assert!(
+        test.process_transaction(&[duplicate_ix]).await.is_err(),
+        "Duplicate key accepted!"
+    );
+
+    //an empty set stops checking Ed25519 signatures
+    let clear_ix = set_ed25519_validators(
+        test.program_id,
+        test.payer_pk,
+        SetEd25519Validators {
+            keys: vec![],
+            threshold: 0,
+        },
+    );
+
+    test.process_transaction(&[clear_ix]).await.unwrap();
+
+    let raw_validators = test
+        .client
+        .get_account(set_ix.accounts[3].pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let validators = ValidatorSet::unpack(&raw_validators.data).unwrap();
+
+    assert!(
+        !validators.ed25519_enabled(),
+        "Ed25519 verification enabled!"
+    );
+}
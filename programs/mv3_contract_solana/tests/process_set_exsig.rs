@@ -0,0 +1,222 @@
+#![cfg(feature = "test-sbf")]
+
+mod utils;
+use std::assert;
+
+use mv3_contract_solana::{
+    constants::EXSIG_GRACE_PERIOD,
+    instruction::{configure_client, set_exsig, SetExsig},
+    state::{
+        account::GatewayAccount,
+        config::{MessageClient, PendingExsig},
+    },
+    utils::get_message_client_pda,
+};
+use solana_program::{clock::Clock, pubkey::Pubkey};
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+use crate::utils::ProgramTestBench;
+use solana_program_test::tokio;
+
+async fn get_client(test: &mut ProgramTestBench, program: Pubkey) -> MessageClient {
+    let (client, _) = get_message_client_pda(program, &test.program_id);
+
+    let raw_account = test.client.get_account(client).await.unwrap().unwrap();
+
+    MessageClient::unpack(&raw_account.data).unwrap()
+}
+
+async fn setup_client() -> (ProgramTestBench, Keypair, Pubkey) {
+    let authority = Keypair::new();
+    let program = Pubkey::new_unique();
+
+    let mut test = ProgramTestBench::start_with_client_program(program, authority.pubkey()).await;
+
+    let fund_ix = solana_program::system_instruction::transfer(
+        &test.payer_pk,
+        &authority.pubkey(),
+        1_000_000_000,
+    );
+
+    let configure_ix = configure_client(
+        test.program_id,
+        authority.pubkey(),
+        MessageClient {
+            version: MessageClient::VERSION,
+            authority: authority.pubkey(),
+            destination_contract: program,
+            notify_on_failure: false,
+            supported_chains: vec![],
+            allowed_contracts: vec![],
+            exsig: None,
+            paused: false,
+            pending_authority: None,
+            pending_exsig: None,
+            rate_limit: None,
+            optimistic: false,
+            ordered: false,
+        },
+    );
+
+    test.process_transaction_with_signers(&[fund_ix, configure_ix], &[&authority])
+        .await
+        .unwrap();
+
+    (test, authority, program)
+}
+
+#[tokio::test]
+pub async fn test_set_exsig_round_trip() {
+    let (mut test, authority, program) = setup_client().await;
+
+    let exsig = [7; 32];
+
+    let set_ix = set_exsig(
+        test.program_id,
+        authority.pubkey(),
+        program,
+        SetExsig {
+            exsig: Some(exsig),
+            activation_delay: 0,
+        },
+    );
+
+    test.process_transaction_with_signers(&[set_ix], &[&authority])
+        .await
+        .unwrap();
+
+    let client = get_client(&mut test, program).await;
+
+    assert!(client.exsig == Some(exsig), "Exsig not persisted!");
+
+    assert!(client.pending_exsig.is_none(), "Unexpected pending exsig!");
+
+    let clear_ix = set_exsig(
+        test.program_id,
+        authority.pubkey(),
+        program,
+        SetExsig {
+            exsig: None,
+            activation_delay: 0,
+        },
+    );
+
+    test.process_transaction_with_signers(&[clear_ix], &[&authority])
+        .await
+        .unwrap();
+
+    let client = get_client(&mut test, program).await;
+
+    assert!(client.exsig.is_none(), "Exsig not cleared!");
+}
+
+#[tokio::test]
+pub async fn test_set_exsig_rotation() {
+    let (mut test, authority, program) = setup_client().await;
+
+    let old_exsig = [1; 32];
+    let new_exsig = [2; 32];
+
+    let set_ix = set_exsig(
+        test.program_id,
+        authority.pubkey(),
+        program,
+        SetExsig {
+            exsig: Some(old_exsig),
+            activation_delay: 0,
+        },
+    );
+
+    let rotate_ix = set_exsig(
+        test.program_id,
+        authority.pubkey(),
+        program,
+        SetExsig {
+            exsig: Some(new_exsig),
+            activation_delay: 600,
+        },
+    );
+
+    test.process_transaction_with_signers(&[set_ix, rotate_ix], &[&authority])
+        .await
+        .unwrap();
+
+    let now = test
+        .client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+
+    let client = get_client(&mut test, program).await;
+
+    let activates_at = client.pending_exsig.as_ref().unwrap().activates_at;
+
+    assert!(
+        client.pending_exsig
+            == Some(PendingExsig {
+                exsig: Some(new_exsig),
+                activates_at,
+            }),
+        "Invalid pending exsig!"
+    );
+
+    assert!(activates_at >= now + 600, "Invalid activation time!");
+
+    assert!(
+        client.active_exsig(activates_at - 1) == Some(old_exsig),
+        "Old exsig not active during grace window!"
+    );
+
+    assert!(
+        client.active_exsig(activates_at) == Some(new_exsig),
+        "New exsig not active after grace window!"
+    );
+
+    //messages signed before the rotation are accepted for the grace period after it
+    assert!(
+        client.accepted_exsigs(activates_at) == vec![Some(new_exsig), Some(old_exsig)],
+        "Old exsig not accepted during grace period!"
+    );
+
+    assert!(
+        client.accepted_exsigs(activates_at + EXSIG_GRACE_PERIOD) == vec![Some(new_exsig)],
+        "Old exsig accepted after grace period!"
+    );
+
+    let mut client = client;
+
+    client.apply_pending_exsig(activates_at);
+
+    assert!(
+        client.exsig == Some(old_exsig),
+        "Old exsig dropped during grace period!"
+    );
+
+    client.apply_pending_exsig(activates_at + EXSIG_GRACE_PERIOD);
+
+    assert!(
+        client.exsig == Some(new_exsig) && client.pending_exsig.is_none(),
+        "Rotation not applied after grace period!"
+    );
+}
+
+#[tokio::test]
+pub async fn test_set_exsig_invalid_authority() {
+    let (mut test, _, program) = setup_client().await;
+
+    let set_ix = set_exsig(
+        test.program_id,
+        test.payer_pk,
+        program,
+        SetExsig {
+            exsig: Some([3; 32]),
+            activation_delay: 0,
+        },
+    );
+
+    assert!(
+        test.process_transaction(&[set_ix]).await.is_err(),
+        "Exsig set by non-authority!"
+    );
+}
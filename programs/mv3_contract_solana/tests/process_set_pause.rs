@@ -0,0 +1,113 @@
+#![cfg(feature = "test-sbf")]
+
+mod utils;
+use std::assert;
+
+use mv3_contract_solana::constants::SOLANA_CHAIN_ID;
+use mv3_contract_solana::instruction::{
+    initialize_config, register_chain, set_pause, PauseTarget, RegisterChain, SetPause,
+};
+use mv3_contract_solana::state::{
+    account::GatewayAccount,
+    chain::{AddressFormat, ChainConfig, ChainFees},
+    config::MessengerConfig,
+};
+use mv3_contract_solana::utils::get_chain_pda;
+
+use crate::utils::ProgramTestBench;
+use solana_program_test::tokio;
+#[tokio::test]
+pub async fn test_set_pause() {
+    let mut test = ProgramTestBench::start_impl().await;
+
+    let init_ix = initialize_config(
+        test.payer_pk,
+        SOLANA_CHAIN_ID,
+        test.payer_pk,
+        &test.program_id,
+    );
+
+    let register_ixs = [2, 3, 4].into_iter().map(|chain_id| {
+        register_chain(
+            test.program_id,
+            test.payer_pk,
+            RegisterChain {
+                chain_id,
+                name: format!("chain-{}", chain_id),
+                address_format: AddressFormat::Evm,
+                min_confirmations: 0,
+                fees: ChainFees::default(),
+                enabled: true,
+            },
+        )
+    });
+
+    let pause_ixs = [
+        PauseTarget::Inbound,
+        PauseTarget::SourceChain(2),
+        PauseTarget::SourceChain(3),
+        PauseTarget::DestinationChain(4),
+    ]
+    .into_iter()
+    .map(|target| {
+        set_pause(
+            test.program_id,
+            test.payer_pk,
+            SetPause {
+                target,
+                paused: true,
+            },
+        )
+    });
+
+    let unpause_ix = set_pause(
+        test.program_id,
+        test.payer_pk,
+        SetPause {
+            target: PauseTarget::SourceChain(2),
+            paused: false,
+        },
+    );
+
+    let mut ixs = vec![init_ix.clone()];
+    ixs.extend(register_ixs);
+    ixs.extend(pause_ixs);
+    ixs.push(unpause_ix);
+
+    test.process_transaction(&ixs).await.unwrap();
+
+    let raw_account = test
+        .client
+        .get_account(init_ix.accounts[1].pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let config = MessengerConfig::unpack(&raw_account.data).unwrap();
+
+    assert!(config.inbound_paused(), "Inbound not paused!");
+
+    assert!(!config.outbound_paused(), "Outbound paused!");
+
+    let mut chains = vec![];
+
+    for chain_id in [2, 3, 4] {
+        let raw_chain = test
+            .client
+            .get_account(get_chain_pda(chain_id, &test.program_id).0)
+            .await
+            .unwrap()
+            .unwrap();
+
+        chains.push(ChainConfig::unpack(&raw_chain.data).unwrap());
+    }
+
+    assert!(!chains[0].inbound_paused, "Source chain not unpaused!");
+
+    assert!(chains[1].inbound_paused, "Source chain not paused!");
+
+    assert!(
+        chains[2].outbound_paused && !chains[2].inbound_paused,
+        "Invalid paused destination chain!"
+    );
+}
@@ -0,0 +1,138 @@
+use mv3_contract_solana::{
+    error::MessengerError,
+    state::rate_limit::{RateLimitConfig, RateLimitState},
+};
+
+const LIMIT: RateLimitConfig = RateLimitConfig {
+    max_messages: 2,
+    max_bytes: 100,
+    window: 60,
+};
+
+#[test]
+pub fn test_rate_limit_messages() {
+    let mut state = RateLimitState::new(&LIMIT, 1_000);
+
+    state.consume(&LIMIT, 1_000, 10).unwrap();
+    state.consume(&LIMIT, 1_000, 10).unwrap();
+
+    assert!(
+        state.consume(&LIMIT, 1_000, 10).is_err(),
+        "Message limit not enforced!"
+    );
+
+    //one message refills every 30 seconds
+    assert!(state.consume(&LIMIT, 1_029, 10).is_err(), "Refilled early!");
+
+    state.consume(&LIMIT, 1_030, 10).unwrap();
+}
+
+#[test]
+pub fn test_rate_limit_bytes() {
+    let mut state = RateLimitState::new(&LIMIT, 1_000);
+
+    state.consume(&LIMIT, 1_000, 90).unwrap();
+
+    assert!(
+        state.consume(&LIMIT, 1_000, 20).is_err(),
+        "Byte limit not enforced!"
+    );
+
+    //partial refills accumulate instead of being rounded away
+    for second in 1..=6 {
+        let _ = state.consume(&LIMIT, 1_000 + second, 1_000);
+    }
+
+    state.consume(&LIMIT, 1_006, 20).unwrap();
+}
+
+#[test]
+pub fn test_rate_limit_message_too_large() {
+    let mut state = RateLimitState::new(&LIMIT, 1_000);
+
+    //larger than the whole bucket, unlike messages which only have to wait for a refill
+    assert_eq!(
+        state.consume(&LIMIT, 1_000, 101),
+        Err(MessengerError::MessageTooLarge)
+    );
+
+    state.consume(&LIMIT, 1_000, 100).unwrap();
+
+    assert_eq!(
+        state.consume(&LIMIT, 1_000, 1),
+        Err(MessengerError::RateLimitExceeded)
+    );
+}
+
+#[test]
+pub fn test_rate_limit_unlimited_dimension() {
+    let limit = RateLimitConfig {
+        max_messages: 1,
+        max_bytes: 0,
+        window: 60,
+    };
+
+    let mut state = RateLimitState::new(&limit, 0);
+
+    state.consume(&limit, 0, u64::MAX).unwrap();
+}
+
+#[test]
+pub fn test_rate_limit_rescaled_to_new_window() {
+    let mut state = RateLimitState::new(&LIMIT, 1_000);
+
+    state.consume(&LIMIT, 1_000, 10).unwrap();
+
+    //the message left in the bucket carries over to the longer window
+    let slower = RateLimitConfig {
+        window: 600,
+        ..LIMIT
+    };
+
+    state.rescale(&LIMIT, &slower, 1_000);
+
+    state.consume(&slower, 1_000, 10).unwrap();
+
+    assert!(
+        state.consume(&slower, 1_000, 10).is_err(),
+        "Message limit not enforced!"
+    );
+
+    //one message refills every 300 seconds now
+    assert!(
+        state.consume(&slower, 1_299, 10).is_err(),
+        "Refilled early!"
+    );
+
+    state.consume(&slower, 1_300, 10).unwrap();
+
+    //levels above the capacity of the new window are capped
+    let mut state = RateLimitState::new(&slower, 2_000);
+
+    state.rescale(&slower, &LIMIT, 2_000);
+
+    state.consume(&LIMIT, 2_000, 10).unwrap();
+    state.consume(&LIMIT, 2_000, 10).unwrap();
+
+    assert!(
+        state.consume(&LIMIT, 2_000, 10).is_err(),
+        "Message limit not enforced!"
+    );
+}
+
+#[test]
+pub fn test_rate_limit_rescale_limits_unlimited_dimension() {
+    let unlimited_bytes = RateLimitConfig {
+        max_bytes: 0,
+        ..LIMIT
+    };
+
+    let mut state = RateLimitState::new(&unlimited_bytes, 0);
+
+    state.consume(&unlimited_bytes, 0, u64::MAX).unwrap();
+
+    //bytes sent while unlimited don't count against the new limit
+    state.rescale(&unlimited_bytes, &LIMIT, 0);
+
+    state.consume(&LIMIT, 0, 100).unwrap();
+}
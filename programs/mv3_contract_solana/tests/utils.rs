@@ -1,65 +1,142 @@
-// use std::{fmt::Error, str::FromStr};
-
-// use mv3_contract_solana::{error::MessengerError, processor::process_instruction};
-// use solana_program::msg;
-// use solana_program::{entrypoint::ProgramResult, instruction::Instruction, pubkey::Pubkey};
-// use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
-// pub struct ProgramTestBench {
-//     pub payer: Keypair,
-//     pub client: BanksClient,
-//     pub program_id: Pubkey,
-//     pub payer_pk: Pubkey,
-// }
-
-// impl ProgramTestBench {
-//     async fn create_bench(program_test: ProgramTest) -> Self {
-//         let test = program_test.start_with_context().await;
-
-//         let program_id = Pubkey::from_str("mv3PxTJXnsExfkFtwbKCo35fGKdtfcowo9xZsmXQ2qJ").unwrap();
-
-//         let payer_pk = test.payer.pubkey();
-
-//         ProgramTestBench {
-//             client: test.banks_client,
-//             payer: test.payer,
-//             program_id,
-//             payer_pk,
-//         }
-//     }
-
-//     pub async fn start_impl() -> ProgramTestBench {
-//         let mut test = ProgramTest::default();
-//         test.add_program(
-//             "mv3_contract_solana",
-//             Pubkey::from_str("mv3PxTJXnsExfkFtwbKCo35fGKdtfcowo9xZsmXQ2qJ").unwrap(),
-//             processor!(process_instruction),
-//         );
-
-//         Self::create_bench(test).await
-//     }
-
-//     pub async fn process_transaction(&mut self, instructions: &[Instruction]) -> Result<(), Error> {
-//         let recent_blockhash = self.client.get_latest_blockhash().await.unwrap();
-
-//         let tx = Transaction::new_signed_with_payer(
-//             instructions,
-//             Some(&self.payer_pk),
-//             &[&self.payer],
-//             recent_blockhash,
-//         );
-
-//         // let sim = self.client.simulate_transaction(tx.clone()).await.unwrap();
-
-//         // println!("SIM: {:?}", sim.result.unwrap());
-
-//         self.client
-//             .process_transaction_with_commitment(
-//                 tx,
-//                 solana_sdk::commitment_config::CommitmentLevel::Finalized,
-//             )
-//             .await
-//             .unwrap();
-
-//         Ok(())
-//     }
-// }
+#![cfg(feature = "test-sbf")]
+#![allow(dead_code)]
+
+use std::str::FromStr;
+
+use mv3_contract_solana::processor::process_instruction;
+use solana_program::{bpf_loader_upgradeable, instruction::Instruction, pubkey::Pubkey};
+use solana_program_test::{
+    processor, BanksClient, BanksClientError, ProgramTest, ProgramTestContext,
+};
+use solana_sdk::{account::Account, signature::Keypair, signer::Signer, transaction::Transaction};
+
+pub struct ProgramTestBench {
+    pub payer: Keypair,
+    pub client: BanksClient,
+    pub context: ProgramTestContext,
+    pub program_id: Pubkey,
+    pub payer_pk: Pubkey,
+}
+
+impl ProgramTestBench {
+    async fn create_bench(program_test: ProgramTest) -> Self {
+        let context = program_test.start_with_context().await;
+
+        let program_id = Pubkey::from_str("mv3PxTJXnsExfkFtwbKCo35fGKdtfcowo9xZsmXQ2qJ").unwrap();
+
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+        let payer_pk = payer.pubkey();
+
+        ProgramTestBench {
+            client: context.banks_client.clone(),
+            context,
+            payer,
+            program_id,
+            payer_pk,
+        }
+    }
+
+    fn program_test() -> ProgramTest {
+        let mut test = ProgramTest::default();
+        test.add_program(
+            "mv3_contract_solana",
+            Pubkey::from_str("mv3PxTJXnsExfkFtwbKCo35fGKdtfcowo9xZsmXQ2qJ").unwrap(),
+            processor!(process_instruction),
+        );
+
+        test
+    }
+
+    pub async fn start_impl() -> ProgramTestBench {
+        Self::create_bench(Self::program_test()).await
+    }
+
+    /// Starts the bench with `accounts` already present, e.g. state in a legacy layout
+    pub async fn start_with_accounts(accounts: Vec<(Pubkey, Account)>) -> ProgramTestBench {
+        let mut test = Self::program_test();
+
+        for (address, account) in accounts {
+            test.add_account(address, account);
+        }
+
+        Self::create_bench(test).await
+    }
+
+    /// Starts the bench with `client_program` deployed through the upgradeable loader, so it can
+    /// be registered as a message client by `upgrade_authority`.
+    pub async fn start_with_client_program(
+        client_program: Pubkey,
+        upgrade_authority: Pubkey,
+    ) -> ProgramTestBench {
+        let mut test = Self::program_test();
+
+        let (program_data, _) =
+            Pubkey::find_program_address(&[client_program.as_ref()], &bpf_loader_upgradeable::id());
+
+        let mut program_account_data = 2_u32.to_le_bytes().to_vec();
+        program_account_data.extend_from_slice(program_data.as_ref());
+
+        test.add_account(
+            client_program,
+            Account {
+                lamports: 1_000_000_000,
+                data: program_account_data,
+                owner: bpf_loader_upgradeable::id(),
+                executable: true,
+                rent_epoch: 0,
+            },
+        );
+
+        let mut program_data_account_data = 3_u32.to_le_bytes().to_vec();
+        program_data_account_data.extend_from_slice(&0_u64.to_le_bytes());
+        program_data_account_data.push(1);
+        program_data_account_data.extend_from_slice(upgrade_authority.as_ref());
+
+        test.add_account(
+            program_data,
+            Account {
+                lamports: 1_000_000_000,
+                data: program_data_account_data,
+                owner: bpf_loader_upgradeable::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        Self::create_bench(test).await
+    }
+
+    pub async fn process_transaction(
+        &mut self,
+        instructions: &[Instruction],
+    ) -> Result<(), BanksClientError> {
+        self.process_transaction_with_signers(instructions, &[])
+            .await
+    }
+
+    pub async fn process_transaction_with_signers(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let recent_blockhash = self.client.get_latest_blockhash().await.unwrap();
+
+        let mut all_signers = vec![&self.payer];
+        all_signers.extend_from_slice(signers);
+
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer_pk),
+            &all_signers,
+            recent_blockhash,
+        );
+
+        self.client
+            .process_transaction_with_commitment(
+                tx,
+                solana_sdk::commitment_config::CommitmentLevel::Finalized,
+            )
+            .await
+    }
+}
@@ -0,0 +1,166 @@
+use borsh::BorshSerialize;
+use mv3_contract_solana::{
+    constants::SOLANA_CHAIN_ID,
+    error::MessengerError,
+    state::{
+        account::{GatewayAccount, DISCRIMINATOR_LEN},
+        chain::AddressFormat,
+        config::{MessageClient, MessengerConfig, UserPermission},
+        version::{unpack_legacy_config, MessageClientV0, MessengerConfigV0, VersionedAccount},
+    },
+};
+use solana_program::pubkey::Pubkey;
+
+fn legacy_config(owner: Pubkey) -> MessengerConfigV0 {
+    MessengerConfigV0 {
+        owner,
+        next_tx_id: 9,
+        enabled_chains: vec![1, 5],
+        whitelists: vec![],
+        bridge_enabled: false,
+        fee_currency: None,
+        bridge_operators: vec![],
+        bridge_supers: vec![],
+        bridge_a_team: vec![],
+        accountant: owner,
+        whitelist_only: true,
+        chainsig: Some([3; 32]),
+    }
+}
+
+#[test]
+pub fn test_unpack_first_deployment_layouts() {
+    let owner = Pubkey::new_unique();
+
+    let data = legacy_config(owner).try_to_vec().unwrap();
+
+    let (version, config) = MessengerConfig::unpack_versioned(&data).unwrap();
+
+    assert_eq!(version, 0);
+    assert_eq!(config.version, MessengerConfig::VERSION);
+    assert_eq!(config.owner, owner);
+    assert_eq!(config.next_tx_id(), 9);
+    assert!(!config.bridge_enabled() && config.whitelist_only());
+    assert_eq!(config.chainsig(), Some([3; 32]));
+    assert_eq!(config.chain_id(), SOLANA_CHAIN_ID);
+    assert!(!config.inbound_paused());
+
+    //the current loader only accepts the current layout
+    assert!(MessengerConfig::unpack(&data).is_err());
+
+    let client = MessageClientV0 {
+        authority: owner,
+        destination_contract: Pubkey::new_unique(),
+        notify_on_failure: true,
+        supported_chains: vec![5],
+        allowed_contracts: vec![[1; 32]],
+        exsig: None,
+    };
+
+    let (version, upgraded) =
+        MessageClient::unpack_versioned(&client.try_to_vec().unwrap()).unwrap();
+
+    assert_eq!(version, 0);
+    assert_eq!(upgraded.destination_contract, client.destination_contract);
+    assert_eq!(upgraded.allowed_contracts, vec![[1; 32]]);
+    assert!(upgraded.notify_on_failure && !upgraded.paused);
+    assert_eq!(upgraded.pending_authority, None);
+}
+
+#[test]
+pub fn test_split_legacy_config() {
+    let owner = Pubkey::new_unique();
+
+    let mut legacy = legacy_config(owner);
+    legacy.enabled_chains = vec![5, 1, 5];
+    legacy.bridge_supers = vec![UserPermission {
+        wallet: owner,
+        is_active: true,
+    }];
+
+    let data = legacy.try_to_vec().unwrap();
+
+    //the lists moved out of the config are kept for the migration
+    let legacy = unpack_legacy_config(&data).unwrap();
+
+    assert_eq!(legacy.chain_ids(), vec![1, 5]);
+
+    let (config, roles, chains) = legacy.split();
+
+    assert_eq!(config.next_tx_id(), 9);
+    assert_eq!(roles.bridge_supers[0].wallet, owner);
+
+    //until the registry, every chain was an EVM chain
+    assert_eq!(chains.len(), 2);
+    assert!(chains.iter().all(|chain| chain.enabled
+        && !chain.inbound_paused
+        && !chain.outbound_paused
+        && chain.address_format == AddressFormat::Evm));
+    assert_eq!(chains[1].chain_id, 5);
+    assert_eq!(chains[1].name, "5");
+
+    //current configs are not legacy ones
+    assert_eq!(
+        unpack_legacy_config(&MessengerConfig::new(&owner, &owner, 1).pack().unwrap()).err(),
+        Some(MessengerError::AccountAlreadyMigrated.into())
+    );
+}
+
+#[test]
+pub fn test_config_header_is_zero_copy() {
+    let owner = Pubkey::new_unique();
+
+    let mut config = MessengerConfig::new(&owner, &owner, 1);
+    config.set_next_tx_id(u128::MAX - 1);
+    config.set_chainsig(Some([9; 32]));
+    config.record_sent();
+
+    //the borsh encoding is the in memory layout the hot paths borrow in place
+    let data = config.pack().unwrap();
+
+    assert_eq!(data.len(), DISCRIMINATOR_LEN + MessengerConfig::LEN);
+    assert_eq!(&data[DISCRIMINATOR_LEN..], bytemuck::bytes_of(&config));
+
+    let borrowed: &MessengerConfig = bytemuck::from_bytes(&data[DISCRIMINATOR_LEN..]);
+
+    assert_eq!(borrowed.next_tx_id(), u128::MAX - 1);
+    assert_eq!(borrowed.chainsig(), Some([9; 32]));
+    assert_eq!(borrowed.messages_sent(), 1);
+    assert_eq!(borrowed.messages_received(), 0);
+
+    config.set_chainsig(None);
+
+    assert_eq!(config.chainsig(), None);
+    assert!(config.bridge_enabled());
+}
+
+#[test]
+pub fn test_unpack_current_and_unknown_versions() {
+    let owner = Pubkey::new_unique();
+
+    let mut data = MessengerConfig::new(&owner, &owner, 1).pack().unwrap();
+
+    let (version, config) = MessengerConfig::unpack_versioned(&data).unwrap();
+
+    assert_eq!(version, MessengerConfig::VERSION);
+    assert_eq!(config.owner, owner);
+
+    //written by a newer program
+    data[DISCRIMINATOR_LEN] = MessengerConfig::VERSION + 1;
+
+    assert_eq!(
+        MessengerConfig::unpack_versioned(&data).err(),
+        Some(MessengerError::UnsupportedAccountVersion.into())
+    );
+    assert_eq!(
+        MessengerConfig::unpack(&data).err(),
+        Some(MessengerError::UnsupportedAccountVersion.into())
+    );
+
+    //other gateway types are never mistaken for a first deployment layout
+    assert_eq!(
+        MessageClient::unpack_versioned(&MessengerConfig::new(&owner, &owner, 1).pack().unwrap())
+            .err(),
+        Some(MessengerError::InvalidAccountDiscriminator.into())
+    );
+}
@@ -0,0 +1,59 @@
+use message_hook::idl::{Idl, IdlAccountItem, IdlSeed, IdlStateAccount, IdlTypes};
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    instructions::{Mv3SenderInstruction, CONFIG_SEED, MESSENGER_SEED},
+    state::{Config, Message},
+};
+
+/// Accounts of a `Mv3SenderInstruction` variant, in the order the processor reads them
+fn instruction_accounts(instruction: &str) -> Vec<IdlAccountItem> {
+    match instruction {
+        "SendData" => vec![
+            IdlAccountItem::new("config", false, true),
+            IdlAccountItem::new("message", false, true),
+            IdlAccountItem::new("sender", false, true),
+            IdlAccountItem::new("payer", true, true),
+            IdlAccountItem::new("sysvar_instructions", false, false),
+            IdlAccountItem::new("system_program", false, false),
+        ],
+        _ => panic!("Missing accounts of {}", instruction),
+    }
+}
+
+pub fn idl() -> Idl {
+    let mut types = IdlTypes::default();
+
+    let instructions = types.add_instruction_enum::<Mv3SenderInstruction>(instruction_accounts);
+
+    let pubkey = types.add::<Pubkey>();
+
+    let accounts = vec![
+        IdlStateAccount {
+            name: "Config".to_string(),
+            ty: types.add::<Config>(),
+            seeds: vec![IdlSeed::constant(CONFIG_SEED)],
+            discriminator: None,
+        },
+        IdlStateAccount {
+            name: "Message".to_string(),
+            ty: types.add::<Message>(),
+            seeds: vec![
+                IdlSeed::constant(MESSENGER_SEED),
+                IdlSeed::arg("sender", &pubkey),
+            ],
+            discriminator: None,
+        },
+    ];
+
+    Idl {
+        name: "mv3_sender_solana".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        program_id: Some(crate::id().to_string()),
+        instructions,
+        accounts,
+        events: None,
+        errors: vec![],
+        types: types.into_json(),
+    }
+}
@@ -1,4 +1,7 @@
-use borsh::{BorshDeserialize, BorshSerialize};
+//BorshSchema derives helper structs for enum variants which are never read
+#![allow(dead_code)]
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 
 use solana_program::{
     instruction::{AccountMeta, Instruction},
@@ -8,7 +11,7 @@ use solana_program::{
 
 use crate::state::ForeignAddress;
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema)]
 pub enum Mv3SenderInstruction {
     SendData {
         destination_chain_id: u64,
@@ -1,8 +1,10 @@
 use solana_program::declare_id;
 
 mod entrypoint;
+#[cfg(not(target_os = "solana"))]
+pub mod idl;
 pub mod instructions;
 pub mod processor;
-mod state;
+pub mod state;
 
 declare_id!("mv3da82EyTkoJdGLg7j93iWNzMxyJNmKDm7uQF3HZnY");
@@ -1,9 +1,9 @@
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::pubkey::Pubkey;
 
 pub type ForeignAddress = [u8; 32];
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct Message {
     pub sender: Pubkey,
     pub destination_chain: u64,
@@ -14,7 +14,7 @@ pub struct Message {
     pub slot: u64,
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize, BorshSchema)]
 pub struct Config {
     pub tx_id: u128,
     pub last_relayed_at: i64,
@@ -1,8 +1,6 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use message_hook::{get_extra_account_metas_address, instruction::MessageHookInstruction};
-use mv3_contract_solana::{
-    constants::MESSAGE_SEED, state::config::ForeignAddress, utils::get_message_pda,
-};
+use mv3_contract_solana::{state::config::ForeignAddress, utils::get_message_pda};
 use solana_program::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
@@ -17,8 +15,11 @@ pub struct HopData {
     pub chainlist: Vec<u8>,
 }
 
+/// `extra_account_metas` start with the config of the gateway, the hop forwards messages with the
+/// chain ID stored in it
 pub fn init_extra_account_meta_list(
     program_id: Pubkey,
+    gateway_program_id: Pubkey,
     authority: Pubkey,
     extra_account_metas: Vec<ExtraAccountMeta>,
 ) -> Instruction {
@@ -27,7 +28,7 @@ pub fn init_extra_account_meta_list(
     }
     .pack();
 
-    let message = get_message_pda(&program_id);
+    let message = get_message_pda(&program_id, &gateway_program_id);
 
     let extra_account_meta_key = get_extra_account_metas_address(&message, &program_id);
 
@@ -49,6 +50,11 @@ pub fn init_extra_account_meta_list(
                 is_writable: true,
                 pubkey: system_program::id(),
             },
+            AccountMeta {
+                is_signer: false,
+                is_writable: false,
+                pubkey: gateway_program_id,
+            },
         ],
         data,
     }
@@ -56,6 +62,7 @@ pub fn init_extra_account_meta_list(
 
 pub fn update_extra_account_meta_list(
     program_id: Pubkey,
+    gateway_program_id: Pubkey,
     authority: Pubkey,
     extra_account_metas: Vec<ExtraAccountMeta>,
 ) -> Instruction {
@@ -64,10 +71,7 @@ pub fn update_extra_account_meta_list(
     }
     .pack();
 
-    let (message_pda, _) = Pubkey::find_program_address(
-        &[MESSAGE_SEED, program_id.as_ref()],
-        &mv3_contract_solana::id(),
-    );
+    let message_pda = get_message_pda(&program_id, &gateway_program_id);
 
     let extra_account_meta_address = get_extra_account_metas_address(&message_pda, &program_id);
 
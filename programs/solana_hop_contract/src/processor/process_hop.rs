@@ -3,6 +3,10 @@ use std::ops::Div;
 use borsh::BorshSerialize;
 use ethnum::U256;
 use message_hook::instruction::ProcessMessageInstruction;
+use mv3_contract_solana::{
+    constants::CONFIG_SEED,
+    state::{account::load_account, config::MessengerConfig},
+};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
@@ -61,7 +65,21 @@ pub fn process_hop(program_id: &Pubkey, accounts: &[AccountInfo], data: Vec<u8>)
     let mut next_hop_serialized = next_hop.try_to_vec().unwrap();
     next_hop_serialized.reverse();
 
-    let mut chain_serialized = (19999999991_u64).try_to_vec().unwrap();
+    let extra_account_meta = next_account_info(accounts_iter)?;
+
+    ExtraAccountMetaList::check_account_infos::<ProcessMessageInstruction>(
+        accounts,
+        data.as_ref(),
+        program_id,
+        &extra_account_meta.data.borrow(),
+    )?;
+
+    //config of the gateway the message was delivered by, the first extra account
+    let gateway_config = next_account_info(accounts_iter)?;
+
+    let config: MessengerConfig = load_account(gateway_config, message.owner, &[CONFIG_SEED])?;
+
+    let mut chain_serialized = config.chain_id().try_to_vec().unwrap();
     chain_serialized.reverse();
 
     let payload = HopData {
@@ -72,15 +90,6 @@ pub fn process_hop(program_id: &Pubkey, accounts: &[AccountInfo], data: Vec<u8>)
     .try_to_vec()
     .unwrap();
 
-    let extra_account_meta = next_account_info(accounts_iter)?;
-
-    ExtraAccountMetaList::check_account_infos::<ProcessMessageInstruction>(
-        accounts,
-        data.as_ref(),
-        program_id,
-        &extra_account_meta.data.borrow(),
-    )?;
-
     let ix = mv3_solana_sender::instructions::send_message(
         mv3_solana_sender::id(),
         payer.key,
@@ -2,7 +2,7 @@ use message_hook::{
     collect_extra_account_metas_signer_seeds, get_extra_account_metas_address_and_bump_seed,
     instruction::ProcessMessageInstruction,
 };
-use mv3_contract_solana::constants::MESSAGE_SEED;
+use mv3_contract_solana::utils::get_message_pda;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
@@ -27,10 +27,12 @@ pub fn process_initialize_extra_account_meta_list(
 
     let extra_account_metas_info = next_account_info(account_info_iter)?;
 
-    let (message, _) = Pubkey::find_program_address(
-        &[MESSAGE_SEED, program_id.as_ref()],
-        &mv3_contract_solana::id(),
-    );
+    let _system_program = next_account_info(account_info_iter)?;
+
+    //gateway delivering the messages, the meta list is derived from its message account
+    let gateway = next_account_info(account_info_iter)?;
+
+    let message = get_message_pda(program_id, gateway.key);
 
     let (expected_validation_address, bump_seed) =
         get_extra_account_metas_address_and_bump_seed(&message, program_id);
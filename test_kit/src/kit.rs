@@ -0,0 +1,793 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use borsh::BorshSerialize;
+use message_hook::{get_extra_account_metas_address, instruction::ProcessMessageInstruction};
+use mv3_client::{
+    bls::{BlsAggregator, BlsSecretKey},
+    error::{decode_instruction_error, ClientError},
+    fetcher::AccountFetcher,
+    pda::program_data_address,
+    Gateway,
+};
+use mv3_contract_solana::{
+    constants::SOLANA_CHAIN_ID,
+    ed25519,
+    error::MessengerError,
+    events::{parse_events_from_logs, GatewayEvent},
+    instruction::{
+        BatchProof, FraudEvidence, MessageDigest, PostMessageRoot, ReceiveMessage, RootDigest,
+    },
+    merkle,
+    processor::process_instruction,
+    state::{
+        account::GatewayAccount,
+        chain::ChainConfig,
+        config::{
+            DigestScheme, ForeignAddress, MessageClient, MessengerConfig, RoleRegistry,
+            UserPermission,
+        },
+        optimistic::OptimisticConfig,
+        rate_limit::{RateLimitConfig, RateLimitState},
+        validators::ValidatorSet,
+    },
+    utils::keccak256,
+};
+use mv3_solana_sender::state::Message as SenderMessage;
+use solana_program::{
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    clock::Clock,
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+};
+use solana_program_test::{
+    processor, BanksClient, BanksClientError, ProgramTest, ProgramTestContext,
+};
+use solana_sdk::{
+    account::Account,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList};
+
+use crate::signer::MockEvmSigner;
+
+/// Reads accounts of a running test validator for the client decoders and account resolution
+pub struct BanksFetcher(pub BanksClient);
+
+#[async_trait]
+impl AccountFetcher for BanksFetcher {
+    async fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError> {
+        self.0
+            .clone()
+            .get_account(*address)
+            .await
+            .map(|account| account.map(|account| account.data))
+            .map_err(|err| ClientError::FetchFailed(*address, err.to_string()))
+    }
+}
+
+/// Gateway error a transaction processed by the kit failed with, `None` for other failures
+pub fn gateway_error(error: &BanksClientError) -> Option<MessengerError> {
+    match error {
+        BanksClientError::TransactionError(TransactionError::InstructionError(_, error))
+        | BanksClientError::SimulationError {
+            err: TransactionError::InstructionError(_, error),
+            ..
+        } => decode_instruction_error(error),
+        _ => None,
+    }
+}
+
+/// Message client written into the genesis accounts, registered without going through the upgrade
+/// authority of the program
+pub struct ClientSetup {
+    pub program_id: Pubkey,
+    /// Defaults to the admin of the kit
+    pub authority: Option<Pubkey>,
+    pub supported_chains: Vec<u64>,
+    pub allowed_contracts: Vec<ForeignAddress>,
+    pub exsig: Option<MockEvmSigner>,
+    /// Accounts the program requires on execute, stored in its extra account meta list
+    pub extra_account_metas: Vec<ExtraAccountMeta>,
+    pub treasury_lamports: u64,
+    /// Messages are executed on commitments of bonded operators instead of validator signatures
+    pub optimistic: bool,
+    /// Messages carry a sequence number and are executed in order per source chain and sender
+    pub ordered: bool,
+    /// Limit of the client, written along with a full bucket
+    pub rate_limit: Option<RateLimitConfig>,
+}
+
+impl ClientSetup {
+    pub fn new(program_id: Pubkey) -> Self {
+        Self {
+            program_id,
+            authority: None,
+            supported_chains: vec![],
+            allowed_contracts: vec![],
+            exsig: None,
+            extra_account_metas: vec![],
+            treasury_lamports: LAMPORTS_PER_SOL,
+            optimistic: false,
+            ordered: false,
+            rate_limit: None,
+        }
+    }
+}
+
+pub struct TestKitBuilder {
+    program_test: ProgramTest,
+    gateway: Gateway,
+    admin: Keypair,
+    chain_id: u64,
+    chainsig: Option<MockEvmSigner>,
+    digest_scheme: DigestScheme,
+    bls_validators: Vec<BlsSecretKey>,
+    bls_threshold: u16,
+    ed25519_validators: Vec<Keypair>,
+    ed25519_threshold: u16,
+    chains: Vec<ChainConfig>,
+    clients: Vec<ClientSetup>,
+    optimistic_config: Option<OptimisticConfig>,
+    operators: Vec<Pubkey>,
+}
+
+impl Default for TestKitBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestKitBuilder {
+    /// Gateway and sender program with a config owned by a funded admin and signed by a mock
+    /// chainsig
+    pub fn new() -> Self {
+        let gateway = Gateway::default();
+
+        let mut program_test = ProgramTest::new(
+            "mv3_contract_solana",
+            gateway.program_id,
+            processor!(process_instruction),
+        );
+
+        program_test.add_program(
+            "mv3_solana_sender",
+            gateway.sender_program_id,
+            processor!(mv3_solana_sender::processor::process_instruction),
+        );
+
+        Self {
+            program_test,
+            gateway,
+            admin: Keypair::new(),
+            chain_id: SOLANA_CHAIN_ID,
+            chainsig: Some(MockEvmSigner::from_seed(b"chainsig")),
+            digest_scheme: DigestScheme::PersonalSign,
+            bls_validators: vec![],
+            bls_threshold: 0,
+            ed25519_validators: vec![],
+            ed25519_threshold: 0,
+            chains: vec![],
+            clients: vec![],
+            optimistic_config: None,
+            operators: vec![],
+        }
+    }
+
+    /// Adds the hook programs messages are delivered to, e.g.
+    /// `builder.program_test().add_program("hello_token", id, processor!(process))`
+    pub fn program_test(&mut self) -> &mut ProgramTest {
+        &mut self.program_test
+    }
+
+    /// Chain ID the gateway is initialized with, `SOLANA_CHAIN_ID` by default
+    pub fn chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// `None` boots a gateway which accepts messages without chainsig
+    pub fn chainsig(mut self, chainsig: Option<MockEvmSigner>) -> Self {
+        self.chainsig = chainsig;
+        self
+    }
+
+    /// Hash signed messages are signed over, `DigestScheme::PersonalSign` by default
+    pub fn digest_scheme(mut self, digest_scheme: DigestScheme) -> Self {
+        self.digest_scheme = digest_scheme;
+        self
+    }
+
+    /// Verifies messages with an aggregated signature of `validators` instead of the chainsig
+    pub fn bls_validators(mut self, validators: Vec<BlsSecretKey>, threshold: u16) -> Self {
+        self.bls_validators = validators;
+        self.bls_threshold = threshold;
+        self
+    }
+
+    /// Requires Ed25519 signatures of `threshold` of `validators` on top of the other
+    /// signatures, every validator signs the delivered messages
+    pub fn ed25519_validators(mut self, validators: Vec<Keypair>, threshold: u16) -> Self {
+        self.ed25519_validators = validators;
+        self.ed25519_threshold = threshold;
+        self
+    }
+
+    /// Registry entry of a chain messages are delivered from or sent to
+    pub fn chain(mut self, chain: ChainConfig) -> Self {
+        self.chains.push(chain);
+        self
+    }
+
+    pub fn client(mut self, client: ClientSetup) -> Self {
+        self.clients.push(client);
+        self
+    }
+
+    /// Delivers messages to optimistic clients on the commitments of `operators`, which still
+    /// have to bond, challenged by the watchers of `config`
+    pub fn optimistic(mut self, config: OptimisticConfig, operators: Vec<Pubkey>) -> Self {
+        self.optimistic_config = Some(config);
+        self.operators = operators;
+        self
+    }
+
+    /// Deploys a stub of `program_id` with the BPF upgradeable loader, so it can be registered as
+    /// a client by `upgrade_authority`. The stub has no code and can't be invoked.
+    pub fn upgradeable_program(
+        mut self,
+        program_id: Pubkey,
+        upgrade_authority: Option<Pubkey>,
+    ) -> Self {
+        let program_data = program_data_address(&program_id);
+
+        //bincode layouts of `UpgradeableLoaderState::Program` and `ProgramData`
+        let program = [&2_u32.to_le_bytes()[..], program_data.as_ref()].concat();
+
+        let mut metadata = vec![0; UpgradeableLoaderState::size_of_programdata_metadata()];
+        metadata[..4].copy_from_slice(&3_u32.to_le_bytes());
+
+        if let Some(upgrade_authority) = upgrade_authority {
+            metadata[12] = 1;
+            metadata[13..45].copy_from_slice(upgrade_authority.as_ref());
+        }
+
+        self.add_raw_account(program_data, bpf_loader_upgradeable::id(), metadata, 0);
+
+        self.program_test.add_account(
+            program_id,
+            Account {
+                lamports: Rent::default().minimum_balance(program.len()),
+                data: program,
+                owner: bpf_loader_upgradeable::id(),
+                executable: true,
+                rent_epoch: 0,
+            },
+        );
+
+        self
+    }
+
+    fn add_state_account<T: GatewayAccount>(&mut self, address: Pubkey, owner: Pubkey, state: &T) {
+        self.add_raw_account(address, owner, state.pack().unwrap(), 0);
+    }
+
+    fn add_raw_account(&mut self, address: Pubkey, owner: Pubkey, data: Vec<u8>, lamports: u64) {
+        let rent = Rent::default().minimum_balance(data.len());
+
+        self.program_test.add_account(
+            address,
+            Account {
+                lamports: rent + lamports,
+                data,
+                owner,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+    }
+
+    pub async fn start(mut self) -> TestKit {
+        let admin = self.admin.pubkey();
+        let gateway = self.gateway;
+
+        self.add_raw_account(admin, system_program::id(), vec![], 100 * LAMPORTS_PER_SOL);
+
+        let mut config = MessengerConfig::new(&admin, &admin, self.chain_id);
+        config.set_chainsig(self.chainsig.as_ref().map(MockEvmSigner::address));
+        config.set_digest_scheme(self.digest_scheme);
+        config.set_bls_enabled(!self.bls_validators.is_empty());
+
+        self.add_state_account(gateway.config_address().0, gateway.program_id, &config);
+
+        let roles = RoleRegistry {
+            bridge_operators: std::mem::take(&mut self.operators)
+                .into_iter()
+                .map(|wallet| UserPermission {
+                    wallet,
+                    is_active: true,
+                })
+                .collect(),
+            ..RoleRegistry::default()
+        };
+
+        self.add_state_account(gateway.roles_address().0, gateway.program_id, &roles);
+
+        if let Some(config) = self.optimistic_config.take() {
+            self.add_state_account(
+                gateway.optimistic_config_address().0,
+                gateway.program_id,
+                &config,
+            );
+        }
+
+        if !self.bls_validators.is_empty() || !self.ed25519_validators.is_empty() {
+            let validators = ValidatorSet {
+                version: ValidatorSet::VERSION,
+                threshold: self.bls_threshold,
+                bls_keys: self
+                    .bls_validators
+                    .iter()
+                    .map(|key| key.public_key().g1)
+                    .collect(),
+                ed25519_threshold: self.ed25519_threshold,
+                ed25519_keys: self
+                    .ed25519_validators
+                    .iter()
+                    .map(Keypair::pubkey)
+                    .collect(),
+            };
+
+            self.add_state_account(
+                gateway.validators_address().0,
+                gateway.program_id,
+                &validators,
+            );
+        }
+
+        for chain in std::mem::take(&mut self.chains) {
+            self.add_state_account(
+                gateway.chain_address(chain.chain_id).0,
+                gateway.program_id,
+                &chain,
+            );
+        }
+
+        let mut exsigs = HashMap::new();
+
+        for setup in std::mem::take(&mut self.clients) {
+            let client = MessageClient {
+                version: MessageClient::VERSION,
+                authority: setup.authority.unwrap_or(admin),
+                destination_contract: setup.program_id,
+                notify_on_failure: false,
+                supported_chains: setup.supported_chains,
+                allowed_contracts: setup.allowed_contracts,
+                exsig: setup.exsig.as_ref().map(MockEvmSigner::address),
+                paused: false,
+                pending_authority: None,
+                pending_exsig: None,
+                rate_limit: setup.rate_limit,
+                optimistic: setup.optimistic,
+                ordered: setup.ordered,
+            };
+
+            let (message_client, _) = gateway.message_client_address(&setup.program_id);
+
+            self.add_state_account(message_client, gateway.program_id, &client);
+
+            if let Some(limit) = &setup.rate_limit {
+                self.add_state_account(
+                    gateway.client_rate_limit_address(&setup.program_id).0,
+                    gateway.program_id,
+                    &RateLimitState::new(limit, 0),
+                );
+            }
+
+            self.add_raw_account(
+                gateway.client_treasury_address(&setup.program_id).0,
+                system_program::id(),
+                vec![],
+                setup.treasury_lamports,
+            );
+
+            let mut extra_account_metas =
+                vec![0; ExtraAccountMetaList::size_of(setup.extra_account_metas.len()).unwrap()];
+
+            ExtraAccountMetaList::init::<ProcessMessageInstruction>(
+                &mut extra_account_metas,
+                &setup.extra_account_metas,
+            )
+            .unwrap();
+
+            self.add_raw_account(
+                get_extra_account_metas_address(&message_client, &setup.program_id),
+                setup.program_id,
+                extra_account_metas,
+                0,
+            );
+
+            if let Some(exsig) = setup.exsig {
+                exsigs.insert(setup.program_id, exsig);
+            }
+        }
+
+        TestKit {
+            context: self.program_test.start_with_context().await,
+            gateway,
+            admin: self.admin,
+            chain_id: self.chain_id,
+            chainsig: self.chainsig,
+            digest_scheme: self.digest_scheme,
+            bls_validators: self.bls_validators,
+            ed25519_validators: self.ed25519_validators,
+            exsigs,
+            next_tx_id: 1,
+        }
+    }
+}
+
+/// Outcome of a message delivered by `TestKit::deliver`
+#[derive(Debug)]
+pub struct Delivery {
+    pub tx_id: u128,
+    /// Empty unless the gateway runs from its SBF build, see `TestKit::process`
+    pub events: Vec<GatewayEvent>,
+}
+
+pub struct TestKit {
+    pub context: ProgramTestContext,
+    pub gateway: Gateway,
+    /// Owner of the config and authority of the clients without an explicit authority
+    pub admin: Keypair,
+    /// Chain ID of the gateway, the destination of every signed message
+    pub chain_id: u64,
+    pub chainsig: Option<MockEvmSigner>,
+    pub digest_scheme: DigestScheme,
+    /// Every validator signs the messages of a gateway with BLS verification enabled
+    pub bls_validators: Vec<BlsSecretKey>,
+    /// Every validator signs the messages of a gateway with Ed25519 verification enabled
+    pub ed25519_validators: Vec<Keypair>,
+    exsigs: HashMap<Pubkey, MockEvmSigner>,
+    next_tx_id: u128,
+}
+
+impl TestKit {
+    pub fn builder() -> TestKitBuilder {
+        TestKitBuilder::new()
+    }
+
+    pub fn fetcher(&self) -> BanksFetcher {
+        BanksFetcher(self.context.banks_client.clone())
+    }
+
+    /// Processes `instructions` paid by the context payer and returns the gateway events they
+    /// emitted. Events are only logged by the gateway loaded from its SBF build (`cargo test-sbf`),
+    /// the native processor prints them to stdout and the list is empty.
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Vec<GatewayEvent>, BanksClientError> {
+        let blockhash = self.context.get_new_latest_blockhash().await?;
+
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+
+        let processed = self
+            .context
+            .banks_client
+            .process_transaction_with_metadata(tx)
+            .await?;
+
+        processed.result?;
+
+        let logs = processed
+            .metadata
+            .map(|metadata| metadata.log_messages)
+            .unwrap_or_default();
+
+        Ok(parse_events_from_logs(&self.gateway.program_id, &logs))
+    }
+
+    /// `ReceiveMessage` of `payload` from `sender` on `source_chain_id`, signed by the exsig of
+    /// the receiver and the BLS validators or the chainsig. `payload` is the body after the
+    /// receiver the delivered payload starts with. Tests of rejected messages can tamper with it
+    /// before passing it to `deliver_message`.
+    pub fn signed_message(
+        &mut self,
+        source_chain_id: u64,
+        sender: ForeignAddress,
+        receiver: &Pubkey,
+        payload: Vec<u8>,
+    ) -> ReceiveMessage {
+        self.message(source_chain_id, sender, receiver, payload, true)
+    }
+
+    /// `ReceiveMessage` of `payload` like `signed_message`, without the validator signature
+    /// messages delivered as part of a batch or to optimistic clients do without
+    pub fn batched_message(
+        &mut self,
+        source_chain_id: u64,
+        sender: ForeignAddress,
+        receiver: &Pubkey,
+        payload: Vec<u8>,
+    ) -> ReceiveMessage {
+        self.message(source_chain_id, sender, receiver, payload, false)
+    }
+
+    fn message(
+        &mut self,
+        source_chain_id: u64,
+        sender: ForeignAddress,
+        receiver: &Pubkey,
+        payload: Vec<u8>,
+        validator_signed: bool,
+    ) -> ReceiveMessage {
+        let tx_id = self.next_tx_id;
+        self.next_tx_id += 1;
+
+        let payload = [receiver.as_ref(), &payload].concat();
+
+        let digest = MessageDigest::new(
+            tx_id,
+            source_chain_id,
+            self.chain_id,
+            sender,
+            *receiver,
+            self.gateway.program_id,
+            &payload,
+        );
+
+        let mut data = vec![];
+
+        if let Some(exsig) = self.exsigs.get(receiver) {
+            data.push(exsig.sign_digest(&digest, self.digest_scheme));
+        }
+
+        if validator_signed {
+            data.extend(self.validator_signature(&digest.hash(self.digest_scheme)));
+        }
+
+        data.push(payload);
+
+        ReceiveMessage {
+            tx_id,
+            dest_chain_id: self.chain_id,
+            receiver: *receiver,
+            data,
+            source_chain_id,
+            sender,
+        }
+    }
+
+    /// BLS proof of the validators or chainsig signature over `hash`, `None` when the gateway
+    /// verifies neither
+    fn validator_signature(&self, hash: &[u8; 32]) -> Option<Vec<u8>> {
+        if !self.bls_validators.is_empty() {
+            let mut aggregator = BlsAggregator::new(
+                *hash,
+                self.bls_validators
+                    .iter()
+                    .map(BlsSecretKey::public_key)
+                    .collect(),
+            );
+
+            for (index, validator) in self.bls_validators.iter().enumerate() {
+                aggregator.add(index, &validator.sign(hash)).unwrap();
+            }
+
+            Some(aggregator.proof().try_to_vec().unwrap())
+        } else {
+            self.chainsig.as_ref().map(|chainsig| chainsig.sign(hash))
+        }
+    }
+
+    /// Digest hash of `message`, the hash its signatures and batch proofs are over
+    pub fn message_hash(&self, message: &ReceiveMessage) -> [u8; 32] {
+        MessageDigest::new(
+            message.tx_id,
+            message.source_chain_id,
+            message.dest_chain_id,
+            message.sender,
+            message.receiver,
+            self.gateway.program_id,
+            message.data.last().expect("payload of the message"),
+        )
+        .hash(self.digest_scheme)
+    }
+
+    /// Evidence against a commitment to the `tx_id` of `message` other than `message`, signed by
+    /// the BLS validators or the chainsig
+    pub fn fraud_evidence(&self, message: &ReceiveMessage) -> FraudEvidence {
+        FraudEvidence {
+            source_chain_id: message.source_chain_id,
+            sender: message.sender,
+            receiver: message.receiver,
+            payload_hash: keccak256(message.data.last().expect("payload of the message")),
+            signature: self
+                .validator_signature(&self.message_hash(message))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Moves the cluster clock `seconds` ahead, e.g. past a challenge period
+    pub async fn advance_clock(&mut self, seconds: i64) {
+        let mut clock: Clock = self
+            .context
+            .banks_client
+            .get_sysvar()
+            .await
+            .expect("clock sysvar");
+
+        clock.unix_timestamp += seconds;
+
+        self.context.set_sysvar(&clock);
+    }
+
+    /// Ed25519 program instruction with the signatures of the Ed25519 validators over `message`
+    pub fn ed25519_signatures(&self, message: &ReceiveMessage) -> Instruction {
+        self.ed25519_instruction(&self.message_hash(message))
+    }
+
+    fn ed25519_instruction(&self, hash: &[u8; 32]) -> Instruction {
+        let signatures: Vec<_> = self
+            .ed25519_validators
+            .iter()
+            .map(|validator| {
+                (
+                    validator.pubkey(),
+                    validator.sign_message(hash).as_ref().try_into().unwrap(),
+                )
+            })
+            .collect();
+
+        ed25519::verify_instruction(&signatures, hash)
+    }
+
+    /// Relays `message` with the extra accounts of the receiver resolved from its meta list,
+    /// after the signatures of the Ed25519 validators
+    pub async fn deliver_message(
+        &mut self,
+        message: ReceiveMessage,
+    ) -> Result<Vec<GatewayEvent>, BanksClientError> {
+        let mut instructions = vec![];
+
+        if !self.ed25519_validators.is_empty() {
+            instructions.push(self.ed25519_signatures(&message));
+        }
+
+        instructions.push(
+            self.gateway
+                .receive_message(&self.fetcher(), &self.context.payer.pubkey(), message)
+                .await
+                .expect("extra accounts of the receiver"),
+        );
+
+        self.process(&instructions, &[]).await
+    }
+
+    /// Posts the root over `messages`, all from one source chain, as its batch `batch` signed by
+    /// the validators, then delivers every message with its proof against the root
+    pub async fn deliver_batch(
+        &mut self,
+        batch: u64,
+        messages: Vec<ReceiveMessage>,
+    ) -> Result<Vec<GatewayEvent>, BanksClientError> {
+        let source_chain_id = messages
+            .first()
+            .expect("messages of the batch")
+            .source_chain_id;
+
+        let leaves: Vec<[u8; 32]> = messages
+            .iter()
+            .map(|message| self.message_hash(message))
+            .collect();
+
+        let root = merkle::root(&leaves).unwrap();
+
+        let hash = RootDigest {
+            source_chain_id,
+            dest_chain_id: self.chain_id,
+            batch,
+            root,
+            gateway: self.gateway.program_id,
+        }
+        .hash(self.digest_scheme);
+
+        let mut instructions = vec![];
+
+        if !self.ed25519_validators.is_empty() {
+            instructions.push(self.ed25519_instruction(&hash));
+        }
+
+        instructions.push(self.gateway.post_message_root(
+            &self.context.payer.pubkey(),
+            PostMessageRoot {
+                source_chain_id,
+                batch,
+                root,
+                signature: self.validator_signature(&hash).unwrap_or_default(),
+            },
+        ));
+
+        let mut events = self.process(&instructions, &[]).await?;
+
+        for (index, message) in messages.into_iter().enumerate() {
+            let proof = BatchProof {
+                batch,
+                proof: merkle::proof(&leaves, index).unwrap(),
+            };
+
+            let instruction = self
+                .gateway
+                .receive_batched_message(
+                    &self.fetcher(),
+                    &self.context.payer.pubkey(),
+                    message,
+                    proof,
+                )
+                .await
+                .expect("extra accounts of the receiver");
+
+            events.extend(self.process(&[instruction], &[]).await?);
+        }
+
+        Ok(events)
+    }
+
+    /// Delivers `payload` from `sender` on `source_chain_id` to the hook program `receiver`, the
+    /// delivered payload being `receiver || payload`
+    pub async fn deliver(
+        &mut self,
+        source_chain_id: u64,
+        sender: ForeignAddress,
+        receiver: &Pubkey,
+        payload: Vec<u8>,
+    ) -> Result<Delivery, BanksClientError> {
+        let message = self.signed_message(source_chain_id, sender, receiver, payload);
+        let tx_id = message.tx_id;
+
+        let events = self.deliver_message(message).await?;
+
+        Ok(Delivery { tx_id, events })
+    }
+
+    /// Sends `payload` from `sender` through the sender program and reads back the stored
+    /// outbound message
+    pub async fn send(
+        &mut self,
+        sender: &Pubkey,
+        destination_chain_id: u64,
+        destination: ForeignAddress,
+        payload: Vec<u8>,
+    ) -> Result<SenderMessage, BanksClientError> {
+        let instruction = self.gateway.send_data(
+            &self.context.payer.pubkey(),
+            sender,
+            destination_chain_id,
+            destination,
+            payload,
+        );
+
+        self.process(&[instruction], &[]).await?;
+
+        Ok(self
+            .gateway
+            .get_sender_message(&self.fetcher(), sender)
+            .await
+            .expect("outbound message of the sender"))
+    }
+}
@@ -0,0 +1,5 @@
+pub mod kit;
+pub mod signer;
+
+pub use kit::{gateway_error, BanksFetcher, ClientSetup, Delivery, TestKit, TestKitBuilder};
+pub use signer::MockEvmSigner;
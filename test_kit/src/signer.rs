@@ -0,0 +1,52 @@
+use libsecp256k1::{Message, PublicKey, SecretKey};
+use mv3_contract_solana::{
+    instruction::MessageDigest,
+    state::config::{DigestScheme, ForeignAddress},
+    utils::public_key_to_address,
+};
+use solana_program::keccak;
+
+/// secp256k1 key standing in for an EVM chainsig or exsig signer
+#[derive(Clone)]
+pub struct MockEvmSigner {
+    secret: SecretKey,
+}
+
+impl MockEvmSigner {
+    /// Derives the key from `seed`, so the same seed always yields the same address
+    pub fn from_seed(seed: &[u8]) -> Self {
+        let mut hash = keccak::hash(seed).to_bytes();
+
+        loop {
+            match SecretKey::parse(&hash) {
+                Ok(secret) => return Self { secret },
+                Err(_) => hash = keccak::hash(&hash).to_bytes(),
+            }
+        }
+    }
+
+    /// EVM address of the key left padded to 32 bytes, as stored in the config and clients
+    pub fn address(&self) -> ForeignAddress {
+        let public_key = PublicKey::from_secret_key(&self.secret).serialize();
+
+        let mut address = [0; 32];
+        address[12..].copy_from_slice(&public_key_to_address(&public_key));
+
+        address
+    }
+
+    /// 65 byte `r || s || v` signature over `hash` with `v` in 27/28, like EVM wallets produce
+    pub fn sign(&self, hash: &[u8; 32]) -> Vec<u8> {
+        let (signature, recovery_id) = libsecp256k1::sign(&Message::parse(hash), &self.secret);
+
+        let mut bytes = signature.serialize().to_vec();
+        bytes.push(27 + recovery_id.serialize());
+
+        bytes
+    }
+
+    /// Signs `digest` hashing it under `scheme`
+    pub fn sign_digest(&self, digest: &MessageDigest, scheme: DigestScheme) -> Vec<u8> {
+        self.sign(&digest.hash(scheme))
+    }
+}
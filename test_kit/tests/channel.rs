@@ -0,0 +1,140 @@
+#![cfg(feature = "test-sbf")]
+
+use message_hook::instruction::MessageHookInstruction;
+use mv3_client::error::ClientError;
+use mv3_contract_solana::{
+    error::MessengerError,
+    state::chain::{AddressFormat, ChainConfig},
+};
+use mv3_test_kit::{gateway_error, ClientSetup, MockEvmSigner, TestKit};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey, rent::Rent, system_instruction,
+};
+use solana_program_test::{processor, tokio};
+use solana_sdk::signer::Signer;
+
+fn process_echo(_program_id: &Pubkey, _accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    match MessageHookInstruction::unpack(data)? {
+        MessageHookInstruction::ProcessMessage { data } => {
+            msg!("Echo: {:?}", data);
+            Ok(())
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+async fn start_with_echo(ordered: bool) -> (TestKit, Pubkey) {
+    let echo = Pubkey::new_unique();
+
+    let mut builder = TestKit::builder();
+
+    builder
+        .program_test()
+        .add_program("echo_hook", echo, processor!(process_echo));
+
+    let mut client = ClientSetup::new(echo);
+    client.ordered = ordered;
+
+    let kit = builder
+        .chain(ChainConfig::new(5, "evm", AddressFormat::Evm))
+        .client(client)
+        .start()
+        .await;
+
+    (kit, echo)
+}
+
+/// Payload carrying the channel sequence number `sequence` after the receiver
+fn payload(sequence: u64) -> Vec<u8> {
+    [&sequence.to_be_bytes()[..], b"transfer"].concat()
+}
+
+#[tokio::test]
+async fn test_ordered_channel_rejects_gaps() {
+    let (mut kit, echo) = start_with_echo(true).await;
+
+    let sender = MockEvmSigner::from_seed(b"sender").address();
+
+    kit.deliver(5, sender, &echo, payload(0)).await.unwrap();
+
+    //n + 2 skips a message of the channel
+    let error = kit.deliver(5, sender, &echo, payload(2)).await.unwrap_err();
+
+    assert_eq!(
+        gateway_error(&error),
+        Some(MessengerError::OutOfOrderMessage)
+    );
+
+    //n + 1 is the next one
+    let delivery = kit.deliver(5, sender, &echo, payload(1)).await.unwrap();
+
+    let received = kit
+        .gateway
+        .get_received_message(&kit.fetcher(), delivery.tx_id)
+        .await
+        .unwrap();
+
+    assert_eq!(received.destination, echo);
+
+    let channel = kit
+        .gateway
+        .get_channel(&kit.fetcher(), 5, &sender, &echo)
+        .await
+        .unwrap();
+
+    assert_eq!(channel.next_sequence, 2);
+
+    //other senders have channels of their own
+    let other = MockEvmSigner::from_seed(b"other").address();
+
+    kit.deliver(5, other, &echo, payload(0)).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_unordered_client_ignores_sequence() {
+    let (mut kit, echo) = start_with_echo(false).await;
+
+    let sender = MockEvmSigner::from_seed(b"sender").address();
+
+    for sequence in [7, 3] {
+        kit.deliver(5, sender, &echo, payload(sequence))
+            .await
+            .unwrap();
+    }
+
+    assert!(matches!(
+        kit.gateway
+            .get_channel(&kit.fetcher(), 5, &sender, &echo)
+            .await,
+        Err(ClientError::AccountNotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_ordered_channel_created_at_funded_address() {
+    let (mut kit, echo) = start_with_echo(true).await;
+
+    let sender = MockEvmSigner::from_seed(b"sender").address();
+
+    let (channel, _) = kit.gateway.channel_address(5, &sender, &echo);
+
+    //lamports sent to the address ahead of time don't block the channel
+    let transfer = system_instruction::transfer(
+        &kit.context.payer.pubkey(),
+        &channel,
+        Rent::default().minimum_balance(0),
+    );
+
+    kit.process(&[transfer], &[]).await.unwrap();
+
+    kit.deliver(5, sender, &echo, payload(0)).await.unwrap();
+
+    let channel = kit
+        .gateway
+        .get_channel(&kit.fetcher(), 5, &sender, &echo)
+        .await
+        .unwrap();
+
+    assert_eq!(channel.next_sequence, 1);
+}
@@ -0,0 +1,446 @@
+#![cfg(feature = "test-sbf")]
+
+use mv3_client::pda::program_data_address;
+use mv3_contract_solana::{
+    error::MessengerError,
+    events::GatewayEvent,
+    instruction::{PauseTarget, SendMessage, SetPause, TransferClientAuthority},
+    state::{
+        chain::{AddressFormat, ChainConfig},
+        config::MessageClient,
+        rate_limit::RateLimitConfig,
+    },
+};
+use mv3_test_kit::{gateway_error, ClientSetup, TestKit};
+use solana_program::{
+    instruction::Instruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, rent::Rent,
+    system_instruction,
+};
+use solana_program_test::tokio;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+/// Gateway with a client of `authority` holding a funded treasury
+async fn start_with_client(authority: &Keypair) -> (TestKit, Pubkey) {
+    let destination_contract = Pubkey::new_unique();
+
+    let mut client = ClientSetup::new(destination_contract);
+    client.authority = Some(authority.pubkey());
+
+    let kit = TestKit::builder()
+        .upgradeable_program(destination_contract, None)
+        .client(client)
+        .start()
+        .await;
+
+    (kit, destination_contract)
+}
+
+/// Client of `destination_contract` with `authority` and no chains
+fn client(destination_contract: Pubkey, authority: Pubkey) -> MessageClient {
+    MessageClient {
+        version: MessageClient::VERSION,
+        authority,
+        destination_contract,
+        notify_on_failure: false,
+        supported_chains: vec![],
+        allowed_contracts: vec![],
+        exsig: None,
+        paused: false,
+        pending_authority: None,
+        pending_exsig: None,
+        rate_limit: None,
+        optimistic: false,
+        ordered: false,
+    }
+}
+
+/// Processes `instruction` signed by `signer`, which the context payer funds first
+async fn process_as(
+    kit: &mut TestKit,
+    instruction: Instruction,
+    signer: &Keypair,
+) -> Result<Vec<GatewayEvent>, MessengerError> {
+    let fund = system_instruction::transfer(
+        &kit.context.payer.pubkey(),
+        &signer.pubkey(),
+        LAMPORTS_PER_SOL,
+    );
+
+    kit.process(&[fund, instruction], &[signer])
+        .await
+        .map_err(|error| gateway_error(&error).expect("gateway error"))
+}
+
+async fn pause(
+    kit: &mut TestKit,
+    authority: &Keypair,
+    destination_contract: Pubkey,
+    paused: bool,
+) -> Result<(), MessengerError> {
+    let instruction = kit.gateway.set_pause(
+        &authority.pubkey(),
+        SetPause {
+            target: PauseTarget::Client(destination_contract),
+            paused,
+        },
+    );
+
+    kit.process(&[instruction], &[authority])
+        .await
+        .map(|_| ())
+        .map_err(|error| gateway_error(&error).expect("gateway error"))
+}
+
+async fn close(
+    kit: &mut TestKit,
+    authority: &Keypair,
+    destination_contract: Pubkey,
+) -> Result<Vec<GatewayEvent>, MessengerError> {
+    let instruction = kit
+        .gateway
+        .close_client(&authority.pubkey(), destination_contract);
+
+    kit.process(&[instruction], &[authority])
+        .await
+        .map_err(|error| gateway_error(&error).expect("gateway error"))
+}
+
+async fn lamports(kit: &mut TestKit, address: Pubkey) -> u64 {
+    kit.context.banks_client.get_balance(address).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_close_client_returns_rent() {
+    let authority = Keypair::new();
+    let destination_contract = Pubkey::new_unique();
+
+    let mut client = ClientSetup::new(destination_contract);
+    client.authority = Some(authority.pubkey());
+    client.rate_limit = Some(RateLimitConfig {
+        max_messages: 10,
+        max_bytes: 1_000,
+        window: 60,
+    });
+
+    let mut kit = TestKit::builder()
+        .upgradeable_program(destination_contract, None)
+        .client(client)
+        .start()
+        .await;
+
+    let (message_client, _) = kit.gateway.message_client_address(&destination_contract);
+    let (treasury, _) = kit.gateway.client_treasury_address(&destination_contract);
+    let (rate_limit, _) = kit.gateway.client_rate_limit_address(&destination_contract);
+
+    let client_lamports = lamports(&mut kit, message_client).await;
+    let treasury_lamports = lamports(&mut kit, treasury).await;
+    let rate_limit_lamports = lamports(&mut kit, rate_limit).await;
+
+    //the treasury holds its rent on top of the deposit
+    assert_eq!(
+        treasury_lamports,
+        LAMPORTS_PER_SOL + Rent::default().minimum_balance(0)
+    );
+    assert!(rate_limit_lamports > 0);
+
+    //the authority drains its client without the owner
+    pause(&mut kit, &authority, destination_contract, true)
+        .await
+        .unwrap();
+
+    close(&mut kit, &authority, destination_contract)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        lamports(&mut kit, authority.pubkey()).await,
+        client_lamports + treasury_lamports + rate_limit_lamports
+    );
+
+    assert_eq!(lamports(&mut kit, treasury).await, 0);
+
+    for closed in [message_client, rate_limit] {
+        assert!(kit
+            .context
+            .banks_client
+            .get_account(closed)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_close_active_client() {
+    let authority = Keypair::new();
+    let (mut kit, destination_contract) = start_with_client(&authority).await;
+
+    //relayers could still be delivering to it
+    assert_eq!(
+        close(&mut kit, &authority, destination_contract).await,
+        Err(MessengerError::ClientNotPaused)
+    );
+
+    //the pause of an authority is lifted by the owner only
+    pause(&mut kit, &authority, destination_contract, true)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        pause(&mut kit, &authority, destination_contract, false).await,
+        Err(MessengerError::CallerNotOwner)
+    );
+}
+
+#[tokio::test]
+async fn test_close_client_by_other_authority() {
+    let authority = Keypair::new();
+    let (mut kit, destination_contract) = start_with_client(&authority).await;
+
+    let other = Keypair::new();
+
+    assert_eq!(
+        pause(&mut kit, &other, destination_contract, true).await,
+        Err(MessengerError::CallerNotSuper)
+    );
+
+    pause(&mut kit, &authority, destination_contract, true)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        close(&mut kit, &other, destination_contract).await,
+        Err(MessengerError::InvalidUpdateAuthority)
+    );
+
+    let (message_client, _) = kit.gateway.message_client_address(&destination_contract);
+
+    assert!(lamports(&mut kit, message_client).await > 0);
+}
+
+#[tokio::test]
+async fn test_register_client_by_upgrade_authority() {
+    let upgrade_authority = Keypair::new();
+    let program = Pubkey::new_unique();
+
+    let mut kit = TestKit::builder()
+        .upgradeable_program(program, Some(upgrade_authority.pubkey()))
+        .start()
+        .await;
+
+    //anyone else is turned away
+    let other = Keypair::new();
+
+    let instruction = kit
+        .gateway
+        .configure_client(&other.pubkey(), client(program, other.pubkey()));
+
+    assert_eq!(
+        process_as(&mut kit, instruction, &other).await,
+        Err(MessengerError::InvalidUpdateAuthority)
+    );
+
+    let instruction = kit.gateway.configure_client(
+        &upgrade_authority.pubkey(),
+        client(program, upgrade_authority.pubkey()),
+    );
+
+    process_as(&mut kit, instruction, &upgrade_authority)
+        .await
+        .unwrap();
+
+    let registered = kit
+        .gateway
+        .get_message_client(&kit.fetcher(), &program)
+        .await
+        .unwrap();
+
+    assert_eq!(registered.authority, upgrade_authority.pubkey());
+}
+
+#[tokio::test]
+async fn test_register_client_at_funded_address() {
+    let upgrade_authority = Keypair::new();
+    let program = Pubkey::new_unique();
+
+    let mut kit = TestKit::builder()
+        .upgradeable_program(program, Some(upgrade_authority.pubkey()))
+        .start()
+        .await;
+
+    //lamports sent to the address ahead of time don't block the registration
+    let (message_client, _) = kit.gateway.message_client_address(&program);
+
+    let transfer = system_instruction::transfer(
+        &kit.context.payer.pubkey(),
+        &message_client,
+        Rent::default().minimum_balance(0),
+    );
+
+    kit.process(&[transfer], &[]).await.unwrap();
+
+    let instruction = kit.gateway.configure_client(
+        &upgrade_authority.pubkey(),
+        client(program, upgrade_authority.pubkey()),
+    );
+
+    process_as(&mut kit, instruction, &upgrade_authority)
+        .await
+        .unwrap();
+
+    let registered = kit
+        .gateway
+        .get_message_client(&kit.fetcher(), &program)
+        .await
+        .unwrap();
+
+    assert_eq!(registered.authority, upgrade_authority.pubkey());
+}
+
+#[tokio::test]
+async fn test_register_client_with_spoofed_program_data() {
+    let upgrade_authority = Keypair::new();
+    let attacker = Keypair::new();
+    let program = Pubkey::new_unique();
+    let decoy = Pubkey::new_unique();
+
+    let mut kit = TestKit::builder()
+        .upgradeable_program(program, Some(upgrade_authority.pubkey()))
+        .upgradeable_program(decoy, Some(attacker.pubkey()))
+        .start()
+        .await;
+
+    //program data of another program naming the attacker as upgrade authority
+    let mut instruction = kit
+        .gateway
+        .configure_client(&attacker.pubkey(), client(program, attacker.pubkey()));
+
+    instruction.accounts[3].pubkey = program_data_address(&decoy);
+
+    assert_eq!(
+        process_as(&mut kit, instruction, &attacker).await,
+        Err(MessengerError::InvalidClientProgramId)
+    );
+}
+
+#[tokio::test]
+async fn test_transfer_client_authority() {
+    let authority = Keypair::new();
+    let (mut kit, destination_contract) = start_with_client(&authority).await;
+
+    let new_authority = Keypair::new();
+
+    let instruction = kit.gateway.transfer_client_authority(
+        &authority.pubkey(),
+        TransferClientAuthority {
+            destination_contract,
+            new_authority: Some(new_authority.pubkey()),
+        },
+    );
+
+    process_as(&mut kit, instruction, &authority).await.unwrap();
+
+    //only the pending authority accepts
+    let other = Keypair::new();
+
+    let instruction = kit
+        .gateway
+        .accept_client_authority(&other.pubkey(), destination_contract);
+
+    assert_eq!(
+        process_as(&mut kit, instruction, &other).await,
+        Err(MessengerError::NotPendingAuthority)
+    );
+
+    let instruction = kit
+        .gateway
+        .accept_client_authority(&new_authority.pubkey(), destination_contract);
+
+    process_as(&mut kit, instruction, &new_authority)
+        .await
+        .unwrap();
+
+    let transferred = kit
+        .gateway
+        .get_message_client(&kit.fetcher(), &destination_contract)
+        .await
+        .unwrap();
+
+    assert_eq!(transferred.authority, new_authority.pubkey());
+    assert_eq!(transferred.pending_authority, None);
+
+    //the old authority is locked out
+    let instruction = kit.gateway.configure_client(
+        &authority.pubkey(),
+        client(destination_contract, authority.pubkey()),
+    );
+
+    assert_eq!(
+        process_as(&mut kit, instruction, &authority).await,
+        Err(MessengerError::InvalidUpdateAuthority)
+    );
+
+    let instruction = kit.gateway.transfer_client_authority(
+        &authority.pubkey(),
+        TransferClientAuthority {
+            destination_contract,
+            new_authority: Some(authority.pubkey()),
+        },
+    );
+
+    assert_eq!(
+        process_as(&mut kit, instruction, &authority).await,
+        Err(MessengerError::InvalidUpdateAuthority)
+    );
+
+    assert_eq!(
+        close(&mut kit, &authority, destination_contract).await,
+        Err(MessengerError::InvalidUpdateAuthority)
+    );
+}
+
+#[tokio::test]
+async fn test_paused_client_cannot_send() {
+    //programs registered as clients send from their own address
+    let sender = Keypair::new();
+
+    let mut client = ClientSetup::new(sender.pubkey());
+    client.authority = Some(sender.pubkey());
+
+    let mut kit = TestKit::builder()
+        .chain(ChainConfig::new(5, "evm", AddressFormat::Evm))
+        .client(client)
+        .start()
+        .await;
+
+    let send = |kit: &TestKit| {
+        kit.gateway.send(
+            &sender.pubkey(),
+            SendMessage {
+                recipient: [9; 32],
+                chain: 5,
+                confirmations: 0,
+                data: vec![1, 2, 3],
+            },
+        )
+    };
+
+    //an active client gets as far as the check of the caller program
+    let instruction = send(&kit);
+
+    assert_eq!(
+        process_as(&mut kit, instruction, &sender).await,
+        Err(MessengerError::CallerProgramNotConfigured)
+    );
+
+    pause(&mut kit, &sender, sender.pubkey(), true)
+        .await
+        .unwrap();
+
+    let instruction = send(&kit);
+
+    assert_eq!(
+        process_as(&mut kit, instruction, &sender).await,
+        Err(MessengerError::ClientPaused)
+    );
+}
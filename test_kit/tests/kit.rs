@@ -0,0 +1,253 @@
+#![cfg(feature = "test-sbf")]
+
+use message_hook::instruction::MessageHookInstruction;
+use mv3_client::bls::BlsSecretKey;
+use mv3_contract_solana::{
+    error::MessengerError,
+    instruction::BatchProof,
+    state::chain::{AddressFormat, ChainConfig},
+};
+use mv3_test_kit::{gateway_error, ClientSetup, MockEvmSigner, TestKit};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use solana_program_test::{processor, tokio};
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+fn process_echo(_program_id: &Pubkey, _accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    match MessageHookInstruction::unpack(data)? {
+        MessageHookInstruction::ProcessMessage { data } => {
+            msg!("Echo: {:?}", data);
+            Ok(())
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+async fn start_with_echo(exsig: Option<MockEvmSigner>) -> (TestKit, Pubkey) {
+    let echo = Pubkey::new_unique();
+
+    let mut builder = TestKit::builder();
+
+    builder
+        .program_test()
+        .add_program("echo_hook", echo, processor!(process_echo));
+
+    let mut client = ClientSetup::new(echo);
+    client.exsig = exsig;
+
+    let kit = builder
+        .chain(ChainConfig::new(5, "evm", AddressFormat::Evm))
+        .client(client)
+        .start()
+        .await;
+
+    (kit, echo)
+}
+
+#[tokio::test]
+async fn test_deliver_to_hook() {
+    let (mut kit, echo) = start_with_echo(Some(MockEvmSigner::from_seed(b"exsig"))).await;
+
+    let sender = MockEvmSigner::from_seed(b"sender").address();
+
+    let delivery = kit.deliver(5, sender, &echo, vec![1, 2, 3]).await.unwrap();
+
+    //the record of a delivered message only persists when its execution succeeded
+    let received = kit
+        .gateway
+        .get_received_message(&kit.fetcher(), delivery.tx_id)
+        .await
+        .unwrap();
+
+    assert_eq!(received.tx_id, delivery.tx_id);
+    assert_eq!(received.destination, echo);
+}
+
+#[tokio::test]
+async fn test_deliver_rejects_foreign_chainsig() {
+    let (mut kit, echo) = start_with_echo(None).await;
+
+    let mut message = kit.signed_message(5, [7; 32], &echo, vec![1, 2, 3]);
+
+    message.data[0] = MockEvmSigner::from_seed(b"other").sign(&[0; 32]);
+
+    assert!(kit.deliver_message(message).await.is_err());
+}
+
+#[tokio::test]
+async fn test_deliver_with_bls_validators() {
+    let echo = Pubkey::new_unique();
+
+    let validators = (0..4u8)
+        .map(|i| BlsSecretKey::from_seed(&[b'v', i]))
+        .collect();
+
+    let mut builder = TestKit::builder().bls_validators(validators, 3);
+
+    builder
+        .program_test()
+        .add_program("echo_hook", echo, processor!(process_echo));
+
+    let mut kit = builder
+        .chain(ChainConfig::new(5, "evm", AddressFormat::Evm))
+        .client(ClientSetup::new(echo))
+        .start()
+        .await;
+
+    let sender = MockEvmSigner::from_seed(b"sender").address();
+
+    let delivery = kit.deliver(5, sender, &echo, vec![1, 2, 3]).await.unwrap();
+
+    let received = kit
+        .gateway
+        .get_received_message(&kit.fetcher(), delivery.tx_id)
+        .await
+        .unwrap();
+
+    assert_eq!(received.destination, echo);
+
+    //the proof is checked instead of the chainsig
+    let mut message = kit.signed_message(5, sender, &echo, vec![1, 2, 3]);
+
+    message.data[0] = kit.chainsig.as_ref().unwrap().sign(&[0; 32]);
+
+    let error = kit.deliver_message(message).await.unwrap_err();
+
+    assert_eq!(
+        gateway_error(&error),
+        Some(MessengerError::InvalidSignature)
+    );
+}
+
+#[tokio::test]
+async fn test_deliver_with_ed25519_validators() {
+    let echo = Pubkey::new_unique();
+
+    let validators = (0..3).map(|_| Keypair::new()).collect();
+
+    let mut builder = TestKit::builder().ed25519_validators(validators, 2);
+
+    builder
+        .program_test()
+        .add_program("echo_hook", echo, processor!(process_echo));
+
+    let mut kit = builder
+        .chain(ChainConfig::new(5, "evm", AddressFormat::Evm))
+        .client(ClientSetup::new(echo))
+        .start()
+        .await;
+
+    let sender = MockEvmSigner::from_seed(b"sender").address();
+
+    let delivery = kit.deliver(5, sender, &echo, vec![1, 2, 3]).await.unwrap();
+
+    let received = kit
+        .gateway
+        .get_received_message(&kit.fetcher(), delivery.tx_id)
+        .await
+        .unwrap();
+
+    assert_eq!(received.destination, echo);
+
+    //the signatures have to precede the message
+    let message = kit.signed_message(5, sender, &echo, vec![1, 2, 3]);
+
+    let instruction = kit
+        .gateway
+        .receive_message(&kit.fetcher(), &kit.context.payer.pubkey(), message)
+        .await
+        .unwrap();
+
+    let error = kit.process(&[instruction], &[]).await.unwrap_err();
+
+    assert_eq!(
+        gateway_error(&error),
+        Some(MessengerError::InvalidInstructionIndex)
+    );
+
+    //a single validator is below the threshold
+    kit.ed25519_validators.truncate(1);
+
+    let message = kit.signed_message(5, sender, &echo, vec![1, 2, 3]);
+
+    let error = kit.deliver_message(message).await.unwrap_err();
+
+    assert_eq!(
+        gateway_error(&error),
+        Some(MessengerError::Ed25519ThresholdNotMet)
+    );
+}
+
+#[tokio::test]
+async fn test_deliver_batch() {
+    let (mut kit, echo) = start_with_echo(Some(MockEvmSigner::from_seed(b"exsig"))).await;
+
+    let sender = MockEvmSigner::from_seed(b"sender").address();
+
+    let messages: Vec<_> = (0..3)
+        .map(|i| kit.batched_message(5, sender, &echo, vec![i]))
+        .collect();
+
+    let tx_ids: Vec<u128> = messages.iter().map(|message| message.tx_id).collect();
+
+    kit.deliver_batch(1, messages).await.unwrap();
+
+    for tx_id in tx_ids {
+        let received = kit
+            .gateway
+            .get_received_message(&kit.fetcher(), tx_id)
+            .await
+            .unwrap();
+
+        assert_eq!(received.destination, echo);
+    }
+
+    //a message outside the batch does not match its root
+    let message = kit.batched_message(5, sender, &echo, vec![9]);
+
+    let instruction = kit
+        .gateway
+        .receive_batched_message(
+            &kit.fetcher(),
+            &kit.context.payer.pubkey(),
+            message,
+            BatchProof {
+                batch: 1,
+                proof: vec![],
+            },
+        )
+        .await
+        .unwrap();
+
+    let error = kit.process(&[instruction], &[]).await.unwrap_err();
+
+    assert_eq!(
+        gateway_error(&error),
+        Some(MessengerError::InvalidMerkleProof)
+    );
+
+    //a batch is posted once
+    let message = kit.batched_message(5, sender, &echo, vec![9]);
+
+    let error = kit.deliver_batch(1, vec![message]).await.unwrap_err();
+
+    assert_eq!(
+        gateway_error(&error),
+        Some(MessengerError::RootAlreadyPosted)
+    );
+}
+
+#[tokio::test]
+async fn test_send_reads_back_message() {
+    let (mut kit, _) = start_with_echo(None).await;
+
+    let sender = Pubkey::new_unique();
+
+    let message = kit.send(&sender, 5, [9; 32], vec![4, 5, 6]).await.unwrap();
+
+    assert_eq!(message.sender, sender);
+    assert_eq!(message.destination_chain, 5);
+    assert_eq!(message.payload, vec![4, 5, 6]);
+}
@@ -0,0 +1,470 @@
+#![cfg(feature = "test-sbf")]
+
+use message_hook::instruction::MessageHookInstruction;
+use mv3_contract_solana::{
+    error::MessengerError,
+    events::GatewayEvent,
+    instruction::{DepositWithdraw, PostCommitment, ReceiveMessage},
+    state::{
+        chain::{AddressFormat, ChainConfig},
+        optimistic::OptimisticConfig,
+    },
+};
+use mv3_test_kit::{gateway_error, ClientSetup, MockEvmSigner, TestKit};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, native_token::LAMPORTS_PER_SOL,
+    program_error::ProgramError, pubkey::Pubkey, system_instruction,
+};
+use solana_program_test::{processor, tokio};
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+const CHALLENGE_PERIOD: i64 = 600;
+
+const MIN_BOND: u64 = LAMPORTS_PER_SOL;
+
+fn process_echo(_program_id: &Pubkey, _accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    match MessageHookInstruction::unpack(data)? {
+        MessageHookInstruction::ProcessMessage { data } => {
+            msg!("Echo: {:?}", data);
+            Ok(())
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+struct Optimistic {
+    kit: TestKit,
+    echo: Pubkey,
+    operators: Vec<Keypair>,
+    watcher: Keypair,
+}
+
+/// Gateway with an optimistic echo client, two bonded operators and a funded watcher
+async fn start() -> Optimistic {
+    let echo = Pubkey::new_unique();
+    let operators = vec![Keypair::new(), Keypair::new()];
+    let watcher = Keypair::new();
+
+    let mut builder = TestKit::builder().optimistic(
+        OptimisticConfig::new(CHALLENGE_PERIOD, MIN_BOND, vec![watcher.pubkey()]),
+        operators.iter().map(Keypair::pubkey).collect(),
+    );
+
+    builder
+        .program_test()
+        .add_program("echo_hook", echo, processor!(process_echo));
+
+    let mut client = ClientSetup::new(echo);
+    client.optimistic = true;
+
+    let mut kit = builder
+        .chain(ChainConfig::new(5, "evm", AddressFormat::Evm))
+        .client(client)
+        .start()
+        .await;
+
+    for operator in &operators {
+        let payer = kit.context.payer.pubkey();
+
+        let fund = system_instruction::transfer(&payer, &operator.pubkey(), 2 * MIN_BOND);
+        let bond = kit.gateway.deposit_withdraw_bond(
+            &operator.pubkey(),
+            DepositWithdraw::Deposit,
+            MIN_BOND,
+        );
+
+        kit.process(&[fund, bond], &[operator]).await.unwrap();
+    }
+
+    //the watcher pays the rent of its disputes
+    let fund = system_instruction::transfer(
+        &kit.context.payer.pubkey(),
+        &watcher.pubkey(),
+        LAMPORTS_PER_SOL,
+    );
+
+    kit.process(&[fund], &[]).await.unwrap();
+
+    Optimistic {
+        kit,
+        echo,
+        operators,
+        watcher,
+    }
+}
+
+impl Optimistic {
+    fn message(&mut self, payload: Vec<u8>) -> ReceiveMessage {
+        let sender = MockEvmSigner::from_seed(b"sender").address();
+
+        self.kit.batched_message(5, sender, &self.echo, payload)
+    }
+
+    async fn commit(
+        &mut self,
+        operator: usize,
+        message: &ReceiveMessage,
+        hash: [u8; 32],
+    ) -> Result<Vec<GatewayEvent>, MessengerError> {
+        let operator = &self.operators[operator];
+
+        let instruction = self.kit.gateway.post_commitment(
+            &operator.pubkey(),
+            PostCommitment {
+                tx_id: message.tx_id,
+                receiver: message.receiver,
+                hash,
+            },
+        );
+
+        self.kit
+            .process(&[instruction], &[operator])
+            .await
+            .map_err(|error| gateway_error(&error).expect("gateway error"))
+    }
+
+    async fn deliver(&mut self, message: &ReceiveMessage) -> Result<(), MessengerError> {
+        self.kit
+            .deliver_message(message.clone())
+            .await
+            .map(|_| ())
+            .map_err(|error| gateway_error(&error).expect("gateway error"))
+    }
+
+    async fn challenge(
+        &mut self,
+        operator: usize,
+        message: &ReceiveMessage,
+    ) -> Result<(), MessengerError> {
+        let instruction = self.kit.gateway.challenge_commitment(
+            &self.watcher.pubkey(),
+            &self.operators[operator].pubkey(),
+            message.tx_id,
+            self.kit.fraud_evidence(message),
+        );
+
+        self.kit
+            .process(&[instruction], &[&self.watcher])
+            .await
+            .map(|_| ())
+            .map_err(|error| gateway_error(&error).expect("gateway error"))
+    }
+
+    async fn dispute(&mut self, operator: usize, tx_id: u128) -> Result<(), MessengerError> {
+        let instruction = self.kit.gateway.dispute_commitment(
+            &self.watcher.pubkey(),
+            &self.operators[operator].pubkey(),
+            tx_id,
+        );
+
+        self.kit
+            .process(&[instruction], &[&self.watcher])
+            .await
+            .map(|_| ())
+            .map_err(|error| gateway_error(&error).expect("gateway error"))
+    }
+
+    async fn settle(
+        &mut self,
+        operator: usize,
+        tx_id: u128,
+        signature: Option<Vec<u8>>,
+    ) -> Result<(), MessengerError> {
+        let instruction =
+            self.kit
+                .gateway
+                .settle_dispute(&self.operators[operator].pubkey(), tx_id, signature);
+
+        self.kit
+            .process(&[instruction], &[])
+            .await
+            .map(|_| ())
+            .map_err(|error| gateway_error(&error).expect("gateway error"))
+    }
+
+    async fn bond(&mut self, operator: usize) -> u64 {
+        self.kit
+            .gateway
+            .get_operator_bond(&self.kit.fetcher(), &self.operators[operator].pubkey())
+            .await
+            .unwrap()
+            .amount
+    }
+}
+
+#[tokio::test]
+async fn test_commitment_executes_after_challenge_period() {
+    let mut test = start().await;
+
+    let message = test.message(vec![1, 2, 3]);
+    let hash = test.kit.message_hash(&message);
+
+    test.commit(0, &message, hash).await.unwrap();
+
+    assert_eq!(
+        test.deliver(&message).await,
+        Err(MessengerError::ChallengePeriodActive)
+    );
+
+    test.kit.advance_clock(CHALLENGE_PERIOD).await;
+
+    assert_eq!(test.deliver(&message).await, Ok(()));
+
+    let received = test
+        .kit
+        .gateway
+        .get_received_message(&test.kit.fetcher(), message.tx_id)
+        .await
+        .unwrap();
+
+    assert_eq!(received.destination, test.echo);
+
+    //a message is committed once
+    assert_eq!(
+        test.commit(1, &message, hash).await,
+        Err(MessengerError::MessageAlreadyProcessed)
+    );
+}
+
+#[tokio::test]
+async fn test_challenge_slashes_bond_and_blocks_message() {
+    let mut test = start().await;
+
+    let message = test.message(vec![1, 2, 3]);
+    let hash = test.kit.message_hash(&message);
+
+    test.commit(0, &message, [9; 32]).await.unwrap();
+
+    test.challenge(0, &message).await.unwrap();
+
+    assert_eq!(test.bond(0).await, 0);
+
+    test.kit.advance_clock(CHALLENGE_PERIOD).await;
+
+    assert_eq!(
+        test.deliver(&message).await,
+        Err(MessengerError::CommitmentChallenged)
+    );
+
+    //another operator commits to the signed message in place of the fraudulent commitment
+    test.commit(1, &message, hash).await.unwrap();
+
+    test.kit.advance_clock(CHALLENGE_PERIOD).await;
+
+    assert_eq!(test.deliver(&message).await, Ok(()));
+}
+
+#[tokio::test]
+async fn test_challenge_requires_contradicting_evidence() {
+    let mut test = start().await;
+
+    let message = test.message(vec![1, 2, 3]);
+    let hash = test.kit.message_hash(&message);
+
+    test.commit(0, &message, hash).await.unwrap();
+
+    //the signed message is the committed one
+    assert_eq!(
+        test.challenge(0, &message).await,
+        Err(MessengerError::NoFraudProven)
+    );
+
+    //evidence without a validator signature proves nothing
+    let mut forged = test.kit.fraud_evidence(&message);
+    forged.payload_hash = [7; 32];
+    forged.signature = MockEvmSigner::from_seed(b"forger").sign(&[0; 32]);
+
+    let instruction = test.kit.gateway.challenge_commitment(
+        &test.watcher.pubkey(),
+        &test.operators[0].pubkey(),
+        message.tx_id,
+        forged,
+    );
+
+    let error = test
+        .kit
+        .process(&[instruction], &[&test.watcher])
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        gateway_error(&error),
+        Some(MessengerError::InvalidSignature)
+    );
+
+    assert_eq!(test.bond(0).await, MIN_BOND);
+}
+
+#[tokio::test]
+async fn test_non_operator_cannot_commit() {
+    let mut test = start().await;
+
+    let message = test.message(vec![1, 2, 3]);
+    let hash = test.kit.message_hash(&message);
+
+    let outsider = Keypair::new();
+
+    let instruction = test.kit.gateway.post_commitment(
+        &outsider.pubkey(),
+        PostCommitment {
+            tx_id: message.tx_id,
+            receiver: message.receiver,
+            hash,
+        },
+    );
+
+    let error = test
+        .kit
+        .process(&[instruction], &[&outsider])
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        gateway_error(&error),
+        Some(MessengerError::CallerNotOperator)
+    );
+}
+
+#[tokio::test]
+async fn test_receive_rejects_mismatched_hash() {
+    let mut test = start().await;
+
+    let message = test.message(vec![1, 2, 3]);
+    let hash = test.kit.message_hash(&message);
+
+    test.commit(0, &message, hash).await.unwrap();
+
+    test.kit.advance_clock(CHALLENGE_PERIOD).await;
+
+    //the committed hash covers the payload
+    let mut tampered = message.clone();
+    *tampered.data.last_mut().unwrap() = vec![4, 5, 6];
+
+    assert_eq!(
+        test.deliver(&tampered).await,
+        Err(MessengerError::InvalidSignature)
+    );
+
+    assert_eq!(test.deliver(&message).await, Ok(()));
+
+    //a commitment to a hash other than the one of the message executes nothing
+    let message = test.message(vec![7, 8, 9]);
+
+    test.commit(1, &message, [9; 32]).await.unwrap();
+
+    test.kit.advance_clock(CHALLENGE_PERIOD).await;
+
+    assert_eq!(
+        test.deliver(&message).await,
+        Err(MessengerError::InvalidSignature)
+    );
+}
+
+#[tokio::test]
+async fn test_unanswered_dispute_slashes_bond() {
+    let mut test = start().await;
+
+    let message = test.message(vec![1, 2, 3]);
+    let hash = test.kit.message_hash(&message);
+
+    test.commit(0, &message, [9; 32]).await.unwrap();
+
+    //the claim of the watcher needs no evidence
+    assert_eq!(test.dispute(0, message.tx_id).await, Ok(()));
+    assert_eq!(
+        test.dispute(0, message.tx_id).await,
+        Err(MessengerError::CommitmentDisputed)
+    );
+
+    let commitment = test
+        .kit
+        .gateway
+        .get_commitment(&test.kit.fetcher(), message.tx_id)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        commitment.dispute.map(|dispute| dispute.watcher),
+        Some(test.watcher.pubkey())
+    );
+
+    assert_eq!(
+        test.settle(0, message.tx_id, None).await,
+        Err(MessengerError::DisputePeriodActive)
+    );
+
+    test.kit.advance_clock(CHALLENGE_PERIOD).await;
+
+    assert_eq!(test.settle(0, message.tx_id, None).await, Ok(()));
+    assert_eq!(test.bond(0).await, 0);
+
+    assert_eq!(
+        test.deliver(&message).await,
+        Err(MessengerError::CommitmentChallenged)
+    );
+
+    //the settled commitment is replaced like a challenged one
+    test.commit(1, &message, hash).await.unwrap();
+
+    test.kit.advance_clock(CHALLENGE_PERIOD).await;
+
+    assert_eq!(test.deliver(&message).await, Ok(()));
+}
+
+#[tokio::test]
+async fn test_dispute_cleared_by_validators() {
+    let mut test = start().await;
+
+    let message = test.message(vec![1, 2, 3]);
+    let hash = test.kit.message_hash(&message);
+
+    test.commit(0, &message, hash).await.unwrap();
+
+    let outsider = Keypair::new();
+
+    let instruction = test.kit.gateway.dispute_commitment(
+        &outsider.pubkey(),
+        &test.operators[0].pubkey(),
+        message.tx_id,
+    );
+
+    let error = test
+        .kit
+        .process(&[instruction], &[&outsider])
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        gateway_error(&error),
+        Some(MessengerError::CallerNotWatcher)
+    );
+
+    assert_eq!(test.dispute(0, message.tx_id).await, Ok(()));
+
+    //the dispute holds the message past the challenge period
+    test.kit.advance_clock(CHALLENGE_PERIOD).await;
+
+    assert_eq!(
+        test.deliver(&message).await,
+        Err(MessengerError::CommitmentDisputed)
+    );
+
+    //only the signature of the committed message answers the claim
+    let forged = MockEvmSigner::from_seed(b"forger").sign(&hash);
+
+    assert_eq!(
+        test.settle(0, message.tx_id, Some(forged)).await,
+        Err(MessengerError::InvalidSignature)
+    );
+
+    let signature = test.kit.fraud_evidence(&message).signature;
+
+    assert_eq!(test.settle(0, message.tx_id, Some(signature)).await, Ok(()));
+    assert_eq!(test.bond(0).await, MIN_BOND);
+    assert_eq!(
+        test.settle(0, message.tx_id, None).await,
+        Err(MessengerError::CommitmentNotDisputed)
+    );
+
+    assert_eq!(test.deliver(&message).await, Ok(()));
+}
@@ -0,0 +1,49 @@
+use mv3_contract_solana::{
+    instruction::MessageDigest, state::config::DigestScheme, utils::verify_evm_signature,
+};
+use mv3_test_kit::MockEvmSigner;
+use solana_program::pubkey::Pubkey;
+
+#[test]
+pub fn test_mock_signer_verifies() {
+    let signer = MockEvmSigner::from_seed(b"chainsig");
+
+    assert_eq!(
+        signer.address(),
+        MockEvmSigner::from_seed(b"chainsig").address()
+    );
+    assert_eq!(signer.address()[..12], [0; 12]);
+
+    let digest = MessageDigest::new(
+        1,
+        5,
+        19999999991,
+        [7; 32],
+        Pubkey::new_unique(),
+        mv3_contract_solana::id(),
+        &[1, 2, 3],
+    );
+
+    for scheme in [DigestScheme::PersonalSign, DigestScheme::Eip712] {
+        let hash = digest.hash(scheme);
+
+        let signature = signer.sign_digest(&digest, scheme);
+
+        assert_eq!(signature.len(), 65);
+        assert!(verify_evm_signature(&hash, &signature, &signer.address()).is_ok());
+
+        let other = MockEvmSigner::from_seed(b"exsig");
+
+        assert!(verify_evm_signature(&hash, &signature, &other.address()).is_err());
+    }
+
+    //signatures of one scheme do not verify under the other
+    let signature = signer.sign_digest(&digest, DigestScheme::Eip712);
+
+    assert!(verify_evm_signature(
+        &digest.hash(DigestScheme::PersonalSign),
+        &signature,
+        &signer.address()
+    )
+    .is_err());
+}
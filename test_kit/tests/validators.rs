@@ -0,0 +1,136 @@
+#![cfg(feature = "test-sbf")]
+
+use mv3_client::bls::BlsSecretKey;
+use mv3_contract_solana::{
+    bls::{self, BlsKey},
+    error::MessengerError,
+    instruction::SetBlsValidators,
+};
+use mv3_test_kit::{gateway_error, TestKit};
+use solana_program_test::tokio;
+use solana_sdk::signer::Signer;
+
+fn validators(count: u8) -> Vec<BlsSecretKey> {
+    (0..count)
+        .map(|i| BlsSecretKey::from_seed(&[b'v', i]))
+        .collect()
+}
+
+fn bls_key(validator: &BlsSecretKey) -> BlsKey {
+    validator
+        .public_key()
+        .with_possession(&validator.sign_possession())
+        .unwrap()
+}
+
+/// Writes `keys` from `offset` on as the owner of the config
+async fn set_keys(
+    kit: &mut TestKit,
+    offset: u16,
+    keys: Vec<BlsKey>,
+    threshold: u16,
+    enabled: bool,
+) -> Result<(), MessengerError> {
+    let admin = kit.admin.insecure_clone();
+
+    let instruction = kit.gateway.set_bls_validators(
+        &admin.pubkey(),
+        SetBlsValidators {
+            offset,
+            keys,
+            threshold,
+            enabled,
+        },
+    );
+
+    kit.process(&[instruction], &[&admin])
+        .await
+        .map(|_| ())
+        .map_err(|error| gateway_error(&error).unwrap())
+}
+
+#[tokio::test]
+async fn test_set_bls_validators() {
+    let mut kit = TestKit::builder().start().await;
+
+    let keys: Vec<_> = validators(5).iter().map(bls_key).collect();
+
+    //the set is written in two chunks, verification is enabled by the last
+    set_keys(&mut kit, 0, keys[..3].to_vec(), 4, false)
+        .await
+        .unwrap();
+
+    set_keys(&mut kit, 3, keys[3..].to_vec(), 4, true)
+        .await
+        .unwrap();
+
+    let fetcher = kit.fetcher();
+    let validators = kit.gateway.get_validator_set(&fetcher).await.unwrap();
+
+    assert_eq!(
+        validators.bls_keys,
+        keys.iter().map(|key| key.key).collect::<Vec<_>>()
+    );
+    assert_eq!(validators.threshold, 4);
+    assert!(kit
+        .gateway
+        .get_config(&fetcher)
+        .await
+        .unwrap()
+        .bls_enabled());
+
+    assert_eq!(
+        set_keys(&mut kit, 5, vec![], 6, true).await,
+        Err(MessengerError::InvalidValidatorSet)
+    );
+    assert_eq!(
+        set_keys(&mut kit, 5, vec![keys[0].clone()], 3, true).await,
+        Err(MessengerError::InvalidValidatorSet)
+    );
+}
+
+#[tokio::test]
+async fn test_set_bls_validators_requires_possession() {
+    let mut kit = TestKit::builder().start().await;
+
+    let validators = validators(3);
+    let keys: Vec<_> = validators.iter().map(bls_key).collect();
+
+    //a key cancelling the others out of their sum, which its owner holds no secret of
+    let others = bls::add(&keys[0].key, &keys[1].key).unwrap();
+
+    let mut rogue_key = keys[2].clone();
+    rogue_key.key = bls::add(&keys[2].key, &bls::negate(&others)).unwrap();
+
+    assert_eq!(
+        set_keys(
+            &mut kit,
+            0,
+            vec![keys[0].clone(), keys[1].clone(), rogue_key],
+            2,
+            true
+        )
+        .await,
+        Err(MessengerError::InvalidValidatorSet)
+    );
+
+    //the proof of another validator
+    let mut copied = keys[1].clone();
+    copied.possession = keys[0].possession.clone();
+
+    assert_eq!(
+        set_keys(&mut kit, 0, vec![keys[0].clone(), copied], 2, true).await,
+        Err(MessengerError::InvalidValidatorSet)
+    );
+
+    //a signature over another message
+    let mut other_message = keys[0].clone();
+    other_message.possession.signature = validators[0].sign(&[0; 32]);
+
+    assert_eq!(
+        set_keys(&mut kit, 0, vec![other_message], 1, true).await,
+        Err(MessengerError::InvalidValidatorSet)
+    );
+
+    set_keys(&mut kit, 0, keys, 2, true).await.unwrap();
+}